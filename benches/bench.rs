@@ -1,16 +1,330 @@
-use criterion::{black_box, Criterion, criterion_group, criterion_main};
+use criterion::measurement::WallTime;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkGroup, Criterion};
 
-use advent_of_rust_2024::day06::solve_day_06_part_02;
-use advent_of_rust_2024::util::algebra::{cartesian_product_flat_map, cartesian_product_mut_push, cartesian_product_refs};
+use advent_of_rust_2024::util::algebra::{cartesian_product_flat_map, cartesian_product_refs};
 use advent_of_rust_2024::util::file::read_string;
+use advent_of_rust_2024::*;
 
-fn bench_day06_part2(c: &mut Criterion) {
-    let input = read_string("./src/day06/input.txt").unwrap();
+fn to_lines(input: &str) -> Vec<String> {
+    input.lines().map(str::to_string).collect()
+}
 
-    c.bench_function("day06_part_02", |b| {
-        b.iter(|| {
-            let output = solve_day_06_part_02(black_box(input.clone()));
-            black_box(output);
+/// loads `./src/dayNN/input.txt` once and hands it to `benches` as group `dayNN` - a missing input
+/// skips the whole day instead of panicking (inputs aren't part of the repo for everyone)
+fn bench_day<F>(c: &mut Criterion, day: u8, benches: F)
+where
+    F: FnOnce(&mut BenchmarkGroup<WallTime>, &str),
+{
+    let path = format!("./src/day{day:02}/input.txt");
+    let Ok(input) = read_string(&path) else {
+        eprintln!("skipping day {day:02} - no input at {path}");
+        return;
+    };
+
+    let mut group = c.benchmark_group(format!("day{day:02}"));
+    benches(&mut group, &input);
+    group.finish();
+}
+
+fn bench_day01(c: &mut Criterion) {
+    bench_day(c, 1, |g, input| {
+        g.bench_function("part_01", |b| {
+            b.iter(|| day01::solve_day_01_part_01(black_box(to_lines(input))))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day01::solve_day_01_part_02(black_box(to_lines(input))))
+        });
+    });
+}
+
+fn bench_day02(c: &mut Criterion) {
+    bench_day(c, 2, |g, input| {
+        g.bench_function("part_01", |b| {
+            b.iter(|| day02::solve_day_02_part_01(black_box(to_lines(input))))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day02::solve_day_02_part_02(black_box(to_lines(input))))
+        });
+    });
+}
+
+fn bench_day03(c: &mut Criterion) {
+    bench_day(c, 3, |g, input| {
+        g.bench_function("part_01", |b| {
+            b.iter(|| day03::part1::solve_day_03_part_01(black_box(input).chars()))
+        });
+        g.bench_function("part_01_interpreter", |b| {
+            b.iter(|| day03::interpreter::solve_day_03_part_01_interpreter(black_box(input)))
+        });
+        g.bench_function("part_02_starts_with", |b| {
+            b.iter(|| day03::part2_starts_with::solve_day_03_part_02_starts_with(black_box(input)))
+        });
+        g.bench_function("part_02_interpreter", |b| {
+            b.iter(|| day03::interpreter::solve_day_03_part_02_interpreter(black_box(input)))
+        });
+        g.bench_function("part_02_state_machine", |b| {
+            b.iter(|| {
+                day03::part2_state_machine::solve_day_03_part_02_state_machine(
+                    black_box(input).chars(),
+                )
+            })
+        });
+    });
+}
+
+fn bench_day04(c: &mut Criterion) {
+    bench_day(c, 4, |g, input| {
+        g.bench_function("part_01", |b| {
+            b.iter(|| day04::solve_day_04_part_01(black_box(input.to_string())))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day04::solve_day_04_part_02(black_box(input.to_string())))
+        });
+    });
+}
+
+fn bench_day05(c: &mut Criterion) {
+    bench_day(c, 5, |g, input| {
+        g.bench_function("part_01", |b| {
+            b.iter(|| day05::solve_day_05_part_01(black_box(to_lines(input))))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day05::solve_day_05_part_02(black_box(to_lines(input))))
+        });
+    });
+}
+
+fn bench_day06(c: &mut Criterion) {
+    bench_day(c, 6, |g, input| {
+        g.sample_size(10);
+        g.bench_function("part_01", |b| {
+            b.iter(|| day06::solve_day_06_part_01(black_box(input.to_string())))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day06::solve_day_06_part_02(black_box(input.to_string())))
+        });
+        g.bench_function("part_02_fast", |b| {
+            b.iter(|| day06::solve_day_06_part_02_fast(black_box(input.to_string())))
+        });
+    });
+}
+
+fn bench_day07(c: &mut Criterion) {
+    bench_day(c, 7, |g, input| {
+        g.sample_size(10);
+        g.bench_function("part_01", |b| {
+            b.iter(|| day07::part1::solve_day_07(black_box(input.to_string()), false))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day07::part1::solve_day_07(black_box(input.to_string()), true))
+        });
+    });
+}
+
+fn bench_day08(c: &mut Criterion) {
+    bench_day(c, 8, |g, input| {
+        g.bench_function("part_01", |b| {
+            b.iter(|| day08::part1::solve_day_08_part_01(black_box(input.to_string())))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day08::part2::solve_day_08_part_02(black_box(input.to_string())))
+        });
+    });
+}
+
+fn bench_day09(c: &mut Criterion) {
+    bench_day(c, 9, |g, input| {
+        g.bench_function("part_01", |b| {
+            b.iter(|| day09::part1::solve_day_09_part_01(black_box(input.trim().to_string())))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day09::part2::solve_day_09_part_02(black_box(input.trim().to_string())))
+        });
+    });
+}
+
+fn bench_day10(c: &mut Criterion) {
+    bench_day(c, 10, |g, input| {
+        g.bench_function("part_01", |b| {
+            b.iter(|| day10::part1::solve_day_10_part_01(black_box(input.to_string())))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day10::part2::solve_day_10_part_02(black_box(input.to_string())))
+        });
+    });
+}
+
+fn bench_day11(c: &mut Criterion) {
+    bench_day(c, 11, |g, input| {
+        g.bench_function("part_01", |b| {
+            b.iter(|| day11::solve_day_11(black_box(input), 25))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day11::solve_day_11(black_box(input), 75))
+        });
+    });
+}
+
+fn bench_day12(c: &mut Criterion) {
+    bench_day(c, 12, |g, input| {
+        g.bench_function("part_01", |b| {
+            b.iter(|| day12::part1::solve_day_12_part_01(black_box(input)))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day12::part2::solve_day_12_part_02(black_box(input)))
+        });
+    });
+}
+
+fn bench_day13(c: &mut Criterion) {
+    bench_day(c, 13, |g, input| {
+        g.bench_function("part_01", |b| {
+            b.iter(|| day13::part1::solve_day_13_part_01(black_box(input)))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day13::part2::solve_day_13_part_02(black_box(input)))
+        });
+    });
+}
+
+fn bench_day14(c: &mut Criterion) {
+    bench_day(c, 14, |g, input| {
+        g.bench_function("part_01", |b| {
+            b.iter(|| day14::part1::solve_day_14_part_01(black_box(input), 101, 103, 100))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day14::part2::solve_day_14_part_02(black_box(input)))
+        });
+    });
+}
+
+fn bench_day15(c: &mut Criterion) {
+    bench_day(c, 15, |g, input| {
+        g.bench_function("part_01", |b| {
+            b.iter(|| day15::part1::solve_day_15_part_01(black_box(input)))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day15::part2::solve_day_15_part_02(black_box(input)))
+        });
+    });
+}
+
+fn bench_day16(c: &mut Criterion) {
+    bench_day(c, 16, |g, input| {
+        g.sample_size(10);
+        g.bench_function("part_01", |b| {
+            b.iter(|| day16::part1::solve_day_16_part_01(black_box(input)))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day16::part2::solve_day_16_part_02(black_box(input)))
+        });
+    });
+}
+
+fn bench_day17(c: &mut Criterion) {
+    bench_day(c, 17, |g, input| {
+        g.bench_function("part_01", |b| {
+            b.iter(|| day17::part1::solve_day_17_part_01(black_box(input)))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| {
+                day17::part2::parse(black_box(input))
+                    .map(|machine| day17::part2::solve_day_17_part_02(&machine))
+            })
+        });
+    });
+}
+
+fn bench_day18(c: &mut Criterion) {
+    bench_day(c, 18, |g, input| {
+        g.bench_function("part_01", |b| {
+            b.iter(|| day18::part1::solve_day_18_part_01(black_box(input), 71, 1024))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day18::part2::solve_day_18_part_02(black_box(input), 71))
+        });
+    });
+}
+
+fn bench_day19(c: &mut Criterion) {
+    bench_day(c, 19, |g, input| {
+        g.bench_function("part_01", |b| {
+            b.iter(|| day19::part1::solve_day_19_part_01(black_box(input)))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day19::part2::solve_day_19_part_02(black_box(input)))
+        });
+    });
+}
+
+fn bench_day20(c: &mut Criterion) {
+    bench_day(c, 20, |g, input| {
+        g.sample_size(10);
+        g.bench_function("part_01", |b| {
+            b.iter(|| day20::part1::solve_day_20_part_01(black_box(input), 100))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day20::part2::solve_day_20_part_02(black_box(input), 100))
+        });
+    });
+}
+
+fn bench_day21(c: &mut Criterion) {
+    bench_day(c, 21, |g, input| {
+        let input = input.trim();
+        g.bench_function("part_01_slow", |b| {
+            b.iter(|| day21::part1::solve_day_21_slow(black_box(input), 2))
+        });
+        g.bench_function("part_01_fast", |b| {
+            b.iter(|| day21::part2::solve_day_21_fast(black_box(input), 2))
+        });
+        g.bench_function("part_02_fast", |b| {
+            b.iter(|| day21::part2::solve_day_21_fast(black_box(input), 25))
+        });
+    });
+}
+
+fn bench_day22(c: &mut Criterion) {
+    bench_day(c, 22, |g, input| {
+        g.sample_size(10);
+        g.bench_function("part_01", |b| {
+            b.iter(|| day22::part1::solve_day_22_part_01(black_box(input)))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day22::part2::solve_day_22_part_02(black_box(input)))
+        });
+    });
+}
+
+fn bench_day23(c: &mut Criterion) {
+    bench_day(c, 23, |g, input| {
+        g.bench_function("part_01", |b| {
+            b.iter(|| day23::part1::solve_day_23_part_01(black_box(input)))
+        });
+        g.bench_function("part_02_hacky", |b| {
+            b.iter(|| day23::part2::solve_day_23_part_02_with_hacky_shit_solution(black_box(input)))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day23::part2::solve_day_23_part_02(black_box(input)))
+        });
+    });
+}
+
+fn bench_day24(c: &mut Criterion) {
+    bench_day(c, 24, |g, input| {
+        g.bench_function("part_01", |b| {
+            b.iter(|| day24::part1::solve_day_24_part_01(black_box(input)))
+        });
+        g.bench_function("part_02", |b| {
+            b.iter(|| day24::part2::solve_day_24_part_02(black_box(input)))
+        });
+    });
+}
+
+fn bench_day25(c: &mut Criterion) {
+    bench_day(c, 25, |g, input| {
+        g.bench_function("part_01", |b| {
+            b.iter(|| day25::part1::solve_day_25_part_01(black_box(input)))
         });
     });
 }
@@ -19,7 +333,9 @@ fn bench_cart_product_flat_map(c: &mut Criterion) {
     bench_cartesian_product(c, "cart_prod_flat_map", cartesian_product_flat_map);
 }
 
+#[allow(deprecated)]
 fn bench_cart_product_mut_push(c: &mut Criterion) {
+    use advent_of_rust_2024::util::algebra::cartesian_product_mut_push;
     bench_cartesian_product(c, "cart_prod_mut_push", cartesian_product_mut_push);
 }
 
@@ -36,10 +352,10 @@ fn bench_cart_product_refs(c: &mut Criterion) {
 }
 
 fn bench_cartesian_product<F, I>(c: &mut Criterion, method_name: &str, func: F)
-    where
-        F: Fn(Vec<i32>, Vec<i32>) -> I,
-        I: Iterator<Item=(i32, i32)>,
-        i32: Clone
+where
+    F: Fn(Vec<i32>, Vec<i32>) -> I,
+    I: Iterator<Item = (i32, i32)>,
+    i32: Clone,
 {
     let vec1 = (0..10000).collect::<Vec<_>>();
     let vec2 = (0..10000).collect::<Vec<_>>();
@@ -55,11 +371,39 @@ fn bench_cartesian_product<F, I>(c: &mut Criterion, method_name: &str, func: F)
 }
 
 criterion_group!(
-    benches,
-    // bench_day06_part2,
+    days,
+    bench_day01,
+    bench_day02,
+    bench_day03,
+    bench_day04,
+    bench_day05,
+    bench_day06,
+    bench_day07,
+    bench_day08,
+    bench_day09,
+    bench_day10,
+    bench_day11,
+    bench_day12,
+    bench_day13,
+    bench_day14,
+    bench_day15,
+    bench_day16,
+    bench_day17,
+    bench_day18,
+    bench_day19,
+    bench_day20,
+    bench_day21,
+    bench_day22,
+    bench_day23,
+    bench_day24,
+    bench_day25
+);
+
+criterion_group!(
+    algebra,
     bench_cart_product_flat_map,
     bench_cart_product_mut_push,
     bench_cart_product_refs
 );
 
-criterion_main!(benches);
+criterion_main!(days, algebra);
@@ -0,0 +1,210 @@
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::util::point::Point;
+
+const ARTIFACT_PREFIX: &str = "[artifact] ";
+
+/// the result of a puzzle independent of the day's own return type
+///
+/// `Display` only writes the value - store the [`AnswerKind`] next to it and read it back with
+/// [`Answer::parse_as`] to get the same answer again
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    /// any integer answer - `u128`s beyond `i128::MAX` end up as [`Answer::Text`] (same as parsing them)
+    Integer(i128),
+    /// comma separated sequences, passwords, ...
+    Text(String),
+    /// a position on the puzzle map - written as `x,y` like in the puzzle texts
+    Coordinate(Point),
+    /// nothing to print - the puzzle produced a file (e.g. images) that has to be inspected by hand
+    Artifact(String),
+}
+
+/// which [`Answer`] variant a value is - `Display` drops it, so it has to be stored separately
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnswerKind {
+    Integer,
+    Text,
+    Coordinate,
+    Artifact,
+}
+
+impl Answer {
+    pub fn kind(&self) -> AnswerKind {
+        match self {
+            Answer::Integer(_) => AnswerKind::Integer,
+            Answer::Text(_) => AnswerKind::Text,
+            Answer::Coordinate(_) => AnswerKind::Coordinate,
+            Answer::Artifact(_) => AnswerKind::Artifact,
+        }
+    }
+
+    /// reads a written down answer back as `kind` - the counterpart of `Display`, so
+    /// `Answer::parse_as(a.kind(), &a.to_string()) == Some(a)` for every answer
+    ///
+    /// `None` if the text doesn't fit `kind`, trailing line breaks are ignored
+    pub fn parse_as(kind: AnswerKind, s: &str) -> Option<Answer> {
+        let s = s.trim_end_matches(['\r', '\n']);
+
+        match kind {
+            AnswerKind::Integer => s.trim().parse().ok().map(Answer::Integer),
+            AnswerKind::Text => Some(Answer::Text(s.to_string())),
+            AnswerKind::Coordinate => {
+                let (x, y) = s.trim().split_once(',')?;
+                Some(Answer::Coordinate(Point::new(
+                    x.parse().ok()?,
+                    y.parse().ok()?,
+                )))
+            }
+            AnswerKind::Artifact => s
+                .strip_prefix(ARTIFACT_PREFIX)
+                .map(|location| Answer::Artifact(location.to_string())),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(v) => write!(f, "{v}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Coordinate(p) => write!(f, "{},{}", p.x, p.y),
+            Answer::Artifact(location) => write!(f, "{ARTIFACT_PREFIX}{location}"),
+        }
+    }
+}
+
+/// guesses the kind from the text - never fails, everything that isn't recognized otherwise is
+/// [`Answer::Text`]
+///
+/// texts that look like numbers or coordinates come back as those, use [`Answer::parse_as`] when
+/// the kind is known
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if let Ok(v) = s.parse::<i128>() {
+            return Ok(Answer::Integer(v));
+        }
+        if let Some((x, y)) = s.split_once(',') {
+            if let (Ok(x), Ok(y)) = (x.parse::<i32>(), y.parse::<i32>()) {
+                return Ok(Answer::Coordinate(Point::new(x, y)));
+            }
+        }
+        if let Some(location) = s.strip_prefix(ARTIFACT_PREFIX) {
+            return Ok(Answer::Artifact(location.to_string()));
+        }
+
+        Ok(Answer::Text(s.to_string()))
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        i128::try_from(value)
+            .map(Answer::Integer)
+            .unwrap_or_else(|_| Answer::Text(value.to_string()))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl From<Point> for Answer {
+    fn from(value: Point) -> Self {
+        Answer::Coordinate(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_round_trip() {
+        let answers = [
+            Answer::from(42u32),
+            Answer::from(-7i64),
+            Answer::from(u128::MAX),
+            Answer::from("7,1,3,4,1,2,6,7,1"),
+            Answer::from("42"),
+            Answer::from("4,6"),
+            Answer::from(" padded "),
+            Answer::from(Point::new(52, 5)),
+            Answer::from(Point::new(-1, 0)),
+            Answer::Artifact("./tmp".to_string()),
+        ];
+
+        for answer in answers {
+            let text = answer.to_string();
+            assert_eq!(Some(answer.clone()), Answer::parse_as(answer.kind(), &text));
+            assert_eq!(
+                Some(answer.clone()),
+                Answer::parse_as(answer.kind(), &format!("{text}\n"))
+            );
+        }
+    }
+
+    #[test]
+    fn should_not_parse_as_wrong_kind() {
+        assert_eq!(None, Answer::parse_as(AnswerKind::Integer, "4,6"));
+        assert_eq!(None, Answer::parse_as(AnswerKind::Coordinate, "42"));
+        assert_eq!(None, Answer::parse_as(AnswerKind::Coordinate, "a,b"));
+        assert_eq!(None, Answer::parse_as(AnswerKind::Artifact, "./tmp"));
+    }
+
+    #[test]
+    fn should_guess_kind() {
+        assert_eq!(
+            Answer::Integer(42),
+            Answer::from("42").to_string().parse().unwrap()
+        );
+        assert_eq!(
+            Answer::Coordinate(Point::new(4, 6)),
+            Answer::from("4,6").to_string().parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn should_compare_across_widths() {
+        assert_eq!(Answer::from(1555u32), Answer::from(1555u128));
+        assert_eq!(Answer::from(1555usize), "1555".parse().unwrap());
+    }
+
+    #[test]
+    fn should_parse() {
+        assert_eq!(
+            Answer::Coordinate(Point::new(6, 1)),
+            "6,1\n".parse().unwrap()
+        );
+        assert_eq!(Answer::from("co,de,ka,ta"), "co,de,ka,ta".parse().unwrap());
+        assert_eq!(Answer::Integer(-3), "-3".parse().unwrap());
+    }
+}
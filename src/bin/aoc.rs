@@ -0,0 +1,395 @@
+use std::io::{self, Read};
+use std::panic::{self, AssertUnwindSafe};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use advent_of_rust_2024::solver::{PLAYBACK_DAYS, RECORDING_DAYS};
+use advent_of_rust_2024::util::error::ParseError;
+use advent_of_rust_2024::util::file::read_string;
+use advent_of_rust_2024::{playback, recording, solver, solvers, Answer, Solver};
+
+const USAGE: &str = "usage: aoc run --day <1-25> --part <1|2> [--input <path>|-]
+       aoc run --all
+       aoc watch --day <6|14|15> [--input <path>|-]
+       aoc export --day <14|15> [--input <path>|-] [--out <dir>]";
+
+/// where `aoc export` writes to without `--out`
+const DEFAULT_OUT: &str = "./tmp";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    /// `input` of `None` falls back to `./src/dayNN/input.txt`, `Some("-")` reads stdin
+    Single {
+        day: u8,
+        part: u8,
+        input: Option<String>,
+    },
+    All,
+    /// plays the simulation of the day in the terminal
+    Watch {
+        day: u8,
+        input: Option<String>,
+    },
+    /// writes the frames of the day's simulation as bitmaps and one gif into `out`
+    Export {
+        day: u8,
+        input: Option<String>,
+        out: String,
+    },
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let command = match args.next() {
+        Some(command) if ["run", "watch", "export"].contains(&command.as_str()) => command,
+        Some(other) => return Err(format!("unknown command '{other}'")),
+        None => return Err("missing command".to_string()),
+    };
+
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut out = None;
+    let mut all = false;
+
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| args.next().ok_or(format!("{flag} needs a value"));
+        match arg.as_str() {
+            "--day" => day = Some(parse_number(&value("--day")?, 1..=25, "--day")?),
+            "--part" => part = Some(parse_number(&value("--part")?, 1..=2, "--part")?),
+            "--input" => input = Some(value("--input")?),
+            "--out" => out = Some(value("--out")?),
+            "--all" => all = true,
+            other => return Err(format!("unknown argument '{other}'")),
+        }
+    }
+
+    if command == "export" {
+        return match (day, part, all) {
+            (Some(day), None, false) if RECORDING_DAYS.contains(&day) => Ok(Command::Export {
+                day,
+                input,
+                out: out.unwrap_or(DEFAULT_OUT.to_string()),
+            }),
+            (Some(day), None, false) => Err(format!(
+                "there is nothing to export for day {day} (only {RECORDING_DAYS:?})"
+            )),
+            _ => Err("export needs --day (and no --part or --all)".to_string()),
+        };
+    }
+    if out.is_some() {
+        return Err("--out only works with export".to_string());
+    }
+
+    if command == "watch" {
+        return match (day, part, all) {
+            (Some(day), None, false) if PLAYBACK_DAYS.contains(&day) => {
+                Ok(Command::Watch { day, input })
+            }
+            (Some(day), None, false) => Err(format!(
+                "there is nothing to watch for day {day} (only {PLAYBACK_DAYS:?})"
+            )),
+            _ => Err("watch needs --day (and no --part or --all)".to_string()),
+        };
+    }
+
+    if all {
+        return match (day, part, input) {
+            (None, None, None) => Ok(Command::All),
+            _ => Err("--all can't be combined with --day, --part or --input".to_string()),
+        };
+    }
+
+    match (day, part) {
+        (Some(day), Some(part)) => Ok(Command::Single { day, part, input }),
+        _ => Err("--day and --part are required (or --all)".to_string()),
+    }
+}
+
+fn parse_number(
+    value: &str,
+    range: std::ops::RangeInclusive<u8>,
+    flag: &str,
+) -> Result<u8, String> {
+    value
+        .parse::<u8>()
+        .ok()
+        .filter(|n| range.contains(n))
+        .ok_or(format!(
+            "{flag} expects a number in {range:?} but got '{value}'"
+        ))
+}
+
+fn default_input_path(day: u8) -> String {
+    format!("./src/day{day:02}/input.txt")
+}
+
+fn read_input(day: u8, input: Option<&str>) -> io::Result<String> {
+    match input {
+        Some("-") => {
+            let mut content = String::new();
+            io::stdin().read_to_string(&mut content)?;
+            Ok(content)
+        }
+        Some(path) => read_string(path),
+        None => read_string(default_input_path(day)),
+    }
+}
+
+#[derive(Debug)]
+enum Failure {
+    /// the input is malformed - the error says where
+    Input(ParseError),
+    /// the input parsed fine, but the solver itself gave up
+    Panic,
+}
+
+/// malformed input is reported as [`ParseError`] - a panic while solving is still caught and
+/// turned into an `Err` (the panic message itself is printed to stderr by the default hook)
+fn solve_timed(solver: &dyn Solver, input: &str) -> Result<(Answer, Duration), Failure> {
+    let start = Instant::now();
+    match panic::catch_unwind(AssertUnwindSafe(|| solver.solve(input))) {
+        Ok(Ok(answer)) => Ok((answer, start.elapsed())),
+        Ok(Err(e)) => Err(Failure::Input(e)),
+        Err(_) => Err(Failure::Panic),
+    }
+}
+
+fn run_single(day: u8, part: u8, input: Option<&str>) -> ExitCode {
+    let Some(solver) = solver(day, part) else {
+        eprintln!("no solution registered for day {day} part {part}");
+        return ExitCode::FAILURE;
+    };
+
+    let input = match read_input(day, input) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("failed to read input: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match solve_timed(solver, &input) {
+        Ok((answer, _)) => {
+            println!("{answer}");
+            ExitCode::SUCCESS
+        }
+        Err(Failure::Input(e)) => {
+            eprintln!("invalid input - {e}");
+            ExitCode::FAILURE
+        }
+        Err(Failure::Panic) => {
+            eprintln!("failed to solve day {day} part {part}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_all() -> ExitCode {
+    let mut rows = Vec::new();
+    let mut failed = false;
+    let mut total = Duration::ZERO;
+
+    for solver in solvers() {
+        let (day, part) = (solver.day(), solver.part());
+        let row = match read_input(day, None) {
+            Err(_) => (day, part, "skipped (no input)".to_string(), None),
+            Ok(input) => match solve_timed(solver, &input) {
+                Ok((answer, elapsed)) => {
+                    total += elapsed;
+                    (day, part, answer.to_string(), Some(elapsed))
+                }
+                Err(Failure::Input(e)) => {
+                    failed = true;
+                    (day, part, format!("invalid input - {e}"), None)
+                }
+                Err(Failure::Panic) => {
+                    failed = true;
+                    (day, part, "failed".to_string(), None)
+                }
+            },
+        };
+        rows.push(row);
+    }
+
+    println!(
+        "{:>3} {:>4}  {:<48} {:>12}",
+        "day", "part", "answer", "time"
+    );
+    for (day, part, answer, elapsed) in rows {
+        let elapsed = elapsed.map_or("-".to_string(), format_duration);
+        println!("{day:>3} {part:>4}  {answer:<48} {elapsed:>12}");
+    }
+    println!("{:>58} {:>12}", "total", format_duration(total));
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn watch(day: u8, input: Option<&str>) -> ExitCode {
+    let input = match read_input(day, input) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("failed to read input: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match playback(day, &input) {
+        Some(Ok(mut playback)) => playback.watch(),
+        Some(Err(e)) => {
+            eprintln!("invalid input - {e}");
+            return ExitCode::FAILURE;
+        }
+        None => {
+            eprintln!("there is nothing to watch for day {day}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("failed to play back day {day} - {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn export(day: u8, input: Option<&str>, out: &str) -> ExitCode {
+    let input = match read_input(day, input) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("failed to read input: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let recorder = match recording(day, &input) {
+        Some(Ok(recorder)) => recorder,
+        Some(Err(e)) => {
+            eprintln!("invalid input - {e}");
+            return ExitCode::FAILURE;
+        }
+        None => {
+            eprintln!("there is nothing to export for day {day}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let out = std::path::Path::new(out);
+    let written = recorder
+        .save_gif(out.join(format!("day{day:02}.gif")), 50)
+        .and_then(|_| recorder.save_sequence(out, "bmp"));
+    match written {
+        Ok(frames) => {
+            println!("{} frames written to {}", frames.len(), out.display());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("failed to export day {day} - {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn format_duration(d: Duration) -> String {
+    format!("{:.3}ms", d.as_secs_f64() * 1000.0)
+}
+
+fn main() -> ExitCode {
+    match parse_args(std::env::args().skip(1)) {
+        Ok(Command::Single { day, part, input }) => run_single(day, part, input.as_deref()),
+        Ok(Command::All) => run_all(),
+        Ok(Command::Watch { day, input }) => watch(day, input.as_deref()),
+        Ok(Command::Export { day, input, out }) => export(day, input.as_deref(), &out),
+        Err(e) => {
+            eprintln!("{e}\n{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> impl Iterator<Item = String> + '_ {
+        line.split_whitespace().map(str::to_string)
+    }
+
+    #[test]
+    fn should_parse_single() {
+        assert_eq!(
+            Ok(Command::Single {
+                day: 16,
+                part: 2,
+                input: Some("-".to_string())
+            }),
+            parse_args(args("run --day 16 --part 2 --input -"))
+        );
+        assert_eq!(
+            Ok(Command::Single {
+                day: 1,
+                part: 1,
+                input: None
+            }),
+            parse_args(args("run --part 1 --day 1"))
+        );
+    }
+
+    #[test]
+    fn should_parse_all() {
+        assert_eq!(Ok(Command::All), parse_args(args("run --all")));
+    }
+
+    #[test]
+    fn should_parse_watch() {
+        assert_eq!(
+            Ok(Command::Watch {
+                day: 15,
+                input: None
+            }),
+            parse_args(args("watch --day 15"))
+        );
+        assert!(parse_args(args("watch --day 1")).is_err());
+        assert!(parse_args(args("watch --day 6 --part 1")).is_err());
+        assert!(parse_args(args("watch --all")).is_err());
+    }
+
+    #[test]
+    fn should_parse_export() {
+        assert_eq!(
+            Ok(Command::Export {
+                day: 14,
+                input: None,
+                out: DEFAULT_OUT.to_string()
+            }),
+            parse_args(args("export --day 14"))
+        );
+        assert_eq!(
+            Ok(Command::Export {
+                day: 14,
+                input: Some("-".to_string()),
+                out: "frames".to_string()
+            }),
+            parse_args(args("export --out frames --day 14 --input -"))
+        );
+        assert!(parse_args(args("export --day 1")).is_err());
+        assert!(parse_args(args("export --day 14 --part 2")).is_err());
+        assert!(parse_args(args("run --day 14 --part 2 --out frames")).is_err());
+    }
+
+    #[test]
+    fn should_reject_bad_args() {
+        assert!(parse_args(args("")).is_err());
+        assert!(parse_args(args("walk --all")).is_err());
+        assert!(parse_args(args("run --day 26 --part 1")).is_err());
+        assert!(parse_args(args("run --day 1 --part 3")).is_err());
+        assert!(parse_args(args("run --day 1")).is_err());
+        assert!(parse_args(args("run --day")).is_err());
+        assert!(parse_args(args("run --all --day 1")).is_err());
+    }
+}
@@ -1,35 +1,175 @@
-fn get_lists_from_input(input: Vec<String>) -> (Vec<u32>, Vec<u32>) {
-    input
-        .iter()
-        .map(|line| {
-            let numbers: Vec<u32> = line
-                .split_whitespace()
-                .map(|num| num.parse::<u32>().expect("Invalid number"))
-                .collect();
-            assert_eq!(numbers.len(), 2);
-            (numbers[0], numbers[1])
-        })
-        .unzip()
+use std::collections::BTreeMap;
+
+use crate::util::error::ParseError;
+use crate::util::parse::Line;
+
+const DAY: u8 = 1;
+
+/// one column of location ids per group of historians - separated by whitespace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lists {
+    columns: Vec<Vec<u32>>,
+}
+
+/// two columns sorted and paired up - smallest with smallest and so on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Distance {
+    /// `(left, right, distance)` in sorted order
+    pub pairs: Vec<(u32, u32, u64)>,
+    pub total: u64,
 }
 
-pub fn solve_day_01_part_01(input: Vec<String>) -> u32 {
-    let (mut l1, mut l2): (Vec<u32>, Vec<u32>) = get_lists_from_input(input);
+/// a value that shows up in both columns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub value: u32,
+    pub left_count: usize,
+    pub right_count: usize,
+    pub contribution: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Similarity {
+    /// ascending by value
+    pub matches: Vec<Match>,
+    pub total: u64,
+}
+
+impl Lists {
+    /// any number of columns, but the same on every line - blank lines are skipped
+    pub fn parse(input: &[String]) -> Result<Lists, ParseError> {
+        let mut columns: Vec<Vec<u32>> = Vec::new();
+        for (i, text) in input.iter().enumerate() {
+            let line = Line {
+                day: DAY,
+                number: i + 1,
+                text,
+            };
+            let numbers: Vec<u32> = line.numbers(text, &[' ', '\t'])?;
+            if numbers.is_empty() {
+                continue;
+            }
+            if columns.is_empty() {
+                columns = vec![Vec::new(); numbers.len()];
+            }
+            if numbers.len() != columns.len() {
+                let reason = format!(
+                    "expected {} numbers but got {}",
+                    columns.len(),
+                    numbers.len()
+                );
+                return Err(line.error(reason));
+            }
+            columns
+                .iter_mut()
+                .zip(numbers)
+                .for_each(|(column, n)| column.push(n));
+        }
+        Ok(Lists { columns })
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// panics for a column that doesn't exist
+    pub fn column(&self, i: usize) -> &[u32] {
+        &self.columns[i]
+    }
+
+    /// all `(left, right)` column pairs with `left < right`
+    pub fn column_pairs(&self) -> impl Iterator<Item = (usize, usize)> {
+        let n = self.columns();
+        (0..n).flat_map(move |left| (left + 1..n).map(move |right| (left, right)))
+    }
+
+    /// `|left - right|` summed up over the sorted columns
+    pub fn distance(&self, left: usize, right: usize) -> Distance {
+        self.distance_by(left, right, |a, b| a.abs_diff(b) as u64)
+    }
+
+    /// like [Lists::distance] but with any `metric` for a pair of values
+    pub fn distance_by(
+        &self,
+        left: usize,
+        right: usize,
+        metric: impl Fn(u32, u32) -> u64,
+    ) -> Distance {
+        let sorted = |i: usize| {
+            let mut column = self.column(i).to_vec();
+            column.sort_unstable();
+            column
+        };
+        let pairs: Vec<_> = sorted(left)
+            .into_iter()
+            .zip(sorted(right))
+            .map(|(a, b)| (a, b, metric(a, b)))
+            .collect();
+        let total = pairs.iter().map(|&(_, _, d)| d).sum();
+
+        Distance { pairs, total }
+    }
+
+    /// every left value times how often it shows up on the right
+    pub fn similarity(&self, left: usize, right: usize) -> Similarity {
+        self.similarity_by(left, right, |value, left_count, right_count| {
+            value as u64 * left_count as u64 * right_count as u64
+        })
+    }
+
+    /// like [Lists::similarity] but with any `weight(value, left_count, right_count)` - only
+    /// called for values in both columns, as counting both sides makes it `O(n log n)`
+    pub fn similarity_by(
+        &self,
+        left: usize,
+        right: usize,
+        weight: impl Fn(u32, usize, usize) -> u64,
+    ) -> Similarity {
+        let right_counts = histogram(self.column(right));
+        let matches: Vec<_> = histogram(self.column(left))
+            .into_iter()
+            .filter_map(|(value, left_count)| {
+                let right_count = *right_counts.get(&value)?;
+                Some(Match {
+                    value,
+                    left_count,
+                    right_count,
+                    contribution: weight(value, left_count, right_count),
+                })
+            })
+            .collect();
+        let total = matches.iter().map(|m| m.contribution).sum();
 
-    l1.sort();
-    l2.sort();
+        Similarity { matches, total }
+    }
+}
 
-    l1.iter()
-        .zip(l2.iter())
-        .map(|(a, b)| a.abs_diff(*b))
-        .sum()
+fn histogram(values: &[u32]) -> BTreeMap<u32, usize> {
+    let mut counts = BTreeMap::new();
+    values
+        .iter()
+        .for_each(|&v| *counts.entry(v).or_insert(0) += 1);
+    counts
 }
 
-pub fn solve_day_01_part_02(input: Vec<String>) -> u32 {
-    let (l1, l2): (Vec<u32>, Vec<u32>) = get_lists_from_input(input);
+/// the puzzle only ever compares the first two columns
+fn first_two_columns(input: Vec<String>) -> Result<Lists, ParseError> {
+    let lists = Lists::parse(&input)?;
+    match lists.columns() {
+        0 | 1 => Err(ParseError::new(
+            DAY,
+            format!("expected 2 columns but got {}", lists.columns()),
+        )),
+        _ => Ok(lists),
+    }
+}
 
-    l1.iter()
-        .map(|l1_value| l2.iter().filter(|l2_value| **l2_value == *l1_value).count() as u32 * l1_value)
-        .sum()
+pub fn solve_day_01_part_01(input: Vec<String>) -> Result<u64, ParseError> {
+    Ok(first_two_columns(input)?.distance(0, 1).total)
+}
+
+pub fn solve_day_01_part_02(input: Vec<String>) -> Result<u64, ParseError> {
+    Ok(first_two_columns(input)?.similarity(0, 1).total)
 }
 
 #[cfg(test)]
@@ -41,7 +181,7 @@ mod tests {
     #[test]
     fn should_solve_day_01_part_01() {
         match read_lines("./src/day01/input.txt") {
-            Ok(lines) => println!("Solution: {}", solve_day_01_part_01(lines)),
+            Ok(lines) => println!("Solution: {}", solve_day_01_part_01(lines).unwrap()),
             Err(e) => println!("Failed to parse: {}", e),
         }
     }
@@ -49,9 +189,71 @@ mod tests {
     #[test]
     fn should_solve_day_01_part_02() {
         match read_lines("./src/day01/input.txt") {
-            Ok(lines) => println!("Solution: {}", solve_day_01_part_02(lines)),
+            Ok(lines) => println!("Solution: {}", solve_day_01_part_02(lines).unwrap()),
             Err(e) => println!("Failed to parse: {}", e),
         }
     }
-}
 
+    #[test]
+    fn should_report_bad_line() {
+        let lines = vec!["3   4".to_string(), "4   x3".to_string(), "2".to_string()];
+
+        let e = solve_day_01_part_01(lines.clone()).unwrap_err();
+        assert_eq!(
+            "day 01 (line 2, column 5): 'x3' is not a valid number",
+            e.to_string()
+        );
+
+        let e = solve_day_01_part_01(vec![lines[0].clone(), lines[2].clone()]).unwrap_err();
+        assert_eq!(
+            "day 01 (line 2): expected 2 numbers but got 1",
+            e.to_string()
+        );
+
+        let e = solve_day_01_part_02(lines[2..].to_vec()).unwrap_err();
+        assert_eq!("day 01: expected 2 columns but got 1", e.to_string());
+    }
+
+    fn sample() -> Vec<String> {
+        [
+            "3   4   1",
+            "4   3   3",
+            "2   5   3",
+            "1   3   7",
+            "3   9   2",
+            "3   3   3",
+        ]
+        .map(str::to_string)
+        .to_vec()
+    }
+
+    #[test]
+    fn should_solve_day_01_sample() {
+        assert_eq!(11, solve_day_01_part_01(sample()).unwrap());
+        assert_eq!(31, solve_day_01_part_02(sample()).unwrap());
+    }
+
+    #[test]
+    fn should_reconcile_any_two_columns() {
+        let lists = Lists::parse(&sample()).unwrap();
+
+        assert_eq!(
+            vec![(0, 1), (0, 2), (1, 2)],
+            lists.column_pairs().collect::<Vec<_>>()
+        );
+        // 1 2 3 3 3 4 vs 1 2 3 3 3 7
+        assert_eq!(3, lists.distance(0, 2).total);
+        assert_eq!((4, 7, 3), lists.distance(0, 2).pairs[5]);
+        assert_eq!(1, lists.distance_by(0, 2, |a, b| (a != b) as u64).total);
+
+        let similarity = lists.similarity(1, 2);
+        let three = Match {
+            value: 3,
+            left_count: 3,
+            right_count: 3,
+            contribution: 27,
+        };
+        assert_eq!(vec![three], similarity.matches);
+        assert_eq!(27, similarity.total);
+    }
+}
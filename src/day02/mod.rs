@@ -1,98 +1,167 @@
-use crate::day02::Direction::{Decreasing, Increasing, Undecided};
+use crate::util::error::{parse_number, ParseError};
 
-#[derive(Debug, Copy, Clone)]
-enum Direction {
+const DAY: u8 = 2;
+
+/// which way the levels of a report have to go
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Trend {
     Increasing,
     Decreasing,
-    Undecided,
+    /// all increasing or all decreasing - whichever needs fewer removals
+    Either,
+    /// every step may go up or down
+    Unrestricted,
+}
+
+/// what counts as a safe report - the puzzle's rules are [ReportPolicy::default]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ReportPolicy {
+    min_step: u32,
+    max_step: u32,
+    trend: Trend,
+    removable: usize,
+}
+
+/// `removed` are the indices of the levels the dampener took out - always as few as possible
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assessment {
+    pub safe: bool,
+    pub removed: Vec<usize>,
+}
+
+impl Default for ReportPolicy {
+    /// steps of 1 to 3 in one direction, no dampener
+    fn default() -> Self {
+        ReportPolicy {
+            min_step: 1,
+            max_step: 3,
+            trend: Trend::Either,
+            removable: 0,
+        }
+    }
 }
 
-static DEBUG: bool = false;
+impl ReportPolicy {
+    /// the allowed difference between two adjacent levels (in the direction of the trend)
+    pub fn steps(self, min_step: u32, max_step: u32) -> Self {
+        assert!(min_step <= max_step, "min_step can't be above max_step");
+        ReportPolicy {
+            min_step,
+            max_step,
+            ..self
+        }
+    }
+
+    pub fn trend(self, trend: Trend) -> Self {
+        ReportPolicy { trend, ..self }
+    }
+
+    /// the problem dampener - up to `k` levels may be removed from a report
+    pub fn removable(self, k: usize) -> Self {
+        ReportPolicy {
+            removable: k,
+            ..self
+        }
+    }
+
+    /// `O(n * k)` - for every level the fewest removals that end a valid sequence right there
+    pub fn assess(&self, report: &[i32]) -> Assessment {
+        let trends = match self.trend {
+            Trend::Either => vec![Trend::Increasing, Trend::Decreasing],
+            trend => vec![trend],
+        };
+        trends
+            .into_iter()
+            .filter_map(|trend| self.fewest_removals(report, trend))
+            .min_by_key(Vec::len)
+            .map_or(
+                Assessment {
+                    safe: false,
+                    removed: Vec::new(),
+                },
+                |removed| Assessment {
+                    safe: true,
+                    removed,
+                },
+            )
+    }
+
+    fn is_valid_step(&self, from: i32, to: i32, trend: Trend) -> bool {
+        let step = match trend {
+            Trend::Increasing => to as i64 - from as i64,
+            Trend::Decreasing => from as i64 - to as i64,
+            Trend::Either | Trend::Unrestricted => (to as i64 - from as i64).abs(),
+        };
+        (self.min_step as i64..=self.max_step as i64).contains(&step)
+    }
+
+    /// the removed indices - `None` if more than `removable` would be needed
+    fn fewest_removals(&self, report: &[i32], trend: Trend) -> Option<Vec<usize>> {
+        let k = self.removable;
+        // (removals, previous kept level) for keeping level i as the last one so far
+        let mut best: Vec<Option<(usize, Option<usize>)>> = Vec::with_capacity(report.len());
+        for i in 0..report.len() {
+            // dropping everything before i
+            let mut entry = (i <= k).then_some((i, None));
+            for j in i.saturating_sub(k + 1)..i {
+                let Some((removals, _)) = best[j] else {
+                    continue;
+                };
+                let removals = removals + (i - j - 1);
+                let better = entry.is_none_or(|(fewest, _)| removals < fewest);
+                if removals <= k && better && self.is_valid_step(report[j], report[i], trend) {
+                    entry = Some((removals, Some(j)));
+                }
+            }
+            best.push(entry);
+        }
+
+        // dropping everything after the last kept level
+        let (last, _) = best
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| Some((i, entry.as_ref()?.0 + report.len() - 1 - i)))
+            .filter(|&(_, removals)| removals <= k)
+            .min_by_key(|&(_, removals)| removals)?;
 
-pub fn solve_day_02_part_01(input: Vec<String>) -> u32 {
+        let mut kept = vec![false; report.len()];
+        let mut current = Some(last);
+        while let Some(i) = current {
+            kept[i] = true;
+            current = best[i].and_then(|(_, previous)| previous);
+        }
+        Some((0..report.len()).filter(|&i| !kept[i]).collect())
+    }
+}
+
+/// one report per line - blank lines (e.g. a trailing newline) are no reports at all
+fn parse_reports(input: Vec<String>) -> Result<Vec<Vec<i32>>, ParseError> {
     input
         .iter()
-        .map(|line| {
-            let folded = line
-                .split_whitespace()
-                .map(|num| num.parse::<i32>().expect("Invalid number"))
-                .fold(Some((Undecided, 0)), |option, curr| {
-                    if option.is_none() { return None; }
-                    let dir = option.unwrap().0;
-                    let last = option.unwrap().1;
-                    if DEBUG { println!("dir: {:?} - last: {} - curr: {}", dir, last, curr) };
-                    if last == 0 {
-                        return Some((Undecided, curr));
-                    }
-                    if last == curr || curr < last - 3 || curr > last + 3 {
-                        return None;
-                    }
-                    return match dir {
-                        Undecided => Some((if last < curr { Increasing } else { Decreasing }, curr)),
-                        Increasing => if last < curr { Some((dir, curr)) } else { None },
-                        Decreasing => if last > curr { Some((dir, curr)) } else { None }
-                    };
-                });
-
-            return match folded {
-                None => 0,
-                Some(_) => 1
-            };
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            line.split_whitespace()
+                .map(|num| parse_number(DAY, num).map_err(|e| e.at_token(line, num).at_line(i + 1)))
+                .collect()
         })
-        .sum()
+        .collect()
 }
 
-
-pub fn solve_day_02_part_02(input: Vec<String>) -> u32 {
-    input
+/// number of safe reports
+pub fn count_safe(input: Vec<String>, policy: ReportPolicy) -> Result<u32, ParseError> {
+    Ok(parse_reports(input)?
         .iter()
-        .map(|line| {
-            let numbers_of_line: Vec<i32> = line
-                .split_whitespace()
-                .map(|num| num.parse::<i32>().expect("Invalid number"))
-                .collect();
-
-            // instead of being smart we can just try all variations!
-            let mut variations: Vec<Vec<i32>> = Vec::with_capacity(numbers_of_line.len() + 1);
-            variations.push(numbers_of_line.clone());
-            for i in 0..numbers_of_line.len() {
-                let mut variant = numbers_of_line.clone();
-                variant.remove(i);
-                variations.push(variant);
-            }
+        .filter(|report| policy.assess(report).safe)
+        .count() as u32)
+}
 
-            let folded = variations
-                .iter()
-                .map(|variation| variation
-                    .iter()
-                    .fold(Some((Undecided, 0)), |option, &curr| {
-                        if option.is_none() { return None; }
-                        let dir = option.unwrap().0;
-                        let last = option.unwrap().1;
-                        if DEBUG { println!("dir: {:?} - last: {} - curr: {}", dir, last, curr); }
-                        if last == 0 {
-                            return Some((Undecided, curr));
-                        }
-                        if last == curr || curr < last - 3 || curr > last + 3 {
-                            return None;
-                        }
-                        return match dir {
-                            Undecided => Some((if last < curr { Increasing } else { Decreasing }, curr)),
-                            Increasing => if last < curr { Some((dir, curr)) } else { None },
-                            Decreasing => if last > curr { Some((dir, curr)) } else { None }
-                        };
-                    }))
-                .find(|variation| variation.is_some());
-
-
-            if DEBUG { println!("{} is {}", line, if folded.is_some() { "safe" } else { "unsafe" }); }
-
-            return match folded {
-                None => 0,
-                Some(_) => 1
-            };
-        })
-        .sum()
+pub fn solve_day_02_part_01(input: Vec<String>) -> Result<u32, ParseError> {
+    count_safe(input, ReportPolicy::default())
+}
+
+pub fn solve_day_02_part_02(input: Vec<String>) -> Result<u32, ParseError> {
+    count_safe(input, ReportPolicy::default().removable(1))
 }
 
 #[cfg(test)]
@@ -109,7 +178,7 @@ mod tests {
         lines.push(line);
 
         // act
-        let actual = solve_day_02_part_01(lines);
+        let actual = solve_day_02_part_01(lines).unwrap();
 
         // assert
         assert_eq!(0, actual);
@@ -123,7 +192,7 @@ mod tests {
         lines.push(line);
 
         // act
-        let actual = solve_day_02_part_01(lines);
+        let actual = solve_day_02_part_01(lines).unwrap();
 
         // assert
         assert_eq!(1, actual);
@@ -132,7 +201,7 @@ mod tests {
     #[test]
     fn should_solve_day_02_part_01() {
         match read_lines("./src/day02/input.txt") {
-            Ok(lines) => println!("Solution: {}", solve_day_02_part_01(lines)),
+            Ok(lines) => println!("Solution: {}", solve_day_02_part_01(lines).unwrap()),
             Err(e) => println!("Failed to parse: {}", e),
         }
     }
@@ -148,15 +217,51 @@ mod tests {
             "1 3 6 7 9"
         ];
 
-        let actual = solve_day_02_part_02(examples.iter().map(|s| s.to_string()).collect());
+        let actual = solve_day_02_part_02(examples.iter().map(|s| s.to_string()).collect()).unwrap();
 
         assert_eq!(4, actual);
     }
 
+    #[test]
+    fn should_skip_blank_lines() {
+        let lines = ["7 6 4 2 1", "", "   ", "1 2 7 8 9", "x"];
+
+        let e = solve_day_02_part_01(lines.iter().map(|s| s.to_string()).collect()).unwrap_err();
+        assert_eq!(Some(5), e.line);
+
+        let actual = solve_day_02_part_01(lines[..4].iter().map(|s| s.to_string()).collect());
+        assert_eq!(Ok(1), actual);
+    }
+
+    #[test]
+    fn should_name_removed_levels() {
+        let dampened = ReportPolicy::default().removable(1);
+
+        assert_eq!(0, dampened.assess(&[7, 6, 4, 2, 1]).removed.len());
+        assert_eq!(vec![2], dampened.assess(&[1, 3, 2, 4, 5]).removed);
+        assert_eq!(vec![0], dampened.assess(&[9, 1, 2, 3]).removed);
+        assert_eq!(vec![4], dampened.assess(&[1, 2, 3, 4, 9]).removed);
+        assert!(!dampened.assess(&[1, 2, 7, 8, 9]).safe);
+    }
+
+    #[test]
+    fn should_apply_other_tolerances() {
+        let report = [1, 2, 7, 8, 9, 3];
+        let policy = ReportPolicy::default();
+        let safe = |policy: ReportPolicy| policy.assess(&report).safe;
+
+        assert!(safe(policy.steps(1, 5).removable(1)));
+        assert_eq!(vec![0, 1, 5], policy.removable(3).assess(&report).removed);
+        assert!(!safe(policy.trend(Trend::Decreasing).removable(3)));
+        assert!(safe(policy.steps(1, 6).trend(Trend::Unrestricted)));
+        // a flat step is fine once 0 is allowed
+        assert!(policy.steps(0, 3).assess(&[8, 6, 4, 4, 1]).safe);
+    }
+
     #[test]
     fn should_solve_day_02_part_02() {
         match read_lines("./src/day02/input.txt") {
-            Ok(lines) => println!("Solution: {}", solve_day_02_part_02(lines)),
+            Ok(lines) => println!("Solution: {}", solve_day_02_part_02(lines).unwrap()),
             Err(e) => println!("Failed to parse: {}", e),
         }
     }
@@ -0,0 +1,206 @@
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+
+use crate::day03::lexer::{Lexer, Operand, Token, TokenKind, Word};
+
+/// everything an instruction can look at or change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct State {
+    /// switched by `do()` and `don't()`
+    pub enabled: bool,
+    /// what the puzzle asks for
+    pub sum: u64,
+    pub registers: BTreeMap<char, u64>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State {
+            enabled: true,
+            sum: 0,
+            registers: BTreeMap::new(),
+        }
+    }
+}
+
+impl State {
+    /// registers nobody wrote to are 0
+    pub fn value(&self, operand: &Operand) -> u64 {
+        match operand {
+            Operand::Number(n) => *n as u64,
+            Operand::Register(r) => self.registers.get(r).copied().unwrap_or(0),
+        }
+    }
+}
+
+/// runs an instruction taught with [Interpreter::instruction] - the arguments always match the
+/// word's params, and it's up to the handler whether `don't()` affects it
+pub type Handler = fn(&mut State, &[Operand]);
+
+/// executes the tokens of a [Lexer] - `mul`, `do` and `don't` are built in, further instructions
+/// bring their own [Handler]
+#[derive(Debug, Clone)]
+pub struct Interpreter {
+    state: State,
+    /// part 1 knows nothing about `do()` and `don't()`
+    conditionals: bool,
+    instructions: Vec<(Word, Handler)>,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Interpreter::new()
+    }
+}
+
+impl Interpreter {
+    pub fn new() -> Self {
+        Interpreter {
+            state: State::default(),
+            conditionals: true,
+            instructions: Vec::new(),
+        }
+    }
+
+    /// `false` treats `do()` and `don't()` like garbage
+    pub fn conditionals(mut self, conditionals: bool) -> Self {
+        self.conditionals = conditionals;
+        self
+    }
+
+    /// teaches the language a new word - the built in ones can't be replaced
+    pub fn instruction(mut self, word: Word, handler: Handler) -> Self {
+        self.instructions.push((word, handler));
+        self
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// a lexer that knows all the instructions of this interpreter
+    pub fn lexer<R: Read>(&self, reader: R) -> Lexer<R> {
+        self.instructions
+            .iter()
+            .fold(Lexer::new(reader), |lexer, &(word, _)| {
+                lexer.with_word(word)
+            })
+    }
+
+    /// `true` if the token did something - `false` for garbage, disabled `mul`s, ...
+    pub fn execute(&mut self, token: &Token) -> bool {
+        let state = &mut self.state;
+        match &token.kind {
+            TokenKind::Mul(a, b) if state.enabled || !self.conditionals => {
+                state.sum += *a as u64 * *b as u64;
+                true
+            }
+            TokenKind::Do if self.conditionals => {
+                state.enabled = true;
+                true
+            }
+            TokenKind::Dont if self.conditionals => {
+                state.enabled = false;
+                true
+            }
+            TokenKind::Call { name, args } => {
+                match self.instructions.iter().find(|(w, _)| w.name == *name) {
+                    Some((_, handler)) => {
+                        handler(state, args);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// executes the whole memory and returns the sum
+    pub fn run(&mut self, reader: impl Read) -> io::Result<u64> {
+        for token in self.lexer(reader) {
+            self.execute(&token?);
+        }
+        Ok(self.state.sum)
+    }
+}
+
+pub fn solve_day_03_part_01_interpreter(input: &str) -> u64 {
+    Interpreter::new()
+        .conditionals(false)
+        .run(input.as_bytes())
+        .expect("reading from memory never fails")
+}
+
+pub fn solve_day_03_part_02_interpreter(input: &str) -> u64 {
+    Interpreter::new()
+        .run(input.as_bytes())
+        .expect("reading from memory never fails")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day03::lexer::Param;
+    use crate::day03::part2_starts_with::solve_day_03_part_02_starts_with;
+    use crate::util::file::read_string;
+
+    use super::*;
+
+    #[test]
+    fn should_solve_examples() {
+        let part1 = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+        let part2 = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+        assert_eq!(161, solve_day_03_part_01_interpreter(part1));
+        assert_eq!(161, solve_day_03_part_01_interpreter(part2));
+        assert_eq!(48, solve_day_03_part_02_interpreter(part2));
+    }
+
+    #[test]
+    fn should_agree_with_starts_with() {
+        let input = read_string("./src/day03/input.txt").unwrap();
+
+        assert_eq!(
+            solve_day_03_part_02_starts_with(&input) as u64,
+            solve_day_03_part_02_interpreter(&input)
+        );
+    }
+
+    #[test]
+    fn should_run_extended_language() {
+        let mut interpreter = Interpreter::new()
+            .instruction(
+                Word::new("set", &[Param::Register, Param::Number { max_digits: 3 }]),
+                |state, args| {
+                    if let [Operand::Register(r), n] = args {
+                        let value = state.value(n);
+                        state.registers.insert(*r, value);
+                    }
+                },
+            )
+            .instruction(
+                Word::new("add", &[Param::Register, Param::Number { max_digits: 3 }]),
+                |state, args| {
+                    if let (true, [Operand::Register(r), n]) = (state.enabled, args) {
+                        *state.registers.entry(*r).or_default() += state.value(n);
+                    }
+                },
+            )
+            .instruction(Word::new("out", &[Param::Register]), |state, args| {
+                let value = state.value(&args[0]);
+                state.sum += value;
+            });
+        let memory = "set(a,4)?add(a,3)don't()add(a,100)mul(9,9)do()out(a)out(b)mul(2,3)";
+
+        let tokens: Vec<Token> = interpreter
+            .lexer(memory.as_bytes())
+            .map(Result::unwrap)
+            .collect();
+        let executed = tokens.iter().filter(|t| interpreter.execute(t)).count();
+
+        assert_eq!(13, interpreter.state().sum);
+        assert_eq!(Some(&7), interpreter.state().registers.get(&'a'));
+        // everything but the garbage and the disabled mul(9,9)
+        assert_eq!(tokens.len() - 2, executed);
+    }
+}
@@ -0,0 +1,413 @@
+/*
+PROGRAM := ( INSTRUCTION | GARBAGE )*
+
+INSTRUCTION := NAME "(" ( ARGUMENT ( "," ARGUMENT )* )? ")"
+ARGUMENT := NUMBER | REGISTER
+NUMBER := DIGIT+
+REGISTER := "a" - "z"
+
+`mul(number,number)`, `do()` and `don't()` are always known - more words can be taught to the lexer
+
+every number parameter has its own digit limit - the ones of `mul` take up to three digits
+*/
+
+use std::collections::VecDeque;
+use std::io::{self, BufReader, Bytes, Read};
+use std::ops::Range;
+
+/// what an instruction expects in between the parentheses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Param {
+    /// one to `max_digits` digits that fit into a `u32` - no sign, no whitespace
+    Number { max_digits: usize },
+    /// a single lowercase letter
+    Register,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operand {
+    Number(u32),
+    Register(char),
+}
+
+/// an instruction's name and parameters -
+/// `Word::new("add", &[Param::Register, Param::Number { max_digits: 3 }])` matches `add(a,5)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Word {
+    pub name: &'static str,
+    pub params: &'static [Param],
+    /// which of the built in tokens it becomes - `None` for taught words
+    builtin: Option<Builtin>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Builtin {
+    Mul,
+    Do,
+    Dont,
+}
+
+impl Word {
+    pub const fn new(name: &'static str, params: &'static [Param]) -> Self {
+        Word {
+            name,
+            params,
+            builtin: None,
+        }
+    }
+
+    const fn builtin(name: &'static str, params: &'static [Param], builtin: Builtin) -> Self {
+        Word {
+            name,
+            params,
+            builtin: Some(builtin),
+        }
+    }
+}
+
+const MUL_NUMBER: Param = Param::Number { max_digits: 3 };
+const MUL: Word = Word::builtin("mul", &[MUL_NUMBER, MUL_NUMBER], Builtin::Mul);
+const DO: Word = Word::builtin("do", &[], Builtin::Do);
+const DONT: Word = Word::builtin("don't", &[], Builtin::Dont);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenKind {
+    Mul(u32, u32),
+    Do,
+    Dont,
+    /// one of the words taught with [Lexer::with_word]
+    Call {
+        name: &'static str,
+        args: Vec<Operand>,
+    },
+    /// everything in between the instructions
+    Garbage,
+}
+
+/// `span` is the byte range in the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Range<usize>,
+}
+
+/// streams tokens out of any [Read] - only keeps the bytes of the instruction it's trying to
+/// match, so the memory can be arbitrarily large
+///
+/// consecutive garbage is merged into one token, so instructions and garbage alternate
+pub struct Lexer<R: Read> {
+    bytes: Bytes<BufReader<R>>,
+    /// bytes read but not consumed yet - `lookahead[0]` is at `offset`
+    lookahead: VecDeque<u8>,
+    offset: usize,
+    garbage_start: Option<usize>,
+    /// the instruction right after some garbage - returned on the next call
+    pending: Option<Token>,
+    words: Vec<Word>,
+}
+
+impl<R: Read> Lexer<R> {
+    pub fn new(reader: R) -> Self {
+        Lexer {
+            bytes: BufReader::new(reader).bytes(),
+            lookahead: VecDeque::new(),
+            offset: 0,
+            garbage_start: None,
+            pending: None,
+            words: vec![MUL, DO, DONT],
+        }
+    }
+
+    /// recognises `word` too - it's ignored if its name is taken already
+    pub fn with_word(mut self, word: Word) -> Self {
+        if self.words.iter().all(|w| w.name != word.name) {
+            self.words.push(word);
+        }
+        self
+    }
+
+    fn peek(&mut self, i: usize) -> io::Result<Option<u8>> {
+        while self.lookahead.len() <= i {
+            match self.bytes.next() {
+                Some(byte) => self.lookahead.push_back(byte?),
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(self.lookahead[i]))
+    }
+
+    fn consume(&mut self, len: usize) {
+        self.lookahead.drain(..len);
+        self.offset += len;
+    }
+
+    /// the first word matching at the current position and its length in bytes
+    fn instruction(&mut self) -> io::Result<Option<(TokenKind, usize)>> {
+        for i in 0..self.words.len() {
+            let word = self.words[i];
+            if let Some((args, len)) = self.arguments(word)? {
+                let kind = match (word.builtin, args.as_slice()) {
+                    (Some(Builtin::Mul), &[Operand::Number(a), Operand::Number(b)]) => {
+                        TokenKind::Mul(a, b)
+                    }
+                    (Some(Builtin::Do), _) => TokenKind::Do,
+                    (Some(Builtin::Dont), _) => TokenKind::Dont,
+                    _ => TokenKind::Call {
+                        name: word.name,
+                        args,
+                    },
+                };
+                return Ok(Some((kind, len)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// `None` if `word` doesn't match exactly at the current position
+    fn arguments(&mut self, word: Word) -> io::Result<Option<(Vec<Operand>, usize)>> {
+        let mut i = 0;
+        for &expected in word.name.as_bytes().iter().chain(b"(") {
+            if self.peek(i)? != Some(expected) {
+                return Ok(None);
+            }
+            i += 1;
+        }
+
+        let mut args = Vec::with_capacity(word.params.len());
+        for (n, param) in word.params.iter().enumerate() {
+            if n > 0 {
+                if self.peek(i)? != Some(b',') {
+                    return Ok(None);
+                }
+                i += 1;
+            }
+            let operand = match param {
+                Param::Number { max_digits } => {
+                    let mut number: u32 = 0;
+                    let mut digits = 0;
+                    while let Some(digit) = self.peek(i)?.filter(u8::is_ascii_digit) {
+                        let next = number
+                            .checked_mul(10)
+                            .and_then(|n| n.checked_add((digit - b'0') as u32));
+                        match next {
+                            Some(next) if digits < *max_digits => number = next,
+                            _ => return Ok(None),
+                        }
+                        digits += 1;
+                        i += 1;
+                    }
+                    if digits == 0 {
+                        return Ok(None);
+                    }
+                    Operand::Number(number)
+                }
+                Param::Register => match self.peek(i)?.filter(u8::is_ascii_lowercase) {
+                    Some(register) => {
+                        i += 1;
+                        Operand::Register(register as char)
+                    }
+                    None => return Ok(None),
+                },
+            };
+            args.push(operand);
+        }
+
+        if self.peek(i)? != Some(b')') {
+            return Ok(None);
+        }
+        Ok(Some((args, i + 1)))
+    }
+}
+
+impl<R: Read> Iterator for Lexer<R> {
+    type Item = io::Result<Token>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(token) = self.pending.take() {
+            return Some(Ok(token));
+        }
+        loop {
+            let found = match self.instruction() {
+                Ok(found) => found,
+                Err(e) => return Some(Err(e)),
+            };
+            if let Some((kind, len)) = found {
+                let token = Token {
+                    kind,
+                    span: self.offset..self.offset + len,
+                };
+                self.consume(len);
+                return match self.garbage_start.take() {
+                    Some(start) => {
+                        let garbage = Token {
+                            kind: TokenKind::Garbage,
+                            span: start..token.span.start,
+                        };
+                        self.pending = Some(token);
+                        Some(Ok(garbage))
+                    }
+                    None => Some(Ok(token)),
+                };
+            }
+
+            match self.peek(0) {
+                Ok(Some(_)) => {
+                    self.garbage_start.get_or_insert(self.offset);
+                    self.consume(1);
+                }
+                Ok(None) => {
+                    return self.garbage_start.take().map(|start| {
+                        Ok(Token {
+                            kind: TokenKind::Garbage,
+                            span: start..self.offset,
+                        })
+                    });
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// all tokens of an in-memory input - reading a `&[u8]` can't fail
+pub fn tokenize(input: &str) -> Vec<Token> {
+    Lexer::new(input.as_bytes())
+        .collect::<io::Result<_>>()
+        .expect("reading from memory never fails")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(tokens: &[Token]) -> Vec<&TokenKind> {
+        tokens.iter().map(|t| &t.kind).collect()
+    }
+
+    fn tokenize_with(input: &str, word: Word) -> Vec<Token> {
+        Lexer::new(input.as_bytes())
+            .with_word(word)
+            .collect::<io::Result<_>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn should_tokenize_with_spans() {
+        let input = "xmul(2,4)undo()don't()mul(1,2";
+
+        let tokens = tokenize(input);
+
+        assert_eq!(
+            vec![
+                (TokenKind::Garbage, 0..1),
+                (TokenKind::Mul(2, 4), 1..9),
+                (TokenKind::Garbage, 9..11),
+                (TokenKind::Do, 11..15),
+                (TokenKind::Dont, 15..22),
+                (TokenKind::Garbage, 22..29),
+            ],
+            tokens
+                .into_iter()
+                .map(|t| (t.kind, t.span))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Vec::<Token>::new(), tokenize(""));
+    }
+
+    #[test]
+    fn should_only_accept_exact_instructions() {
+        let tokens = tokenize(
+            "mul( 2,3)mul(4*mul(2,3,4)mul(+1,2)mul(99999999999,1)mul(1234,5)mul(,5)mumul(6,7)",
+        );
+
+        assert_eq!(
+            vec![&TokenKind::Garbage, &TokenKind::Mul(6, 7)],
+            kinds(&tokens)
+        );
+        assert_eq!(72..80, tokens[1].span);
+        assert_eq!(
+            vec![&TokenKind::Mul(123, 5)],
+            kinds(&tokenize("mul(123,5)"))
+        );
+    }
+
+    #[test]
+    fn should_learn_words() {
+        const NUMBER: Param = Param::Number { max_digits: 3 };
+        let lexer = Lexer::new("set(a,5)add(a,b)add(5,a)do()".as_bytes())
+            .with_word(Word::new("set", &[Param::Register, NUMBER]))
+            .with_word(Word::new("add", &[Param::Register, Param::Register]))
+            .with_word(Word::new("do", &[NUMBER]));
+
+        let tokens: Vec<Token> = lexer.collect::<io::Result<_>>().unwrap();
+
+        assert_eq!(
+            vec![
+                &TokenKind::Call {
+                    name: "set",
+                    args: vec![Operand::Register('a'), Operand::Number(5)]
+                },
+                &TokenKind::Call {
+                    name: "add",
+                    args: vec![Operand::Register('a'), Operand::Register('b')]
+                },
+                &TokenKind::Garbage,
+                &TokenKind::Do,
+            ],
+            kinds(&tokens)
+        );
+    }
+
+    #[test]
+    fn should_limit_digits_per_word() {
+        let tokens = tokenize_with(
+            "add(a,5000)mul(5000,1)add(b,4294967295)add(c,4294967296)",
+            Word::new("add", &[Param::Register, Param::Number { max_digits: 10 }]),
+        );
+
+        assert_eq!(
+            vec![
+                &TokenKind::Call {
+                    name: "add",
+                    args: vec![Operand::Register('a'), Operand::Number(5000)]
+                },
+                &TokenKind::Garbage,
+                &TokenKind::Call {
+                    name: "add",
+                    args: vec![Operand::Register('b'), Operand::Number(u32::MAX)]
+                },
+                &TokenKind::Garbage,
+            ],
+            kinds(&tokens)
+        );
+
+        let tokens = tokenize_with(
+            "add(a,12)add(a,123)",
+            Word::new("add", &[Param::Register, Param::Number { max_digits: 2 }]),
+        );
+        assert_eq!(
+            vec![
+                &TokenKind::Call {
+                    name: "add",
+                    args: vec![Operand::Register('a'), Operand::Number(12)]
+                },
+                &TokenKind::Garbage,
+            ],
+            kinds(&tokens)
+        );
+    }
+
+    #[test]
+    fn should_pass_on_read_errors() {
+        struct Broken;
+        impl Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("unplugged"))
+            }
+        }
+
+        let mut lexer = Lexer::new(Broken);
+
+        assert_eq!("unplugged", lexer.next().unwrap().unwrap_err().to_string());
+    }
+}
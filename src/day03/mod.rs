@@ -1,9 +1,22 @@
+pub mod interpreter;
+pub mod lexer;
 pub mod part1;
 pub mod part2_state_machine;
 pub mod part2_starts_with;
 
+use crate::util::dfa::Match;
 
+/// essentially only `mul(5,4)` should match - not `mul*(5,4)` or `mul( 5, 4)` etc. (no extra
+/// tokens except `mul(number,number)`)
+pub const MUL: &str = r"mul\((\d{1,3}),(\d{1,3})\)";
+pub const DO: &str = r"do\(\)";
+pub const DONT: &str = r"don't\(\)";
 
+/// the product of a [MUL] match
+pub(super) fn multiply(mul: &Match) -> u32 {
+    let factor = |group| mul.number::<u32>(group).expect("at most three digits");
+    factor(1) * factor(2)
+}
 
 #[cfg(test)]
 mod tests {
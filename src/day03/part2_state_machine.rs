@@ -1,198 +1,25 @@
 /*
-PROGRAM := ( COMMAND | GARBAGE )*
+PROGRAM := ( MUL | DO | DONT | GARBAGE )*
 
-COMMAND := MUL_CMD | DO_CMD | DONT_CMD
-
-MUL_CMD := "mul(" NUMBER "," NUMBER ")"
-DO_CMD := "do()"
-DONT_CMD := "don't()"
-
-essentially only `mul(5,4)` should match - not `mul*(5,4)` or `mul( 5, 4)` etc. (no extra tokens except `mul(number,number)` )
+the tokens are declared in the parent module and compiled into a table driven automaton - no
+hand-written states anymore
 */
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum Word {
-    Mul,
-    Do,
-    Dont,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
-enum MulState {
-    #[default]
-    /* nothing of value has been found - also initial state */
-    Garbage,
-    /* d found transition to o allowed */
-    D,
-    /* o found transition to n or ( allowed */
-    O,
-    /* n found transition to ' allowed */
-    N,
-    /* ' found transition to t allowed */
-    Apostrophe,
-    /* t found transition to ( allowed */
-    T,
-    /* m found transition to u allowed */
-    M,
-    /* u found transition to l allowed */
-    U,
-    /* l found transition to ( allowed */
-    L,
-    /* ( found transition to digit or ) allowed - need to state which word we're parsing */
-    OpeningBracket(Word),
-    /* left-digit found transition to digit or comma allowed */
-    LeftNum,
-    /* comma found transition to digit allowed */
-    Comma,
-    /* right-digit found transition to digit or ) allowed */
-    RightNum,
-    /* ) found - sequence is valid */
-    ClosingBracket,
-}
-
-#[derive(Debug, PartialEq, Default)]
-struct MulCache {
-    /* caching left digits */
-    left: Vec<char>,
-    /* caching right digits */
-    right: Vec<char>,
-    /* state of token sequence */
-    mul_state: MulState,
-    /* disabled by 'don't()' sequence*/
-    disabled: bool,
-    /* aggregated sum of all previously parsed multiplications */
-    sum: u32,
-}
-
-impl MulCache {
-    /// Reset this cache to the default state (keeps the sum as is)
-    fn reset(mut self) -> Self {
-        self.mul_state = MulState::default();
-        self.left.clear();
-        self.right.clear();
-        self
-    }
-
-    /// Advance to a new state.
-    fn advance(mut self, state: MulState) -> Self {
-        self.mul_state = state;
-        self
-    }
-
-    /// Advance to a new state (if a and b match), transition into a word entry state or reset if garbage is found
-    fn advance_on_match_or_reset(self, a: char, allowed: &[(char, MulState)]) -> Self {
-        let allowed = allowed.iter().find(|(c, _)| *c == a);
-        if allowed.is_some() {
-            return self.advance(allowed.copied().unwrap().1);
-        }
-        match a {
-            'm' => self.advance(MulState::M),
-            'd' => self.advance(MulState::D),
-            _ => self.reset()
+use crate::day03::{multiply, DO, DONT, MUL};
+use crate::util::dfa::Dfa;
+
+pub fn solve_day_03_part_02_state_machine(input: impl Iterator<Item = char>) -> u32 {
+    let dfa = Dfa::compile(&[MUL, DO, DONT]).expect("the grammar is valid");
+    let (mut enabled, mut sum) = (true, 0);
+    for token in dfa.matches(input) {
+        match token.token {
+            0 if enabled => sum += multiply(&token),
+            0 => {}
+            1 => enabled = true,
+            _ => enabled = false,
         }
     }
-}
-
-
-pub fn solve_day_03_part_02_state_machine(input: impl Iterator<Item=char>) -> u32 {
-    input.fold(MulCache::default(), |mut mul_cache, char| {
-        let next_mul_cache = match mul_cache.mul_state {
-            MulState::Garbage => mul_cache.advance_on_match_or_reset(char, &[]),
-            // do & dont
-            MulState::D => mul_cache.advance_on_match_or_reset(char, &[('o', MulState::O)]),
-            MulState::O => mul_cache.advance_on_match_or_reset(char, &[('n', MulState::N), ('(', MulState::OpeningBracket(Word::Do))]),
-            MulState::N => mul_cache.advance_on_match_or_reset(char, &[('\'', MulState::Apostrophe)]),
-            MulState::Apostrophe => mul_cache.advance_on_match_or_reset(char, &[('t', MulState::T)]),
-            MulState::T => mul_cache.advance_on_match_or_reset(char, &[('(', MulState::OpeningBracket(Word::Dont))]),
-            // mul
-            MulState::M => mul_cache.advance_on_match_or_reset(char, &[('u', MulState::U)]),
-            MulState::U => mul_cache.advance_on_match_or_reset(char, &[('l', MulState::L)]),
-            MulState::L => mul_cache.advance_on_match_or_reset(char, &[('(', MulState::OpeningBracket(Word::Mul))]),
-            MulState::OpeningBracket(Word::Do) =>
-                match char {
-                    ')' => {
-                        mul_cache.mul_state = MulState::ClosingBracket;
-                        mul_cache.disabled = false;
-                        mul_cache
-                    }
-                    _ => mul_cache.reset()
-                }
-            MulState::OpeningBracket(Word::Dont) =>
-                match char {
-                    ')' => {
-                        mul_cache.mul_state = MulState::ClosingBracket;
-                        mul_cache.disabled = true;
-                        mul_cache
-                    }
-                    _ => mul_cache.reset()
-                }
-            MulState::OpeningBracket(Word::Mul) =>
-                if char.is_numeric() {
-                    mul_cache.mul_state = MulState::LeftNum;
-                    mul_cache.left.push(char);
-                    mul_cache
-                } else {
-                    mul_cache.reset()
-                },
-            MulState::LeftNum =>
-                return if char.is_numeric() {
-                    mul_cache.mul_state = MulState::LeftNum;
-                    mul_cache.left.push(char);
-                    mul_cache
-                } else {
-                    return match char {
-                        ',' => {
-                            mul_cache.mul_state = MulState::Comma;
-                            mul_cache
-                        }
-                        _ => mul_cache.reset()
-                    };
-                },
-            MulState::Comma =>
-                return if char.is_numeric() {
-                    mul_cache.mul_state = MulState::RightNum;
-                    mul_cache.right.push(char);
-                    mul_cache
-                } else {
-                    mul_cache.reset()
-                },
-            MulState::RightNum =>
-                return if char.is_numeric() {
-                    mul_cache.mul_state = MulState::RightNum;
-                    mul_cache.right.push(char);
-                    mul_cache
-                } else if !mul_cache.disabled {
-                    return match char {
-                        ')' => {
-                            let left_num: u32 = mul_cache.left
-                                .iter()
-                                .collect::<String>()
-                                .parse()
-                                .unwrap();
-
-                            let right_num: u32 = mul_cache.right
-                                .iter()
-                                .collect::<String>()
-                                .parse()
-                                .unwrap();
-
-                            mul_cache.sum += left_num * right_num;
-                            mul_cache.left.clear();
-                            mul_cache.right.clear();
-                            mul_cache.mul_state = MulState::ClosingBracket;
-                            mul_cache
-                        }
-                        _ => mul_cache.reset()
-                    };
-                } else {
-                    mul_cache.reset()
-                },
-            MulState::ClosingBracket => mul_cache.advance_on_match_or_reset(char, &[]),
-        };
-        // println!("{char} -> {:?}", next_mul_cache);
-        return next_mul_cache;
-    },
-    ).sum
+    sum
 }
 
 #[cfg(test)]
@@ -201,6 +28,13 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn should_restart_on_broken_sequence() {
+        let input = "don't(mul(2,3)mul(4,mul(5,6)do(don't()do()mul(1m";
+
+        assert_eq!(36, solve_day_03_part_02_state_machine(input.chars()));
+    }
+
     #[test]
     fn should_solve_day_03_part_02_state_machine() {
         let input = read_chars("./src/day03/input.txt").unwrap();
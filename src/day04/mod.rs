@@ -1,157 +1,18 @@
-use std::collections::HashMap;
-
-use crate::day04::Direction::*;
-
-/* represents read-direction (eg. NE means from left bottom to right top) */
-#[derive(Debug)]
-enum Direction {
-    N,
-    E,
-    S,
-    W,
-    NW,
-    SW,
-    NE,
-    SE,
-}
-
-const DIRECTIONS: [Direction; 8] = [
-    N,
-    E,
-    S,
-    W,
-    NW,
-    SW,
-    NE,
-    SE,
-];
-
-
-/// takes a string and indexes every letter to a tuple (x, y)
-/// where x is the index of the horizontal position in the line
-/// and y is the index of the vertical position (~ line)
-fn parse_to_map(input: String) -> HashMap<(isize, isize), char> {
-    input
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars().enumerate()//char_indices()
-                .map(move |(x, c)| ((x as isize, y as isize), c))
-        })
-        .collect::<HashMap<(isize, isize), char>>()
-}
-
-type NavigationStrategy = fn((isize, isize)) -> (isize, isize);
-
-/// searches for a sequence in a lookup hashmap with a given strategy
-/// returns true if sequence was found
-/// return false if sequence was not found
-fn search(
-    lookup: &HashMap<(isize, isize), char>,
-    search: &str,
-    entry_pos: (isize, isize),
-    strategy: NavigationStrategy,
-) -> bool
-{
-    search.chars()
-        .into_iter()
-        .fold(Some(entry_pos), |pos, char| {
-            if pos.is_none() { return None; }
-            let next_pos = strategy(pos.unwrap());
-            let next_letter = lookup.get(&next_pos);
-            return match next_letter {
-                Some(&letter) => {
-                    return if letter == char {
-                        Some(next_pos)
-                    } else {
-                        None
-                    };
-                }
-                None => None
-            };
-        })
-        .is_some()
-}
+use crate::util::grid::{Direction, Grid};
+use crate::util::word_search::{Template, WordSearch};
 
 pub fn solve_day_04_part_01(input: String) -> u32 {
-    let letters = parse_to_map(input);
-    let search_word = &"XMAS"[1..]; // X is the entry point so we essentially search for "MAS" from there on
-    letters
-        .iter()
-        .filter(|(_, &v)| v.to_ascii_uppercase() == 'X')
-        .map(|(&(x, y), _)| {
-            DIRECTIONS.iter().map(|dir| {
-                let strategy: fn((isize, isize)) -> (isize, isize) = match dir {
-                    N => |(x, y)| (x, y - 1),
-                    E => |(x, y)| (x + 1, y),
-                    S => |(x, y)| (x, y + 1),
-                    W => |(x, y)| (x - 1, y),
-                    NW => |(x, y)| (x - 1, y - 1),
-                    SW => |(x, y)| (x - 1, y + 1),
-                    NE => |(x, y)| (x + 1, y - 1),
-                    SE => |(x, y)| (x + 1, y + 1)
-                };
-                return match search(&letters, search_word, (x, y), strategy) {
-                    true => 1,
-                    false => 0,
-                };
-            })
-                .sum::<u32>()
-        })
-        .sum()
+    let letters = Grid::from(input.as_str());
+    WordSearch::new(["XMAS"])
+        .find(&letters, &Direction::ALL)
+        .len() as u32
 }
 
-
+/// two `MAS` crossing each other - in any of the four rotations (the mirrored ones look the same)
 pub fn solve_day_04_part_02(input: String) -> u32 {
-    let letters = parse_to_map(input);
-    let search_word = "MAS";
-    // a primary direction and it's two allowed counterparts that form an X
-    // NW     NE
-    //   \   /
-    //    \ /
-    //     X
-    //    / \
-    //   /   \
-    // SW     SE
-    let directions = [
-        (NE, (NW, SE)),
-        (SW, (NW, SE)),
-        (SE, (NE, SW)),
-        (NW, (NE, SW))
-    ];
-
-    let double: u32 = letters
-        .iter()
-        .filter(|(_, &v)| v.to_ascii_uppercase() == 'A')
-        .map(|(&(x, y), _)| {
-            directions.iter().map(|(primary_dir, (secondary_dir_option_1, secondary_dir_option_2))| {
-                let first = search_dir(&letters, search_word, x, y, primary_dir);
-                if !first { return 0; }
-                let sec_1 = search_dir(&letters, search_word, x, y, secondary_dir_option_1);
-                if sec_1 { return 1; }
-                let sec_2 = search_dir(&letters, search_word, x, y, secondary_dir_option_2);
-                if sec_2 { return 1; }
-                0
-            })
-                .sum::<u32>()
-        })
-        .sum();
-    double / 2
-}
-
-fn search_dir(letters: &HashMap<(isize, isize), char>, search_word: &str, x: isize, y: isize, dir: &Direction) -> bool {
-    let (strategy, entry_pos): (NavigationStrategy, (isize, isize)) = match dir {
-        NW => ((|(x, y)| (x - 1, y - 1)),
-               (x + 2, y + 2)),
-        SW => ((|(x, y)| (x - 1, y + 1)),
-               (x + 2, y - 2)),
-        NE => ((|(x, y)| (x + 1, y - 1)),
-               (x - 2, y + 2)),
-        SE => ((|(x, y)| (x + 1, y + 1)),
-               (x - 2, y - 2)),
-        _ => panic!("part_2 should only ever deal with diagonals")
-    };
-    search(&letters, search_word, entry_pos, strategy)
+    let letters = Grid::from(input.as_str());
+    let x_mas = Template::new("M.S\n.A.\nM.S", '.');
+    x_mas.find(&letters).len() as u32
 }
 
 #[cfg(test)]
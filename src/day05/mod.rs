@@ -1,30 +1,33 @@
+use crate::util::error::ParseError;
+use crate::util::parse::{sections, Section};
+
+const DAY: u8 = 5;
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 struct OrderRule {
     before: u8,
     after: u8,
 }
 
-fn parse_rules(input: &Vec<String>) -> Vec<OrderRule> {
-    input
-        .iter()
-        .filter(|line| line.contains('|'))
-        .map(|line| {
-            let values: Vec<u8> = line.split('|').map(|number| number.parse::<u8>().unwrap()).collect();
-            assert_eq!(2, values.len());
-            OrderRule { before: values[0], after: values[1] }
+/// the rules before the blank line and the updates after it
+fn parse(input: &[String]) -> Result<(Vec<OrderRule>, Vec<Vec<u8>>), ParseError> {
+    let input = input.join("\n");
+    let sections = sections(DAY, &input);
+    let lines = |i: usize| sections.get(i).into_iter().flat_map(Section::lines);
+
+    let rules = lines(0)
+        .map(|line| match line.numbers(line.text, &['|'])?[..] {
+            [before, after] => Ok(OrderRule { before, after }),
+            _ => Err(line.error("a rule needs exactly 2 pages")),
         })
-        .collect()
-}
+        .collect::<Result<_, _>>()?;
+    let updates = lines(1)
+        .map(|line| line.numbers(line.text, &[',']))
+        .collect::<Result<_, _>>()?;
 
-fn parse_updates(input: Vec<String>) -> Vec<Vec<u8>> {
-    input
-        .iter()
-        .filter(|line| line.contains(','))
-        .map(|line| line.split(',').map(|number| number.parse::<u8>().unwrap()).collect())
-        .collect()
+    Ok((rules, updates))
 }
 
-
 fn get_relevant_rules(update: &Vec<u8>, rules: &[OrderRule]) -> Vec<OrderRule> {
     rules
         .iter()
@@ -45,15 +48,14 @@ fn check_if_compliant(update: &Vec<u8>, rules: &[OrderRule]) -> bool {
     })
 }
 
-pub fn solve_day_05_part_01(input: Vec<String>) -> u32 {
-    let order_rules = parse_rules(&input);
-    let updates = parse_updates(input);
+pub fn solve_day_05_part_01(input: Vec<String>) -> Result<u32, ParseError> {
+    let (order_rules, updates) = parse(&input)?;
 
-    updates
+    Ok(updates
         .into_iter()
         .filter(|update| check_if_compliant(update, &order_rules[..]))
         .map(|update| update.iter().nth(update.len() / 2).map(|&v| v as u32).unwrap_or(0))
-        .sum()
+        .sum())
 }
 
 fn sort_update(mut update: Vec<u8>, rules: &Vec<OrderRule>) -> Vec<u8> {
@@ -73,11 +75,10 @@ fn sort_update(mut update: Vec<u8>, rules: &Vec<OrderRule>) -> Vec<u8> {
     }
 }
 
-pub fn solve_day_05_part_02(input: Vec<String>) -> u32 {
-    let order_rules: Vec<OrderRule> = parse_rules(&input);
-    let updates: Vec<Vec<u8>> = parse_updates(input);
+pub fn solve_day_05_part_02(input: Vec<String>) -> Result<u32, ParseError> {
+    let (order_rules, updates) = parse(&input)?;
 
-    updates
+    Ok(updates
         .into_iter()
         .filter_map(|update| {
             let relevant_rules = get_relevant_rules(&update, &order_rules);
@@ -90,7 +91,7 @@ pub fn solve_day_05_part_02(input: Vec<String>) -> u32 {
         })
         .map(|(update, relevant_rules)| sort_update(update, &relevant_rules))
         .map(|sorted_update| sorted_update.iter().nth(sorted_update.len() / 2).map(|&v| v as u32).unwrap_or(0))
-        .sum()
+        .sum())
 }
 
 #[cfg(test)]
@@ -130,7 +131,7 @@ mod tests {
 61,13,29
 97,13,75,29,47".lines().map(str::to_string).collect();
 
-        let actual = solve_day_05_part_01(input);
+        let actual = solve_day_05_part_01(input).unwrap();
 
         assert_eq!(143, actual);
     }
@@ -140,7 +141,7 @@ mod tests {
     fn should_solve_day_05_part_01() {
         let input = read_lines("./src/day05/input.txt").unwrap();
 
-        let solution = solve_day_05_part_01(input);
+        let solution = solve_day_05_part_01(input).unwrap();
 
         println!("{solution}");
     }
@@ -177,7 +178,7 @@ mod tests {
 61,13,29
 97,13,75,29,47".lines().map(str::to_string).collect();
 
-        let actual = solve_day_05_part_02(input);
+        let actual = solve_day_05_part_02(input).unwrap();
 
         assert_eq!(123, actual);
     }
@@ -186,8 +187,17 @@ mod tests {
     fn should_solve_day_05_part_02() {
         let input = read_lines("./src/day05/input.txt").unwrap();
 
-        let solution = solve_day_05_part_02(input);
+        let solution = solve_day_05_part_02(input).unwrap();
 
         println!("{solution}");
     }
+
+    #[test]
+    fn should_report_bad_rule() {
+        let input = vec!["47|53".to_string(), "97|1x3".to_string()];
+
+        let e = solve_day_05_part_01(input).unwrap_err();
+
+        assert_eq!("day 05 (line 2, column 4): '1x3' is not a valid number", e.to_string());
+    }
 }
@@ -1,7 +1,14 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::day06::Direction::*;
 use crate::day06::FieldType::*;
+use crate::util::error::ParseError;
+use crate::util::grid::Direction::{self, *};
+use crate::util::grid::DenseGrid;
+use crate::util::playback::{CellColours, Colour, Playback};
+use crate::util::point::Point;
+use crate::util::render::Renderer;
+
+const DAY: u8 = 6;
 
 #[derive(Debug, Copy, Clone, Eq, Hash, PartialEq, Default)]
 enum FieldType {
@@ -14,24 +21,9 @@ enum FieldType {
     Out,
 }
 
-/// Direction into which the guard faces
-#[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
-enum Direction {
-    N,
-    E,
-    S,
-    W,
-}
-
-impl Direction {
-    fn next(&self) -> Direction {
-        match *self {
-            N => E,
-            E => S,
-            S => W,
-            W => N
-        }
-    }
+/// one bit per [Direction] the guard faced on a field (see [DenseVisited])
+fn direction_bit(dir: &Direction) -> u8 {
+    1 << *dir as u8
 }
 
 type GameMap = HashMap<(isize, isize), FieldType>;
@@ -40,7 +32,7 @@ type GamePosition = (isize, isize);
 /// takes a string and indexes every letter to a tuple (x, y)
 /// where x is the index of the horizontal position in the line
 /// and y is the index of the vertical position (~ line)
-fn parse_to_map(input: String) -> (GameMap, GamePosition) {
+fn parse_to_map(input: String) -> Result<(GameMap, GamePosition), ParseError> {
     let mut map: GameMap = HashMap::new();
     let mut start_pos = (isize::MAX, isize::MAX);
 
@@ -59,15 +51,19 @@ fn parse_to_map(input: String) -> (GameMap, GamePosition) {
                     start_pos = (x, y);
                     map.insert((x, y), Free);
                 }
-                _ => panic!("Unexpected token '{}' in input data", c),
+                _ => return Err(unexpected_token(c, x as usize, y as usize)),
             }
         }
     }
 
     if start_pos == (isize::MAX, isize::MAX) {
-        panic!("No start position found!")
+        return Err(ParseError::new(DAY, "no start position '^' found"));
     }
-    (map, start_pos)
+    Ok((map, start_pos))
+}
+
+fn unexpected_token(c: char, x: usize, y: usize) -> ParseError {
+    ParseError::new(DAY, format!("unexpected token '{c}'")).at(y + 1, x + 1)
 }
 
 /// traverses the map from a given start position and returns the count of fields that have been visited (only first visit counts) until the map's end is reached
@@ -80,7 +76,7 @@ fn traverse_map(map: GameMap, start_pos: GamePosition) -> isize {
     while let Some((x, y)) = pos {
         let (mut next_pos, mut next_field) = travel_one_unit(&map, &(x, y), &dir);
         while next_field == Occupied {
-            dir = dir.next();
+            dir = dir.turn_right();
             (next_pos, next_field) = travel_one_unit(&map, &(x, y), &dir);
             // println!("Hit obstacle at ({x}, {y}) - changing dir to {:?}", dir);
         }
@@ -98,112 +94,75 @@ fn traverse_map(map: GameMap, start_pos: GamePosition) -> isize {
 
 /// general strategy: read the map into a suitable data structure, walk the maze according to the
 /// navigation rules and count each visited field (no duplicates = hashset)
-pub fn solve_day_06_part_01(input: String) -> isize {
-    let (map, start_pos) = parse_to_map(input);
-    traverse_map(map, start_pos)
+pub fn solve_day_06_part_01(input: String) -> Result<isize, ParseError> {
+    let (map, start_pos) = parse_to_map(input)?;
+    Ok(traverse_map(map, start_pos))
 }
 
-type Game2DArray = [[FieldType; MAX_MAP_SIZE]; MAX_MAP_SIZE];
-
-type LowMemGamePosition = (u8, u8);
+/// dense (flat `Vec`) version of [GameMap] - cells beyond the map are simply not in the grid
+type DenseGameMap = DenseGrid<FieldType>;
 
+/// the directions the guard faced on each field - one [direction_bit] per direction
+type DenseVisited = DenseGrid<u8>;
 
-/// map is actually square and 130 fields wide/tall
-const MAX_MAP_SIZE: usize = 130;
-
-fn parse_to_2d_arr(input: String) -> (Game2DArray, LowMemGamePosition) {
-    let mut map: Game2DArray = [[Out; MAX_MAP_SIZE]; MAX_MAP_SIZE];
-    let mut start_pos = (u8::MAX, u8::MAX);
+fn parse_to_dense_grid(input: String) -> Result<(DenseGameMap, Point), ParseError> {
+    let width = input.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+    let mut map: DenseGameMap = DenseGrid::new(width, input.lines().count());
+    let mut start_pos = None;
 
     for (y, line) in input.lines().enumerate() {
         for (x, c) in line.chars().enumerate() {
+            let p = Point::new(x as i32, y as i32);
             match c {
-                '#' => {
-                    map[x][y] = Occupied;
-                }
-                '.' => {
-                    map[x][y] = Free;
-                }
+                '#' => map.set(p, Occupied),
+                '.' => map.set(p, Free),
                 '^' => {
-                    map[x][y] = Free;
-                    start_pos = (x as u8, y as u8);
+                    map.set(p, Free);
+                    start_pos = Some(p);
                 }
-                _ => panic!("Unexpected token '{}' in input data", c),
+                _ => return Err(unexpected_token(c, x, y)),
             }
         }
     }
 
-    if start_pos == (u8::MAX, u8::MAX) {
-        panic!("No start position found!")
-    }
-    (map, start_pos)
+    let start_pos = start_pos.ok_or(ParseError::new(DAY, "no start position '^' found"))?;
+    Ok((map, start_pos))
 }
 
-fn travel_one_unit_fast(map: &Game2DArray, pos: &LowMemGamePosition, dir: &Direction) -> (LowMemGamePosition, FieldType) {
-    let mut x = pos.0 as i16;
-    let mut y = pos.1 as i16;
-    match dir {
-        N => {
-            y -= 1;
-        }
-        E => {
-            x += 1;
-        }
-        S => {
-            y += 1;
-        }
-        W => {
-            x -= 1;
-        }
-    };
-
-    if x < 0 || x >= MAX_MAP_SIZE as i16 || y < 0 || y >= MAX_MAP_SIZE as i16 {
-        ((u8::MAX, u8::MAX), Out)
-    } else {
-        ((x as u8, y as u8), map[x as usize][y as usize])
-    }
+fn travel_one_unit_fast(map: &DenseGameMap, pos: &Point, dir: &Direction) -> (Point, FieldType) {
+    let next = *pos + *dir;
+    (next, map.get(&next).copied().unwrap_or(Out))
 }
 
 
 fn travel_one_unit(map: &GameMap, pos: &GamePosition, dir: &Direction) -> (GamePosition, FieldType) {
     let x = pos.0;
     let y = pos.1;
-    let next: GamePosition = match dir {
-        N => {
-            (x, y - 1)
-        }
-        E => {
-            (x + 1, y)
-        }
-        S => {
-            (x, y + 1)
-        }
-        W => {
-            (x - 1, y)
-        }
-    };
+    let (dx, dy) = dir.delta();
+    let next: GamePosition = (x + dx as isize, y + dy as isize);
     map.get(&next).map(|&field_type| (next, field_type)).unwrap_or((next, Out))
 }
 
 /// checks if a map loops or not
 fn check_for_loop_fast(
-    map: &Game2DArray,
-    mut pos: LowMemGamePosition,
+    map: &DenseGameMap,
+    mut pos: Point,
     mut dir: Direction,
-    mut visited: HashSet<(LowMemGamePosition, Direction)>,
+    mut visited: DenseVisited,
 ) -> bool {
     loop {
         let (mut next_pos, mut next_field) = travel_one_unit_fast(&map, &pos, &dir);
         while next_field == Occupied {
-            dir = dir.next();
+            dir = dir.turn_right();
             (next_pos, next_field) = travel_one_unit_fast(&map, &pos, &dir);
         }
         match next_field {
             Free => {
-                if visited.contains(&(next_pos, dir)) {
+                let directions = visited.get_mut(&next_pos).expect("free fields are on the map");
+                if *directions & direction_bit(&dir) != 0 {
                     return true;
                 } else {
-                    visited.insert((next_pos, dir));
+                    *directions |= direction_bit(&dir);
                     pos = next_pos;
                 }
             }
@@ -224,7 +183,7 @@ fn check_for_loop(
     loop {
         let (mut next_pos, mut next_field) = travel_one_unit(&map, &pos, &dir);
         while next_field == Occupied {
-            dir = dir.next();
+            dir = dir.turn_right();
             (next_pos, next_field) = travel_one_unit(&map, &pos, &dir);
             // println!("Hit obstacle at ({x}, {y}) - changing dir to {:?}", dir);
         }
@@ -250,36 +209,36 @@ fn check_for_loop(
 /// these obstacles can only be put on not traveled fields, since otherwise the traveled path would
 /// not be possible
 fn traverse_map_with_obstacle_loops_fast(
-    mut map: Game2DArray,
-    start_pos: LowMemGamePosition,
-    mut visited: HashSet<(LowMemGamePosition, Direction)>,
+    mut map: DenseGameMap,
+    start_pos: Point,
+    mut visited: DenseVisited,
 ) -> usize {
     let mut pos = Some(start_pos);
     let mut dir = N;
     let mut obstacles_for_loop = 0;
-    while let Some((x, y)) = pos {
-        let (mut next_pos, mut next_field) = travel_one_unit_fast(&map, &(x, y), &dir);
+    while let Some(curr) = pos {
+        let (mut next_pos, mut next_field) = travel_one_unit_fast(&map, &curr, &dir);
         while next_field == Occupied {
-            dir = dir.next();
-            (next_pos, next_field) = travel_one_unit_fast(&map, &(x, y), &dir);
-            // println!("Hit obstacle at ({x}, {y}) - changing dir to {:?}", dir);
+            dir = dir.turn_right();
+            (next_pos, next_field) = travel_one_unit_fast(&map, &curr, &dir);
+            // println!("Hit obstacle at {curr} - changing dir to {:?}", dir);
         }
         if next_field == Out {
             // println!("Leaving map on {:?}", next_pos);
             pos = None; // terminates while loop
         } else { // must be free
-            if !visited.iter().any(|f| f.0 == next_pos) {
+            if visited.get(&next_pos) == Some(&0) {
                 // let mut modified_map = map.clone();
-                map[next_pos.0 as usize][next_pos.1 as usize] =  Occupied; // mutate instead of clone - perf. optimization
+                map.set(next_pos, Occupied); // mutate instead of clone - perf. optimization
                 let visited_copy = visited.clone();
-                if check_for_loop_fast(&map, pos.unwrap(), dir.next(), visited_copy) {
+                if check_for_loop_fast(&map, pos.unwrap(), dir.turn_right(), visited_copy) {
                     obstacles_for_loop += 1;
                 }
-                map[next_pos.0 as usize][next_pos.1 as usize] =  Free; // revert to original state - perf. optimization
+                map.set(next_pos, Free); // revert to original state - perf. optimization
             }
             // println!("at {:?}", next_pos);
             pos = Some(next_pos);
-            visited.insert((next_pos, dir));
+            *visited.get_mut(&next_pos).expect("free fields are on the map") |= direction_bit(&dir);
         }
     }
     // println!("obstacles: {:?}", obstacles_for_loop);
@@ -301,7 +260,7 @@ fn traverse_map_with_obstacle_loops(
     while let Some((x, y)) = pos {
         let (mut next_pos, mut next_field) = travel_one_unit(&map, &(x, y), &dir);
         while next_field == Occupied {
-            dir = dir.next();
+            dir = dir.turn_right();
             (next_pos, next_field) = travel_one_unit(&map, &(x, y), &dir);
         }
         if next_field == Out {
@@ -311,7 +270,7 @@ fn traverse_map_with_obstacle_loops(
                 // let mut modified_map = map.clone();
                 map.insert(next_pos, Occupied); // mutate instead of clone - perf. optimization
                 let visited_copy = visited.clone();
-                if check_for_loop(&map, pos.unwrap(), dir.next(), visited_copy) {
+                if check_for_loop(&map, pos.unwrap(), dir.turn_right(), visited_copy) {
                     obstacles_for_loop += 1;
                 }
                 map.insert(next_pos, Free); // revert to original state - perf. optimization
@@ -331,30 +290,82 @@ fn traverse_map_with_obstacle_loops(
 /// as you have already been on or if you leave the map. Count the loops (aka meeting your own path),
 /// jump back to the save-point walk one field forward and repeat the obstacle walk.
 /// do this until your non-obstacle walk leaves the map
-pub fn solve_day_06_part_02(input: String) -> usize {
-    let (map, start_pos) = parse_to_map(input);
+pub fn solve_day_06_part_02(input: String) -> Result<usize, ParseError> {
+    let (map, start_pos) = parse_to_map(input)?;
     let visited: HashSet<(GamePosition, Direction)> = HashSet::from([(start_pos, N)]);
-    traverse_map_with_obstacle_loops(map, start_pos, visited)
+    Ok(traverse_map_with_obstacle_loops(map, start_pos, visited))
 }
 
-pub fn solve_day_06_part_02_fast(input: String) -> usize {
-    let (map, start_pos) = parse_to_2d_arr(input);
+pub fn solve_day_06_part_02_fast(input: String) -> Result<usize, ParseError> {
+    let (map, start_pos) = parse_to_dense_grid(input)?;
     // println!("Map: {:?}", map);
-    let visited: HashSet<(LowMemGamePosition, Direction)> = HashSet::from([(start_pos, N)]);
-    traverse_map_with_obstacle_loops_fast(map, start_pos, visited)
+    let mut visited: DenseVisited = DenseGrid::filled(map.width(), map.height(), 0);
+    visited.set(start_pos, direction_bit(&N));
+    Ok(traverse_map_with_obstacle_loops_fast(map, start_pos, visited))
+}
+
+/// every position of the guard (with the direction they walked in) until they leave the map - the
+/// start included
+fn guard_walk(map: &DenseGameMap, start_pos: Point) -> Vec<(Point, Direction)> {
+    let (mut pos, mut dir) = (start_pos, N);
+    let mut walk = vec![(pos, dir)];
+
+    loop {
+        let (mut next_pos, mut next_field) = travel_one_unit_fast(map, &pos, &dir);
+        while next_field == Occupied {
+            dir = dir.turn_right();
+            (next_pos, next_field) = travel_one_unit_fast(map, &pos, &dir);
+        }
+        if next_field == Out {
+            return walk;
+        }
+        pos = next_pos;
+        walk.push((pos, dir));
+    }
+}
+
+/// the walk of part 1, to be watched in a terminal - `X` marks where the guard has already been
+pub fn playback_day_06(input: String) -> Result<Playback, ParseError> {
+    let (map, start_pos) = parse_to_dense_grid(input)?;
+    let walk = guard_walk(&map, start_pos);
+
+    let colours = CellColours::new()
+        .with('#', Colour::Grey)
+        .with('X', Colour::Blue)
+        .with('^', Colour::Red)
+        .with('>', Colour::Red)
+        .with('v', Colour::Red)
+        .with('<', Colour::Red);
+    Ok(Playback::new(walk.len(), move |i| {
+        let (guard, dir) = walk[i];
+        let visited: HashSet<Point> = walk[..i].iter().map(|(p, _)| *p).collect();
+        Renderer::default().render_with(&map, |p, field| match field {
+            _ if p == guard => match dir {
+                N => '^',
+                E => '>',
+                S => 'v',
+                _ => '<',
+            },
+            _ if visited.contains(&p) => 'X',
+            Occupied => '#',
+            _ => '.',
+        })
+    })
+    .colours(colours))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day06::{solve_day_06_part_01, solve_day_06_part_02_fast};
-    use crate::day06::Direction::*;
+    use crate::day06::{playback_day_06, solve_day_06_part_01, solve_day_06_part_02_fast};
+    use crate::util::grid::Direction::*;
     use crate::util::file::read_string;
+    use crate::util::playback::Scripted;
 
     #[test]
     fn should_solve_day_06_part_01() {
         let input = read_string("./src/day06/input.txt").unwrap();
 
-        let solution = solve_day_06_part_01(input);
+        let solution = solve_day_06_part_01(input).unwrap();
 
         println!("{solution}");
         assert_eq!(5534, solution);
@@ -374,22 +385,46 @@ mod tests {
 #.........
 ......#...".trim().to_string();
 
-        assert_eq!(41, solve_day_06_part_01(input));
+        assert_eq!(41, solve_day_06_part_01(input).unwrap());
+    }
+
+    #[test]
+    fn should_play_back_guard_walk() {
+        let input = "
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...".trim().to_string();
+        let mut playback = playback_day_06(input.clone()).unwrap();
+
+        assert_eq!(input, playback.frame());
+
+        playback.run(&mut Scripted::keys("G"), &mut std::io::sink()).unwrap();
+        let last = playback.frame();
+
+        assert_eq!(41, last.chars().filter(|c| "X^>v<".contains(*c)).count());
+        assert_eq!(Some(7), last.lines().last().and_then(|line| line.find('v')));
     }
 
     #[test]
     fn should_turn_90_degrees() {
-        assert_eq!(E, N.next());
-        assert_eq!(S, E.next());
-        assert_eq!(W, S.next());
-        assert_eq!(N, W.next());
+        assert_eq!(E, N.turn_right());
+        assert_eq!(S, E.turn_right());
+        assert_eq!(W, S.turn_right());
+        assert_eq!(N, W.turn_right());
     }
 
     #[test]
     fn should_solve_day_06_part_02() {
         let input = read_string("./src/day06/input.txt").unwrap();
 
-        let solution = solve_day_06_part_02_fast(input);
+        let solution = solve_day_06_part_02_fast(input).unwrap();
 
         println!("{solution}");
         assert_eq!(2262, solution);
@@ -420,6 +455,15 @@ mod tests {
 #.........
 ......#...".trim().to_string();
 
-        assert_eq!(6, solve_day_06_part_02_fast(input));
+        assert_eq!(6, solve_day_06_part_02_fast(input).unwrap());
+    }
+
+    #[test]
+    fn should_report_bad_map() {
+        let e = solve_day_06_part_01("..#\n.^x".to_string()).unwrap_err();
+        assert_eq!("day 06 (line 2, column 3): unexpected token 'x'", e.to_string());
+
+        let e = solve_day_06_part_02_fast("..#\n...".to_string()).unwrap_err();
+        assert_eq!("day 06: no start position '^' found", e.to_string());
     }
 }
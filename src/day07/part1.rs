@@ -1,3 +1,9 @@
+use std::str::FromStr;
+
+use crate::util::error::{parse_number, ParseError};
+
+use super::DAY;
+
 struct Calibration {
     target: usize,
     parts: Vec<usize>,
@@ -19,17 +25,30 @@ impl Operator {
     }
 }
 
-impl Calibration {
-    fn from_line(line: &str) -> Self {
-        let mut split1 = line.split(":");
-        let target = split1.next().unwrap().parse::<usize>().unwrap();
-        let parts = split1.next().unwrap().split_whitespace().map(|num| num.parse::<usize>().unwrap()).collect();
-        Calibration {
+/// `190: 10 19` - the error has no line since `from_str` only sees the line itself
+impl FromStr for Calibration {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (target, parts) = line
+            .split_once(':')
+            .ok_or(ParseError::new(DAY, "missing ':' after the target value"))?;
+        let target = parse_number(DAY, target).map_err(|e| e.at_token(line, target))?;
+        let parts: Vec<usize> = parts
+            .split_whitespace()
+            .map(|num| parse_number(DAY, num).map_err(|e| e.at_token(line, num)))
+            .collect::<Result<_, _>>()?;
+        if parts.is_empty() {
+            return Err(ParseError::new(DAY, "no numbers after ':'"));
+        }
+        Ok(Calibration {
             target,
             parts,
-        }
+        })
     }
+}
 
+impl Calibration {
 
     fn check_if_can_meet_target(&self, include_concat: bool) -> bool {
         let (&init, tail) = self.parts.split_first().expect("parts cannot be empty");
@@ -76,13 +95,18 @@ impl Calibration {
     }
 }
 
-pub fn solve_day_07(input: String, include_concat: bool) -> usize {
-    input
+pub fn solve_day_07(input: String, include_concat: bool) -> Result<usize, ParseError> {
+    let calibrations = input
         .lines()
-        .map(|l| Calibration::from_line(l))
+        .enumerate()
+        .map(|(i, l)| l.parse::<Calibration>().map_err(|e| e.at_line(i + 1)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(calibrations
+        .into_iter()
         .filter(|c| c.check_if_can_meet_target(include_concat))
         .map(|c| c.target)
-        .sum()
+        .sum())
 }
 
 #[cfg(test)]
@@ -95,7 +119,7 @@ mod tests {
     fn should_solve_day_07_part_01() {
         let input = read_string("./src/day07/input.txt").unwrap();
 
-        let solution = solve_day_07(input, false);
+        let solution = solve_day_07(input, false).unwrap();
 
         println!("{solution}");
         assert_eq!(303766880536, solution);
@@ -114,10 +138,19 @@ mod tests {
 21037: 9 7 18 13
 292: 11 6 16 20".trim().to_string();
 
-        assert_eq!(3749, solve_day_07(input, false));
+        assert_eq!(3749, solve_day_07(input, false).unwrap());
+
+        assert_eq!(3, solve_day_07("3: 1 1 1".to_string(), false).unwrap());
 
-        assert_eq!(3, solve_day_07("3: 1 1 1".to_string(), false));
+        assert_eq!(10, solve_day_07("10: 2 5 1".to_string(), false).unwrap());
+    }
+
+    #[test]
+    fn should_report_bad_calibration() {
+        let e = solve_day_07("190: 10 19\n3267 81 40 27".to_string(), false).unwrap_err();
+        assert_eq!("day 07 (line 2): missing ':' after the target value", e.to_string());
 
-        assert_eq!(10, solve_day_07("10: 2 5 1".to_string(), false));
+        let e = solve_day_07("83: 17 five".to_string(), false).unwrap_err();
+        assert_eq!("day 07 (line 1, column 8): 'five' is not a valid number", e.to_string());
     }
 }
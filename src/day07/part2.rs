@@ -7,7 +7,7 @@ mod tests {
     fn should_solve_day_07_part_02() {
         let input = read_string("./src/day07/input.txt").unwrap();
 
-        let solution = solve_day_07(input, true);
+        let solution = solve_day_07(input, true).unwrap();
 
         println!("{solution}");
         assert_eq!(337041851384440, solution);
@@ -27,6 +27,6 @@ mod tests {
 21037: 9 7 18 13
 292: 11 6 16 20".trim().to_string();
 
-        assert_eq!(11387, solve_day_07(input, true));
+        assert_eq!(11387, solve_day_07(input, true).unwrap());
     }
 }
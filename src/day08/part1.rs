@@ -1,6 +1,8 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::util::algebra::{cartesian_product_refs, mirror};
+use crate::util::grid::DenseGrid;
+use crate::util::point::Point;
 
 pub fn parse_antennas(input: &str) -> Vec<Vec<(usize, usize)>> {
     input
@@ -22,8 +24,7 @@ pub fn parse_antennas(input: &str) -> Vec<Vec<(usize, usize)>> {
 }
 
 pub fn solve_day_08_part_01(input: String) -> usize {
-    let width = input.lines().next().unwrap().trim().chars().count();
-    let height = input.lines().count();
+    let map = DenseGrid::from(input.as_str());
     let antenna_groups = parse_antennas(&input);
     let locations: HashSet<(i32, i32)> = antenna_groups
         .into_iter()
@@ -34,9 +35,7 @@ pub fn solve_day_08_part_01(input: String) -> usize {
                 let right_signed = (right.0 as i32, right.1 as i32);
                 mirror(left_signed, right_signed)
             })
-            .filter(|&(x, y)|
-                x >= 0 && y >= 0 && x < width as i32 && y < height as i32
-            )
+            .filter(|&(x, y)| map.contains(&Point::new(x, y)))
             .collect::<Vec<(i32, i32)>>()
         )
         .collect();
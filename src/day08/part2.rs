@@ -2,19 +2,15 @@ use std::collections::HashSet;
 
 use crate::day08::part1::parse_antennas;
 use crate::util::algebra::{apply_vec, cartesian_product_refs, get_vector_between};
-
-fn is_within_map(pos: (i32, i32), width: i32, height: i32) -> bool {
-    let x = pos.0;
-    let y = pos.1;
-    x >= 0 && y >= 0 && x < width && y < height
-}
+use crate::util::grid::DenseGrid;
+use crate::util::point::Point;
 
 /// traverses into a vec direction from a given start point and collects every visited point into a vec
 /// (vec length matters)
-fn collect_locations(from: (i32, i32), vec: (i32, i32), width: usize, height: usize) -> Vec<(i32, i32)> {
+fn collect_locations(from: (i32, i32), vec: (i32, i32), map: &DenseGrid<char>) -> Vec<(i32, i32)> {
     let mut result = vec![from];
     let mut next = from;
-    while is_within_map(next, width as i32, height as i32) {
+    while map.contains(&Point::new(next.0, next.1)) {
         result.push(next);
         next = apply_vec(next, vec);
     }
@@ -23,8 +19,7 @@ fn collect_locations(from: (i32, i32), vec: (i32, i32), width: usize, height: us
 }
 
 pub fn solve_day_08_part_02(input: String) -> usize {
-    let width = input.lines().next().unwrap().trim().chars().count();
-    let height = input.lines().count();
+    let map = DenseGrid::from(input.as_str());
     let antenna_groups = parse_antennas(&input);
     let locations: HashSet<(i32, i32)> = antenna_groups
         .into_iter()
@@ -34,7 +29,7 @@ pub fn solve_day_08_part_02(input: String) -> usize {
                 let left_signed = (left.0 as i32, left.1 as i32);
                 let right_signed = (right.0 as i32, right.1 as i32);
                 let vec = get_vector_between(left_signed, right_signed);
-                collect_locations(right_signed, vec, width, height)
+                collect_locations(right_signed, vec, &map)
             })
             .collect::<Vec<(i32, i32)>>()
         )
@@ -1,2 +1,13 @@
+use crate::util::error::ParseError;
+
 pub mod part1;
 pub mod part2;
+
+const DAY: u8 = 9;
+
+/// the disk map is a single line of digits - `idx` is the char index within that line
+fn parse_length(idx: usize, c: char) -> Result<usize, ParseError> {
+    c.to_digit(10)
+        .map(|length| length as usize)
+        .ok_or(ParseError::new(DAY, format!("expected a digit but got {c:?}")).at(1, idx + 1))
+}
@@ -1,6 +1,8 @@
 use std::iter::repeat;
 
+use crate::day09::parse_length;
 use crate::day09::part1::MemoryType::{Free, Occupied};
+use crate::util::error::ParseError;
 
 #[derive(Debug, PartialEq, Default, Clone)]
 enum MemoryType {
@@ -20,14 +22,16 @@ struct Disk {
     units: Vec<MemoryUnit>,
 }
 
-impl From<&str> for Disk {
-    fn from(value: &str) -> Self { // can be called from outside
+impl TryFrom<&str> for Disk {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> { // can be called from outside
         let mut disk = Disk::default();
         value
             .chars()
             .enumerate()
-            .for_each(|(idx, c)| {
-                let length = c.to_digit(10).unwrap() as usize;
+            .try_for_each(|(idx, c)| {
+                let length = parse_length(idx, c)?;
                 let is_file = idx % 2 == 0;
                 let next_unit = if is_file {
                     MemoryUnit { pos: idx, memory_type: Occupied { file_id: idx / 2 } }
@@ -36,9 +40,10 @@ impl From<&str> for Disk {
                 };
                 let mut next_chunk: Vec<MemoryUnit> = repeat(next_unit).take(length).collect();
                 disk.units.append(&mut next_chunk);
-            });
+                Ok(())
+            })?;
 
-        disk
+        Ok(disk)
     }
 }
 
@@ -87,10 +92,10 @@ impl Disk {
 }
 
 
-pub fn solve_day_09_part_01(input: String) -> usize {
-    Disk::from(input.as_str())
+pub fn solve_day_09_part_01(input: String) -> Result<usize, ParseError> {
+    Ok(Disk::try_from(input.as_str())?
         .fragment()
-        .checksum()
+        .checksum())
 }
 
 #[cfg(test)]
@@ -103,7 +108,7 @@ mod tests {
     fn should_solve_day_09_part_01() {
         let input = read_string("./src/day09/input.txt").unwrap();
 
-        let solution = solve_day_09_part_01(input);
+        let solution = solve_day_09_part_01(input).unwrap();
 
         println!("{solution}");
         assert_eq!(6398608069280, solution);
@@ -113,6 +118,13 @@ mod tests {
     fn should_solve_day_09_part_01_sample() {
         let input = "2333133121414131402".to_string();
 
-        assert_eq!(1928, solve_day_09_part_01(input));
+        assert_eq!(1928, solve_day_09_part_01(input).unwrap());
+    }
+
+    #[test]
+    fn should_report_non_digit() {
+        let e = solve_day_09_part_01("23331x".to_string()).unwrap_err();
+
+        assert_eq!("day 09 (line 1, column 6): expected a digit but got 'x'", e.to_string());
     }
 }
@@ -1,5 +1,7 @@
 use std::fmt::{Display, Formatter};
-use std::time::Instant;
+
+use crate::day09::parse_length;
+use crate::util::error::ParseError;
 
 #[derive(Debug, PartialEq, Clone)]
 struct FreeBlock {
@@ -55,8 +57,10 @@ struct Disk {
     size: usize,
 }
 
-impl From<&str> for Disk {
-    fn from(value: &str) -> Self {
+impl TryFrom<&str> for Disk {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
         let mut disk = Disk::default();
         disk.size = value.len();
         let mut index_counter = 0;
@@ -64,8 +68,8 @@ impl From<&str> for Disk {
         value
             .chars()
             .enumerate()
-            .for_each(|(idx, c)| {
-                let length = c.to_digit(10).unwrap() as usize;
+            .try_for_each(|(idx, c)| {
+                let length = parse_length(idx, c)?;
                 let is_file = idx % 2 == 0;
                 if is_file {
                     disk.files.push(File::new(idx / 2, index_counter, length));
@@ -73,9 +77,10 @@ impl From<&str> for Disk {
                     disk.free_blocks.push(FreeBlock::new(index_counter, length));
                 }
                 index_counter += length;
-            });
+                Ok(())
+            })?;
 
-        disk
+        Ok(disk)
     }
 }
 
@@ -142,17 +147,10 @@ impl Disk {
     }
 }
 
-pub fn solve_day_09_part_02(input: String) -> usize {
-    let ins = Instant::now();
-    let mut disk = Disk::from(input.as_str());
-    println!("{}ms - unzipped: {disk}", ins.elapsed().as_millis());
-    let ins = Instant::now();
+pub fn solve_day_09_part_02(input: String) -> Result<usize, ParseError> {
+    let mut disk = Disk::try_from(input.as_str())?;
     disk.defragment();
-    println!("{}ms - defragmented: {disk}", ins.elapsed().as_millis());
-    let ins = Instant::now();
-    let r = disk.checksum();
-    println!("{}ms", ins.elapsed().as_millis());
-    r
+    Ok(disk.checksum())
 }
 
 #[cfg(test)]
@@ -165,7 +163,7 @@ mod tests {
     fn should_solve_day_09_part_02() {
         let input = read_string("./src/day09/input.txt").unwrap();
 
-        let solution = solve_day_09_part_02(input);
+        let solution = solve_day_09_part_02(input).unwrap();
 
         println!("{solution}");
         assert_eq!(6427437134372, solution);
@@ -176,6 +174,6 @@ mod tests {
     fn should_solve_day_09_part_02_sample() {
         let input = "2333133121414131402".trim().to_string();
 
-        assert_eq!(2858, solve_day_09_part_02(input));
+        assert_eq!(2858, solve_day_09_part_02(input).unwrap());
     }
 }
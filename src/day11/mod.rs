@@ -1,71 +1,69 @@
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use crate::util::error::{parse_number, ParseError};
+use crate::util::memo::Memo;
 
-use rayon::prelude::*;
+const DAY: u8 = 11;
 
 struct MagicStones {
     stones: Vec<usize>,
-    stone_cache: Arc<Mutex<HashMap<(usize, u8), usize>>>, // just an experiment, but actually the
-    // single threaded variant (w.o. mutex, arc & rayon) is twice as fast, which makes sense
-    // considering that probably most of the time the cache is locked anyways...
 }
 
-impl From<&str> for MagicStones {
-    fn from(value: &str) -> Self {
-        MagicStones {
-            stones: value.split_whitespace().map(|n| n.parse::<usize>().expect("Nan")).collect(),
-            stone_cache: Arc::new(Mutex::new(HashMap::new())),
-        }
+impl TryFrom<&str> for MagicStones {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let stones = value
+            .lines()
+            .enumerate()
+            .flat_map(|(i, line)| {
+                line.split_whitespace()
+                    .map(move |n| parse_number(DAY, n).map_err(|e| e.at_token(line, n).at_line(i + 1)))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(MagicStones { stones })
     }
 }
 
 impl MagicStones {
+    /// one memo for all stones - a rayon variant sharing a (sharded) memo was slower, as most of
+    /// the work is looking things up anyways
     pub fn simulate(self, iterations: u8) -> usize {
+        let mut memo = Memo::new();
         self.stones
-            .clone()
-            .into_par_iter()
-            .map(|s| self.blink(s, iterations))
+            .iter()
+            .map(|&s| blink(&mut memo, s, iterations))
             .sum()
     }
+}
 
-    fn blink(&self, element: usize, iterations: u8) -> usize {
-        if iterations == 0 {
-            return 1;
-        }
-
-        let hash_key = (element, iterations);
-        {
-            match Arc::clone(&self.stone_cache).lock().unwrap().get(&hash_key) {
-                None => {}
-                Some(c) => { return *c; }
-            }
-        }
+/// `None` for stones with an odd number of digits
+fn split(element: usize) -> Option<(usize, usize)> {
+    let x_str = element.to_string();
+    if !x_str.len().is_multiple_of(2) {
+        return None;
+    }
+    let split_idx = x_str.len() / 2;
+    let left = x_str[..split_idx].parse::<usize>().unwrap();
+    let right = x_str[split_idx..].parse::<usize>().unwrap();
+    Some((left, right))
+}
 
+fn blink(memo: &mut Memo<(usize, u8), usize>, element: usize, iterations: u8) -> usize {
+    if iterations == 0 {
+        return 1;
+    }
 
-        let count = match element {
-            0 => {
-                self.blink(1, iterations - 1)
-            }
-            x  if x.to_string().len() % 2 == 0 => {
-                let x_str = x.to_string();
-                let split_idx = x_str.len() / 2;
-                let left = x_str[..split_idx].parse::<usize>().unwrap();
-                let right = x_str[split_idx..].parse::<usize>().unwrap();
-                self.blink(left, iterations - 1) + self.blink(right, iterations - 1)
-            }
-            _ => self.blink(element * 2024, iterations - 1)
-        };
-
-        {
-            Arc::clone(&self.stone_cache).lock().unwrap().insert(hash_key, count);
+    memo.compute((element, iterations), |memo| match (element, split(element)) {
+        (0, _) => blink(memo, 1, iterations - 1),
+        (_, Some((left, right))) => {
+            blink(memo, left, iterations - 1) + blink(memo, right, iterations - 1)
         }
-
-        count
-    }
+        _ => blink(memo, element * 2024, iterations - 1),
+    })
 }
 
-pub fn solve_day_11(input: &str, generations: u8) -> usize {
-    MagicStones::from(input).simulate(generations)
+pub fn solve_day_11(input: &str, generations: u8) -> Result<usize, ParseError> {
+    Ok(MagicStones::try_from(input)?.simulate(generations))
 }
 
 #[cfg(test)]
@@ -78,7 +76,7 @@ mod tests {
     fn should_solve_day_11() {
         let input = read_string("./src/day11/input.txt").unwrap();
 
-        let solution = solve_day_11(input.as_str(), 25);
+        let solution = solve_day_11(input.as_str(), 25).unwrap();
 
         println!("{solution}");
         assert_eq!(199982, solution);
@@ -88,9 +86,16 @@ mod tests {
     fn should_solve_day_11_part_02() {
         let input = read_string("./src/day11/input.txt").unwrap();
 
-        let solution = solve_day_11(input.as_str(), 75);
+        let solution = solve_day_11(input.as_str(), 75).unwrap();
 
         assert_eq!(237149922829154, solution);
         println!("{solution}");
     }
+
+    #[test]
+    fn should_report_bad_stone() {
+        let e = solve_day_11("125 17 -1", 25).unwrap_err();
+
+        assert_eq!("day 11 (line 1, column 8): '-1' is not a valid number", e.to_string());
+    }
 }
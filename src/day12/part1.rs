@@ -1,7 +1,6 @@
 use std::collections::{HashSet, VecDeque};
-use std::time::Instant;
 
-use crate::util::grid::Grid;
+use crate::util::grid::DenseGrid;
 use crate::util::point::Point;
 
 #[derive(Debug)]
@@ -18,7 +17,7 @@ impl Region {
         }
     }
 
-    fn get_perimeter(&self, grid: &Grid<char>) -> usize {
+    fn get_perimeter(&self, grid: &DenseGrid<char>) -> usize {
         self.area
             .iter()
             .map(|p| {
@@ -37,7 +36,7 @@ impl Region {
     }
 }
 
-impl Grid<char> {
+impl DenseGrid<char> {
     pub fn neighbors_incl_outs<'a>(&'a self, p: &'a Point)
                                    -> impl Iterator<Item=(Point, char)> + 'a {
         [p.left(), p.right(), p.up(), p.down()]
@@ -49,18 +48,18 @@ impl Grid<char> {
     }
 }
 
-pub fn get_regions(grid: &Grid<char>) -> Vec<Region> {
+pub fn get_regions(grid: &DenseGrid<char>) -> Vec<Region> {
     let mut indexed: HashSet<Point> = HashSet::new();
     let mut regions: Vec<Region> = Vec::new();
 
     for (p, c) in grid.iter() {
-        if indexed.contains(p) {
+        if indexed.contains(&p) {
             continue;
         }
 
         let mut next_region = Region::new(*c);
         let mut queue = VecDeque::new();
-        queue.push_back(*p);
+        queue.push_back(p);
 
         while let Some(curr) = queue.pop_front() {
             next_region.area.insert(curr);
@@ -83,22 +82,11 @@ pub fn get_regions(grid: &Grid<char>) -> Vec<Region> {
 }
 
 pub fn solve_day_12_part_01(input: &str) -> usize {
-    let grid = Grid::from(input);
-
-    let i = Instant::now();
-    let regions = get_regions(&grid);
-    println!("bfs-flood-fill took {}ms", i.elapsed().as_millis());
-
-    let i = Instant::now();
-    let c = regions
+    let grid = DenseGrid::from(input);
+    get_regions(&grid)
         .iter()
-        .map(|r| {
-            let cost = r.get_area() * r.get_perimeter(&grid);
-            cost
-        })
-        .sum();
-    println!("edge-counting took {}ms", i.elapsed().as_millis());
-    c
+        .map(|r| r.get_area() * r.get_perimeter(&grid))
+        .sum()
 }
 
 #[cfg(test)]
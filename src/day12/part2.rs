@@ -1,33 +1,18 @@
 use std::collections::HashSet;
-use std::time::Instant;
 
 use crate::day12::part1::get_regions;
-use crate::util::grid::Grid;
+use crate::util::grid::DenseGrid;
 use crate::util::point::Point;
 
 pub fn solve_day_12_part_02(input: &str) -> usize {
-    let grid = Grid::from(input);
-
-    let i = Instant::now();
-    let regions = get_regions(&grid);
-    println!("bfs-flood-fill took {}ms", i.elapsed().as_millis());
-
-    let i = Instant::now();
-    let c = regions
+    let grid = DenseGrid::from(input);
+    get_regions(&grid)
         .iter()
-        .map(|r| {
-            let edge_count = get_edge_count(r.token, &r.area, &grid);
-
-            let cost = r.get_area() * edge_count;
-            // println!("{} - size: {} - edges: {} -> {}", r.token, r.get_area(), edge_count, cost);
-            cost
-        })
-        .sum();
-    println!("edge-counting took {}ms", i.elapsed().as_millis());
-    c
+        .map(|r| r.get_area() * get_edge_count(r.token, &r.area, &grid))
+        .sum()
 }
 
-fn get_edge_count(own: char, edge_points: &HashSet<Point>, grid: &Grid<char>) -> usize {
+fn get_edge_count(own: char, edge_points: &HashSet<Point>, grid: &DenseGrid<char>) -> usize {
     edge_points
         .iter()
         .map(|e| {
@@ -1,5 +1,10 @@
+use crate::util::algebra::linear::LinearSystem;
+use crate::util::error::ParseError;
+use crate::util::parse::sections;
 use crate::util::point::Point;
 
+use super::DAY;
+
 #[derive(Debug)]
 pub(crate) struct Arcade {
     pub(crate) machines: Vec<ClawMachine>,
@@ -23,101 +28,79 @@ pub(crate) struct ButtonBehavior {
     pub(crate) y: i128,
 }
 
-impl From<&str> for Arcade {
-    fn from(value: &str) -> Self {
-        let mut machines = Vec::new();
-
-        let mut a: ButtonBehavior = ButtonBehavior::default();
-        let mut b: ButtonBehavior = ButtonBehavior::default();
-
-        const A_PREFIX: &'static str = "Button A: X+";
-        const B_PREFIX: &'static str = "Button B: X+";
-        const PRICE_PREFIX: &'static str = "Prize: X=";
-        const BUTTON_SPLITTER: &'static str = ", Y+";
-        const PRICE_SPLITTER: &'static str = ", Y=";
-
-        for line in value.lines().map(|l| l.trim()) {
-            if line.starts_with(A_PREFIX) {
-                let mut numbers = line[A_PREFIX.len()..]
-                    .split(BUTTON_SPLITTER)
-                    .map(|n| n.parse::<i128>().expect("NaN"));
-                a = ButtonBehavior {
-                    x: numbers.next().expect("Should have X"),
-                    y: numbers.next().expect("Should have Y"),
-                }
-            }
-            if line.starts_with(B_PREFIX) {
-                let mut numbers = line[B_PREFIX.len()..]
-                    .split(BUTTON_SPLITTER)
-                    .map(|n| n.parse::<i128>().expect("NaN"));
-                b = ButtonBehavior {
-                    x: numbers.next().expect("Should have X"),
-                    y: numbers.next().expect("Should have Y"),
-                }
-            }
-            if line.starts_with(PRICE_PREFIX) {
-                let mut numbers = line[PRICE_PREFIX.len()..]
-                    .split(PRICE_SPLITTER)
-                    .map(|n| n.parse::<i128>().expect("NaN"));
-                let price_location: Point<i128> = Point::new(
-                    numbers.next().expect("Should have X"),
-                    numbers.next().expect("Should have Y"),
-                );
-
-                machines.push(ClawMachine {
-                    a: a.clone(),
-                    b: b.clone(),
-                    prize_location: price_location,
+impl TryFrom<&str> for Arcade {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let machines = sections(DAY, value)
+            .iter()
+            .map(|section| {
+                let mut lines = section.lines();
+                let mut pair = |pattern: &str| {
+                    let line = lines
+                        .next()
+                        .ok_or_else(|| section.error(format!("expected '{pattern}'")))?;
+                    let [x, y] = line.scan(pattern)?;
+                    Ok::<_, ParseError>((line.number(x)?, line.number(y)?))
+                };
+
+                let (x, y) = pair("Button A: X+{}, Y+{}")?;
+                let a = ButtonBehavior { x, y };
+                let (x, y) = pair("Button B: X+{}, Y+{}")?;
+                let b = ButtonBehavior { x, y };
+                let (x, y) = pair("Prize: X={}, Y={}")?;
+                Ok(ClawMachine {
+                    a,
+                    b,
+                    prize_location: Point::new(x, y),
                 })
-            }
-        }
+            })
+            .collect::<Result<_, _>>()?;
 
-        Arcade { machines }
+        Ok(Arcade { machines })
     }
 }
 
-pub fn solve_day_13_part_01(input: &str) -> u128 {
-    let arcade = Arcade::from(input);
-    let mut tokens = 0;
-
-    for machine in arcade.machines {
-        if let Some((a_presses, b_presses)) = cramer_integer_solve(
-            machine.a.x as i128,
-            machine.a.y as i128,
-            machine.b.x as i128,
-            machine.b.y as i128,
-            machine.prize_location.x as i128,
-            machine.prize_location.y as i128,
-        ) {
-            if a_presses >= 0 && b_presses >= 0 {
-                tokens += a_presses as u128 * 3 + b_presses as u128;
-            }
-        }
-    }
+fn too_big() -> ParseError {
+    ParseError::new(DAY, "the claw machine numbers are too big")
+}
 
-    tokens
+impl ClawMachine {
+    /// fewest tokens to reach the prize - `None` if it can't be reached with whole button presses,
+    /// an error if the numbers are too big to even find that out
+    pub(crate) fn cheapest_tokens(&self) -> Result<Option<u128>, ParseError> {
+        let system = LinearSystem::from_integers(
+            &[vec![self.a.x, self.b.x], vec![self.a.y, self.b.y]],
+            &[self.prize_location.x, self.prize_location.y],
+        );
+        let Some(presses) = system
+            .cheapest_non_negative_integer(&[3, 1])
+            .map_err(|_| too_big())?
+        else {
+            return Ok(None);
+        };
+        (presses[0] as u128)
+            .checked_mul(3)
+            .and_then(|a| a.checked_add(presses[1] as u128))
+            .map(Some)
+            .ok_or_else(too_big)
+    }
 }
 
-/// best explanation https://www.purplemath.com/modules/cramers.htm
-pub(crate) fn cramer_integer_solve(
-    ax: i128,
-    ay: i128,
-    bx: i128,
-    by: i128,
-    px: i128,
-    py: i128,
-) -> Option<(i128, i128)> {
-    let det = ax * by - ay * bx;
-    let det_sub_a = px * by - py * bx;
-    let det_sub_b = ax * py - ay * px;
-
-    if det == 0 || det_sub_a % det != 0 || det_sub_b % det != 0 {
-        None
-    } else {
-        Some((det_sub_a / det, det_sub_b / det))
+impl Arcade {
+    /// fewest tokens to win every prize that can be won
+    pub(crate) fn cheapest_tokens(&self) -> Result<u128, ParseError> {
+        self.machines.iter().try_fold(0u128, |sum, machine| {
+            sum.checked_add(machine.cheapest_tokens()?.unwrap_or(0))
+                .ok_or_else(too_big)
+        })
     }
 }
 
+pub fn solve_day_13_part_01(input: &str) -> Result<u128, ParseError> {
+    Arcade::try_from(input)?.cheapest_tokens()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::util::file::read_string;
@@ -128,7 +111,7 @@ mod tests {
     fn should_solve_day_13_part_01() {
         let input = read_string("./src/day13/input.txt").unwrap();
 
-        let solution = solve_day_13_part_01(&input);
+        let solution = solve_day_13_part_01(&input).unwrap();
 
         println!("{solution}");
     }
@@ -153,6 +136,71 @@ mod tests {
         Prize: X=18641, Y=10279
         ";
 
-        assert_eq!(480, solve_day_13_part_01(input));
+        assert_eq!(480, solve_day_13_part_01(input).unwrap());
+    }
+
+    #[test]
+    fn should_report_machines_beyond_i128() {
+        let input = "
+        Button A: X+170141183460469231731687303715884105727, Y+1
+        Button B: X+1, Y+170141183460469231731687303715884105727
+        Prize: X=170141183460469231731687303715884105727, Y=170141183460469231731687303715884105727
+        ";
+
+        assert_eq!(
+            "day 13: the claw machine numbers are too big",
+            solve_day_13_part_01(input).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn should_report_totals_beyond_u128() {
+        // 3 * 2^126 tokens each - fits on its own but not twice
+        let machine = "
+        Button A: X+1, Y+0
+        Button B: X+0, Y+1
+        Prize: X=85070591730234615865843651857942052864, Y=0
+        ";
+        assert_eq!(Ok(3 << 126), solve_day_13_part_01(machine));
+
+        assert_eq!(
+            "day 13: the claw machine numbers are too big",
+            solve_day_13_part_01(&format!("{machine}\n{machine}"))
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn should_report_bad_button() {
+        let input = "
+        Button A: X+94, Y+34
+        Button B: X+22, Y+6x7";
+
+        let e = solve_day_13_part_01(input).unwrap_err();
+
+        assert_eq!(
+            "day 13 (line 3, column 27): '6x7' is not a valid number",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn should_solve_machine_with_parallel_buttons() {
+        // no unique solution - the cheap B button alone or the A button with its bigger steps
+        let input = "
+        Button A: X+2, Y+2
+        Button B: X+1, Y+1
+        Prize: X=5, Y=5
+
+        Button A: X+6, Y+6
+        Button B: X+1, Y+1
+        Prize: X=13, Y=13
+
+        Button A: X+4, Y+4
+        Button B: X+6, Y+6
+        Prize: X=9, Y=9";
+
+        assert_eq!(5 + 7, solve_day_13_part_01(input).unwrap());
     }
 }
@@ -1,9 +1,14 @@
-use crate::util::point::Point;
+use std::str::FromStr;
+
+use crate::util::error::{parse_number, ParseError};
+use crate::util::point::{Point, Vec2};
+
+use super::DAY;
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct Robot {
     start_pos: Point,
-    velocity: Point,
+    velocity: Vec2,
 }
 
 impl Robot {
@@ -19,7 +24,7 @@ impl Robot {
         Point::new(bounded_x, bounded_y)
     }
 
-    fn new(start_pos: Point, velocity: Point) -> Self {
+    fn new(start_pos: Point, velocity: Vec2) -> Self {
         Robot {
             start_pos,
             velocity,
@@ -33,33 +38,42 @@ pub(crate) struct Lobby {
     pub(crate) height: u32,
     pub(crate) robots: Vec<Robot>,
 }
+/// `p=0,4 v=3,-3` - the error has no line number since `from_str` only sees the line itself
+impl FromStr for Robot {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let xy = |prefix: &str, info: &str| -> Result<(i32, i32), ParseError> {
+            let (x, y) = info
+                .strip_prefix(prefix)
+                .and_then(|info| info.split_once(','))
+                .ok_or(ParseError::new(DAY, format!("expected '{prefix}x,y'")).at_token(line, info))?;
+            Ok((
+                parse_number(DAY, x).map_err(|e| e.at_token(line, x))?,
+                parse_number(DAY, y).map_err(|e| e.at_token(line, y))?,
+            ))
+        };
+
+        let (pos_info, velocity_info) = line
+            .trim()
+            .split_once(' ')
+            .ok_or(ParseError::new(DAY, "expected position and velocity separated by ' '"))?;
+        let ((x, y), (vx, vy)) = (xy("p=", pos_info)?, xy("v=", velocity_info)?);
+        Ok(Robot::new(Point::new(x, y), Vec2::new(vx, vy)))
+    }
+}
+
 impl TryFrom<(&str, u32, u32)> for Lobby {
-    type Error = String;
+    type Error = ParseError;
 
     fn try_from(value: (&str, u32, u32)) -> Result<Self, Self::Error> {
         let (input, width, height) = value;
         let robots: Vec<Robot> = input
             .lines()
-            .filter_map(|mut l| {
-                l = l.trim();
-                if !l.starts_with("p=") {
-                    return None;
-                }
-
-                let (pos_info, velocity_info) = l.split_once(" ").unwrap();
-                let pos_info = pos_info[2..].split_once(",").unwrap();
-                let velocity_info = velocity_info[2..].split_once(",").unwrap();
-                let pos_info = Point::new(
-                    pos_info.0.parse::<i32>().unwrap(),
-                    pos_info.1.parse::<i32>().unwrap(),
-                );
-                let velocity_info = Point::new(
-                    velocity_info.0.parse::<i32>().unwrap(),
-                    velocity_info.1.parse::<i32>().unwrap(),
-                );
-                Some(Robot::new(pos_info, velocity_info))
-            })
-            .collect();
+            .enumerate()
+            .filter(|(_, l)| l.trim().starts_with("p="))
+            .map(|(i, l)| l.parse::<Robot>().map_err(|e| e.at_line(i + 1)))
+            .collect::<Result<_, _>>()?;
 
         Ok(Lobby {
             width,
@@ -74,8 +88,8 @@ pub fn solve_day_14_part_01(
     canvas_width: u32,
     canvas_height: u32,
     iterations: u32,
-) -> u32 {
-    let lobby = Lobby::try_from((input, canvas_width, canvas_height)).unwrap();
+) -> Result<u32, ParseError> {
+    let lobby = Lobby::try_from((input, canvas_width, canvas_height))?;
 
     let positions_at_target_time: Vec<Point> = lobby
         .robots
@@ -105,7 +119,7 @@ pub fn solve_day_14_part_01(
         .count();
 
     // println!("{q1}/{q2}/{q3}/{q4}");
-    (q1 * q2 * q3 * q4) as u32
+    Ok((q1 * q2 * q3 * q4) as u32)
 }
 
 #[cfg(test)]
@@ -117,7 +131,7 @@ mod tests {
     fn should_solve_day_14_part_01() {
         let input = read_string("./src/day14/input.txt").unwrap();
 
-        let solution = solve_day_14_part_01(&input, 101, 103, 100);
+        let solution = solve_day_14_part_01(&input, 101, 103, 100).unwrap();
 
         println!("{solution}");
     }
@@ -139,7 +153,7 @@ mod tests {
             p=9,5 v=-3,-3"
             .trim();
 
-        let actual = solve_day_14_part_01(&input, 11, 7, 100);
+        let actual = solve_day_14_part_01(&input, 11, 7, 100).unwrap();
 
         assert_eq!(12, actual);
     }
@@ -170,9 +184,18 @@ mod tests {
         assert_eq!(lobby.robots.len(), 12);
     }
 
+    #[test]
+    fn should_report_bad_robot() {
+        let input = "p=0,4 v=3,-3\np=6,3 v=-1;-3";
+
+        let e = Lobby::try_from((input, 10, 11)).unwrap_err();
+
+        assert_eq!("day 14 (line 2, column 7): expected 'v=x,y'", e.to_string());
+    }
+
     #[test]
     fn should_project_single_point_neg_wrap_around() {
-        let r = Robot::new(Point::new(0, 0), Point::new(-1, -1));
+        let r = Robot::new(Point::new(0, 0), Vec2::new(-1, -1));
 
         let actual = Robot::project_pos(&r, 3, 10, 10);
 
@@ -181,7 +204,7 @@ mod tests {
 
     #[test]
     fn should_project_single_point_pos_wrap_around() {
-        let r = Robot::new(Point::new(0, 0), Point::new(3, 3));
+        let r = Robot::new(Point::new(0, 0), Vec2::new(3, 3));
 
         let actual = Robot::project_pos(&r, 4, 10, 10);
 
@@ -190,7 +213,7 @@ mod tests {
 
     #[test]
     fn should_project_single_robot() {
-        let r = Robot::new(Point::new(2, 4), Point::new(2, -3));
+        let r = Robot::new(Point::new(2, 4), Vec2::new(2, -3));
 
         assert_eq!(Point::new(2, 4), Robot::project_pos(&r, 0, 11, 7));
         assert_eq!(Point::new(4, 1), Robot::project_pos(&r, 1, 11, 7));
@@ -1,156 +1,146 @@
 use crate::day14::part1::Lobby;
+use crate::util::animation::Recorder;
+use crate::util::error::ParseError;
+use crate::util::grid::DenseGrid;
+use crate::util::image::{Image, Rgb};
+use crate::util::number::crt;
+use crate::util::playback::{CellColours, Colour, Playback};
 use crate::util::point::Point;
-use std::fs;
-use std::fs::File;
-use std::io::{self, Write};
-use std::path::Path;
+use crate::util::render::Renderer;
 
 const WIDTH: usize = 101;
 const HEIGHT: usize = 103;
 
-#[allow(dead_code)]
-fn write_to_std_out(iteration: usize, points: &Vec<Point>) {
-    let mut display: [[bool; WIDTH]; HEIGHT] = [[false; WIDTH]; HEIGHT];
-
-    points
-        .iter()
-        .for_each(|p| display[p.y as usize][p.x as usize] = true);
+fn to_text(points: &[Point]) -> String {
+    let mut display = DenseGrid::filled(WIDTH, HEIGHT, ' ');
+    points.iter().for_each(|p| display.set(*p, 'X'));
 
-    println!("#{iteration}:");
-    display
-        .iter()
-        .map(|row| {
-            row.iter()
-                .map(|&cell_occupied| if cell_occupied { 'X' } else { ' ' })
-                .collect::<String>()
-        })
-        .for_each(|line| println!("{line}"));
+    Renderer::default().render(&display)
 }
 
 #[allow(dead_code)]
-fn save_to_ppm(iteration: usize, points: &Vec<Point>) {
-    let mut display: [[bool; WIDTH]; HEIGHT] = [[false; WIDTH]; HEIGHT];
+fn write_to_std_out(iteration: usize, points: &Vec<Point>) {
+    println!("#{iteration}:");
+    println!("{}", to_text(points));
+}
 
-    points
+fn positions(lobby: &Lobby, iteration: u32) -> Vec<Point> {
+    lobby
+        .robots
         .iter()
-        .for_each(|p| display[p.y as usize][p.x as usize] = true);
-
-    let file_name = format!("./{iteration}.ppm");
-    let mut file = File::create(file_name).expect("Failed to create file");
+        .map(|r| crate::day14::part1::Robot::project_pos(r, iteration, lobby.width, lobby.height))
+        .collect()
+}
 
-    // Write PPM header (P3 means ASCII, 255 is max color value)
-    writeln!(file, "P3\n{} {}\n255", WIDTH, HEIGHT).unwrap();
+fn frame(lobby: &Lobby, iteration: u32) -> Image {
+    Image::from_points(
+        &positions(lobby, iteration),
+        WIDTH,
+        HEIGHT,
+        Rgb::BLACK,
+        Rgb::WHITE,
+    )
+}
 
-    // Write pixel data
-    for row in &display {
-        for &cell in row {
-            let pixel_value = if cell { "255 255 255" } else { "0 0 0" }; // RGB format
-            writeln!(file, "{}", pixel_value).unwrap();
-        }
-    }
+/// x positions repeat every `WIDTH` seconds and y positions every `HEIGHT` seconds - the tree is
+/// where the robots huddle together on both axes, so the tightest phase of each axis is enough and
+/// the CRT puts them back together
+fn tree_iteration(lobby: &Lobby) -> u32 {
+    let spread = |iteration: u32, axis: fn(&Point) -> i32| {
+        let values: Vec<i64> = positions(lobby, iteration)
+            .iter()
+            .map(|p| axis(p) as i64)
+            .collect();
+        let sum: i64 = values.iter().sum();
+        // variance times n^2 - no need to divide when only comparing
+        values.len() as i64 * values.iter().map(|v| v * v).sum::<i64>() - sum * sum
+    };
+    let x_phase = (0..WIDTH as u32).min_by_key(|&i| spread(i, |p| p.x)).unwrap();
+    let y_phase = (0..HEIGHT as u32).min_by_key(|&i| spread(i, |p| p.y)).unwrap();
+
+    let (iteration, _) = crt([
+        (x_phase as i128, WIDTH as i128),
+        (y_phase as i128, HEIGHT as i128),
+    ])
+    .expect("WIDTH and HEIGHT are coprime");
+    iteration as u32
 }
 
-fn write_to_bmp(iteration: u32, points: &Vec<Point>) -> Result<(), io::Error> {
-    let mut display: [[bool; WIDTH]; HEIGHT] = [[false; WIDTH]; HEIGHT];
+/// one frame per second until the robots are back where they started (they wrap around, so
+/// after `WIDTH * HEIGHT` seconds at the latest) - the tree shows up at [solve_day_14_part_02]
+pub fn playback_day_14(input: &str) -> Result<Playback, ParseError> {
+    let lobby = Lobby::try_from((input, WIDTH as u32, HEIGHT as u32))?;
 
-    points
-        .iter()
-        .for_each(|p| display[p.y as usize][p.x as usize] = true);
-
-    // For 24bpp: each pixel uses 3 bytes.
-    let row_size = WIDTH * 3;
-    // Each row must be a multiple of 4 bytes.
-    let padding = (4 - (row_size % 4)) % 4;
-    let padded_row_size = row_size + padding;
-    let pixel_array_size = padded_row_size * HEIGHT;
-    let header_size = 14 + 40; // BMP header (14 bytes) + DIB header (40 bytes)
-    let file_size = header_size + pixel_array_size;
-
-    let file_name = format!("./tmp/{iteration}.bmp");
-    let mut file = File::create(file_name)?;
-
-    // --- BMP File Header (14 bytes) ---
-    // bfType: "BM"
-    file.write_all(b"BM")?;
-    // bfSize: file size (4 bytes, little-endian)
-    file.write_all(&(file_size as u32).to_le_bytes())?;
-    // bfReserved1: 2 bytes, 0
-    file.write_all(&0u16.to_le_bytes())?;
-    // bfReserved2: 2 bytes, 0
-    file.write_all(&0u16.to_le_bytes())?;
-    // bfOffBits: offset to pixel data (14 + 40 = 54)
-    file.write_all(&(header_size as u32).to_le_bytes())?;
-
-    // --- DIB Header (BITMAPINFOHEADER, 40 bytes) ---
-    // biSize: header size (40 bytes)
-    file.write_all(&40u32.to_le_bytes())?;
-    // biWidth: image width
-    file.write_all(&(WIDTH as i32).to_le_bytes())?;
-    // biHeight: image height. Positive means bottom-up.
-    file.write_all(&(HEIGHT as i32).to_le_bytes())?;
-    // biPlanes: must be 1
-    file.write_all(&1u16.to_le_bytes())?;
-    // biBitCount: 24 bits per pixel
-    file.write_all(&24u16.to_le_bytes())?;
-    // biCompression: 0 (BI_RGB, no compression)
-    file.write_all(&0u32.to_le_bytes())?;
-    // biSizeImage: size of pixel data (can be 0 for BI_RGB, but we set it here)
-    file.write_all(&(pixel_array_size as u32).to_le_bytes())?;
-    // biXPelsPerMeter: horizontal resolution (0 for default)
-    file.write_all(&0u32.to_le_bytes())?;
-    // biYPelsPerMeter: vertical resolution (0 for default)
-    file.write_all(&0u32.to_le_bytes())?;
-    // biClrUsed: number of colors in palette (0 for no palette)
-    file.write_all(&0u32.to_le_bytes())?;
-    // biClrImportant: 0 (all colors are important)
-    file.write_all(&0u32.to_le_bytes())?;
-
-    // --- Pixel Data ---
-    // Remember: BMP stores rows bottom-up.
-    for row in display.iter().rev() {
-        for &cell in row.iter() {
-            if cell {
-                // White pixel: B, G, R
-                file.write_all(&[255, 255, 255])?;
-            } else {
-                // Black pixel: B, G, R
-                file.write_all(&[0, 0, 0])?;
-            }
-        }
-        // Write padding for the row.
-        file.write_all(&vec![0; padding])?;
-    }
-    Ok(())
+    Ok(Playback::new(WIDTH * HEIGHT, move |i| {
+        to_text(&positions(&lobby, i as u32))
+    })
+    .colours(CellColours::new().with('X', Colour::Green)))
 }
 
-pub fn solve_day_14_part_02(input: &str) {
-    let lobby = Lobby::try_from((input, WIDTH as u32, HEIGHT as u32)).unwrap();
-    let tmp_dir = Path::new("./tmp");
-    if !tmp_dir.exists() {
-        fs::create_dir("tmp").expect("should create temp dir");
-    }
+/// the last frames up to the tree - `aoc export` writes them as bitmaps and as one looping gif
+pub fn record_day_14(input: &str) -> Result<Recorder, ParseError> {
+    let lobby = Lobby::try_from((input, WIDTH as u32, HEIGHT as u32))?;
+    let tree = tree_iteration(&lobby);
 
-    for i in 8175..8180 {
-        let positions_at_target_time: Vec<Point> = lobby
-            .robots
-            .iter()
-            .map(|r| crate::day14::part1::Robot::project_pos(&r, i, lobby.width, lobby.height))
-            .collect();
+    let mut recorder = Recorder::new();
+    recorder.record_all(tree.saturating_sub(4)..=tree, |&i| frame(&lobby, i));
+    Ok(recorder)
+}
 
-        write_to_bmp(i, &positions_at_target_time).expect("should write bmp");
-    }
+pub fn solve_day_14_part_02(input: &str) -> Result<u32, ParseError> {
+    let lobby = Lobby::try_from((input, WIDTH as u32, HEIGHT as u32))?;
+    Ok(tree_iteration(&lobby))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::util::file::read_string;
+    use crate::util::playback::Scripted;
 
     #[test]
     fn should_solve_day_14_part_02() {
         let input = read_string("./src/day14/input.txt").unwrap();
 
-        solve_day_14_part_02(&input);
+        let solution = solve_day_14_part_02(&input).unwrap();
+
+        assert_eq!(8179, solution);
+    }
+
+    #[test]
+    fn should_record_frames_up_to_tree() {
+        let input = read_string("./src/day14/input.txt").unwrap();
+        let lobby = Lobby::try_from((input.as_str(), WIDTH as u32, HEIGHT as u32)).unwrap();
+
+        let recorder = record_day_14(&input).unwrap();
+
+        assert_eq!(5, recorder.frames().len());
+        assert_eq!(frame(&lobby, 8179), recorder.frames()[4]);
+    }
+
+    #[test]
+    fn should_find_tree() {
+        let input = read_string("./src/day14/input.txt").unwrap();
+        let lobby = Lobby::try_from((input.as_str(), WIDTH as u32, HEIGHT as u32)).unwrap();
+
+        let tree = tree_iteration(&lobby);
+
+        assert_eq!(8179, tree);
+        assert!(to_text(&positions(&lobby, tree)).contains("XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX"));
+    }
+
+    #[test]
+    fn should_play_back_robots() {
+        let input = read_string("./src/day14/input.txt").unwrap();
+        let lobby = Lobby::try_from((input.as_str(), WIDTH as u32, HEIGHT as u32)).unwrap();
+        let mut playback = playback_day_14(&input).unwrap();
+
+        playback
+            .run(&mut Scripted::keys("lll"), &mut std::io::sink())
+            .unwrap();
+
+        assert_eq!(WIDTH * HEIGHT, playback.len());
+        assert_eq!(to_text(&positions(&lobby, 3)), playback.frame());
     }
 
     #[test]
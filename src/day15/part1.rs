@@ -1,6 +1,14 @@
+use crate::util::animation::Recorder;
+use crate::util::error::ParseError;
 use crate::util::grid::{Direction, Grid};
+use crate::util::image::{Image, Rgb};
+use crate::util::parse::{split_at_blank_line, Section};
+use crate::util::playback::{CellColours, Colour, Playback};
+use crate::util::render::Renderer;
 use crate::util::point::Point;
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+
+use super::DAY;
 
 pub(crate) type RobotMoveInstruction = Direction;
 
@@ -22,16 +30,60 @@ pub(crate) enum WarehouseCell {
     Empty,
 }
 
-impl From<char> for WarehouseCellParsing {
-    fn from(c: char) -> Self {
+impl TryFrom<char> for WarehouseCellParsing {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            '#' => WarehouseCellParsing::Wall,
-            '.' => WarehouseCellParsing::Empty,
-            '@' => WarehouseCellParsing::Robot,
-            'O' => WarehouseCellParsing::Box,
-            c => panic!("didn't expect '{c}' in the warehouse!"),
+            '#' => Ok(WarehouseCellParsing::Wall),
+            '.' => Ok(WarehouseCellParsing::Empty),
+            '@' => Ok(WarehouseCellParsing::Robot),
+            'O' => Ok(WarehouseCellParsing::Box),
+            c => Err(ParseError::new(DAY, format!("didn't expect '{c}' in the warehouse"))),
+        }
+    }
+}
+
+/// splits the input into the warehouse map and the instructions
+pub(crate) fn split_input(input: &str) -> Result<(Section<'_>, Section<'_>), ParseError> {
+    split_at_blank_line(DAY, input)
+        .ok_or(ParseError::new(DAY, "expected an empty line between warehouse and instructions"))
+}
+
+/// the warehouse map incl. the robot's position
+pub(crate) fn parse_warehouse(value: &str) -> Result<(Grid<WarehouseCellParsing>, Point), ParseError> {
+    let mut map = HashMap::new();
+    let mut robot_pos = None;
+
+    for (y, line) in value.lines().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            let p = Point::new(x as i32, y as i32);
+            let cell = WarehouseCellParsing::try_from(c).map_err(|e| e.at(y + 1, x + 1))?;
+            if cell == WarehouseCellParsing::Robot {
+                robot_pos = Some(p);
+            }
+            map.insert(p, cell);
         }
     }
+
+    let robot_pos = robot_pos.ok_or(ParseError::new(DAY, "no robot '@' in the warehouse"))?;
+    Ok((Grid::new(map), robot_pos))
+}
+
+/// the robot's moves - may be split across several lines
+pub(crate) fn parse_instructions(section: Section) -> Result<Vec<RobotMoveInstruction>, ParseError> {
+    section
+        .lines()
+        .flat_map(|line| line.text.chars().enumerate().map(move |(x, c)| (line, x, c)))
+        .filter(|(_, _, c)| !c.is_whitespace())
+        .map(|(line, x, c)| match c {
+            '>' => Ok(Direction::E),
+            '<' => Ok(Direction::W),
+            '^' => Ok(Direction::N),
+            'v' => Ok(Direction::S),
+            _ => Err(ParseError::new(DAY, format!("'{c}' is not a move")).at(line.number, x + 1)),
+        })
+        .collect()
 }
 
 impl From<WarehouseCellParsing> for WarehouseCell {
@@ -93,6 +145,26 @@ impl Warehouse<WarehouseCell> {
             (_, WarehouseCell::Box) => panic!("should not have terminated while loop..."),
         }
     }
+
+    /// one pixel per cell - walls grey, boxes brown, the robot red
+    pub(crate) fn to_image(&self) -> Image {
+        Image::from_grid(&self.grid, Rgb::BLACK, |p, cell| match cell {
+            _ if p == self.robot_pos => Rgb::RED,
+            WarehouseCell::Wall => Rgb(96, 96, 96),
+            WarehouseCell::Box => Rgb(160, 110, 50),
+            WarehouseCell::Empty => Rgb::BLACK,
+        })
+    }
+
+    /// like in the puzzle input - `#` walls, `O` boxes and `@` the robot
+    pub(crate) fn to_text(&self) -> String {
+        Renderer::default().render_with(&self.grid, |p, cell| match cell {
+            _ if p == self.robot_pos => '@',
+            WarehouseCell::Wall => '#',
+            WarehouseCell::Box => 'O',
+            WarehouseCell::Empty => '.',
+        })
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -110,35 +182,22 @@ impl<CELL> WarehouseSituation<CELL> {
     }
 }
 
-impl WarehouseSituation<WarehouseCell> {
-    fn from(value: &str) -> Self {
-        let (grid, instructions) = value
-            .split_once("\n\n")
-            .unwrap_or_else(|| value.split_once("\r\n\r\n").unwrap());
+impl TryFrom<&str> for WarehouseSituation<WarehouseCell> {
+    type Error = ParseError;
 
-        let grid = Grid::<char>::from(grid).map(WarehouseCellParsing::from);
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (grid, instructions) = split_input(value)?;
 
-        let start_pos = *grid
-            .iter()
-            .find(|(&_, cell)| cell == &&WarehouseCellParsing::Robot)
-            .unwrap()
-            .0;
+        let (grid, start_pos) = parse_warehouse(grid.text)?;
         let grid = Warehouse::new(grid.map(WarehouseCell::from), start_pos);
 
-        let instructions = instructions
-            .chars()
-            .filter_map(|c| match c {
-                '>' => Some(Direction::E),
-                '<' => Some(Direction::W),
-                '^' => Some(Direction::N),
-                'v' => Some(Direction::S),
-                _ => None,
-            })
-            .collect();
+        let instructions = parse_instructions(instructions)?;
 
-        WarehouseSituation::new(grid, instructions)
+        Ok(WarehouseSituation::new(grid, instructions))
     }
+}
 
+impl WarehouseSituation<WarehouseCell> {
     fn apply_all_instructions(self) -> Warehouse<WarehouseCell> {
         self.instructions
             .clone()
@@ -147,30 +206,74 @@ impl WarehouseSituation<WarehouseCell> {
                 warehouse.apply_instruction(instruction)
             })
     }
+
+    /// same as [WarehouseSituation::apply_all_instructions] but every state (starting with the
+    /// initial one) is shown to `on_state`
+    fn apply_all_instructions_with(
+        self,
+        mut on_state: impl FnMut(&Warehouse<WarehouseCell>),
+    ) -> Warehouse<WarehouseCell> {
+        on_state(&self.warehouse);
+        self.instructions
+            .into_iter()
+            .fold(self.warehouse, |warehouse, instruction| {
+                let warehouse = warehouse.apply_instruction(instruction);
+                on_state(&warehouse);
+                warehouse
+            })
+    }
+
+    fn record_all_instructions(self, recorder: &mut Recorder) -> Warehouse<WarehouseCell> {
+        self.apply_all_instructions_with(|warehouse| {
+            recorder.record(|| warehouse.to_image());
+        })
+    }
+}
+
+/// every 100th state of the warehouse as image, to be exported
+pub fn record_day_15(input: &str) -> Result<Recorder, ParseError> {
+    let mut recorder = Recorder::new().every(100);
+    WarehouseSituation::try_from(input)?.record_all_instructions(&mut recorder);
+    Ok(recorder)
 }
 
-pub fn solve_day_15_part_01(input: &str) -> u32 {
-    let initial_situation = WarehouseSituation::from(input);
+/// every state of the warehouse, to be watched in a terminal
+pub fn playback_day_15(input: &str) -> Result<Playback, ParseError> {
+    let mut states = vec![];
+    WarehouseSituation::try_from(input)?
+        .apply_all_instructions_with(|warehouse| states.push(warehouse.to_text()));
+
+    let colours = CellColours::new()
+        .with('#', Colour::Grey)
+        .with('O', Colour::Yellow)
+        .with('@', Colour::Red);
+    Ok(Playback::from_states(states, String::clone).colours(colours))
+}
+
+pub fn solve_day_15_part_01(input: &str) -> Result<u32, ParseError> {
+    let initial_situation = WarehouseSituation::try_from(input)?;
     let final_warehouse = initial_situation.apply_all_instructions();
 
-    final_warehouse
+    Ok(final_warehouse
         .grid
         .into_iter()
         .filter(|(_, cell)| cell == &WarehouseCell::Box)
         .map(|(p, _)| p.x as u32 + (p.y as u32 * 100))
-        .sum()
+        .sum())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::util::file::read_string;
+    use crate::util::playback::Scripted;
+    use std::io;
 
     #[test]
     fn should_solve_day_15_part_01() {
         let input = read_string("./src/day15/input.txt").unwrap();
 
-        let solution = solve_day_15_part_01(&input);
+        let solution = solve_day_15_part_01(&input).unwrap();
 
         println!("{solution}");
     }
@@ -202,14 +305,65 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
     fn should_solve_day_15_part_01_sample() {
         let input = INPUT_EXAMPLE.trim();
 
-        assert_eq!(10092, solve_day_15_part_01(input));
+        assert_eq!(10092, solve_day_15_part_01(input).unwrap());
+    }
+
+    #[test]
+    fn should_record_every_step() {
+        let input = INPUT_EXAMPLE.trim();
+        let situation = WarehouseSituation::try_from(input).unwrap();
+        let moves = situation.instructions.len();
+        let mut recorder = Recorder::new().every(100);
+
+        let recorded = WarehouseSituation::try_from(input)
+            .unwrap()
+            .record_all_instructions(&mut recorder);
+
+        assert_eq!(situation.apply_all_instructions(), recorded);
+        assert_eq!(moves / 100 + 1, recorder.frames().len());
+        assert_eq!(Some(Rgb::RED), recorder.frames()[0].get(4, 4));
+        assert_eq!(recorded.to_image(), recorder.frames()[moves / 100]);
+    }
+
+    #[test]
+    fn should_record_sample() {
+        let input = INPUT_EXAMPLE.trim();
+        let final_warehouse = WarehouseSituation::try_from(input)
+            .unwrap()
+            .apply_all_instructions();
+
+        let recorder = record_day_15(input).unwrap();
+
+        assert_eq!(8, recorder.frames().len());
+        assert_eq!(
+            final_warehouse.to_image(),
+            *recorder.frames().last().unwrap()
+        );
+    }
+
+    #[test]
+    fn should_play_back_sample() {
+        let input = INPUT_EXAMPLE.trim();
+        let final_warehouse = WarehouseSituation::try_from(input)
+            .unwrap()
+            .apply_all_instructions();
+        let mut playback = playback_day_15(input).unwrap();
+
+        assert_eq!(input.split("\n\n").next().unwrap(), playback.frame());
+
+        playback
+            .run(&mut Scripted::keys("G"), &mut io::sink())
+            .unwrap();
+
+        assert_eq!(701, playback.len());
+        assert_eq!(final_warehouse.to_text(), playback.frame());
     }
 
     #[test]
     fn should_parse() {
         let input = INPUT_EXAMPLE.trim();
 
-        let actual = WarehouseSituation::from(input);
+        let actual = WarehouseSituation::try_from(input).unwrap();
 
         // assert
         let first_instruction = actual.instructions.get(0).unwrap();
@@ -232,39 +386,39 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
     /// the instructions don't really matter here
     #[test]
     fn should_move_single_box() {
-        let situation = WarehouseSituation::from("@O..#\n\n");
+        let situation = WarehouseSituation::try_from("@O..#\n\n").unwrap();
 
         let actual = situation
             .warehouse
             .attempt_to_move_box(Point::new(1, 0), Direction::E);
 
-        let expected = WarehouseSituation::from(".@O.#\n\n");
+        let expected = WarehouseSituation::try_from(".@O.#\n\n").unwrap();
 
         assert_eq!(expected.warehouse, actual);
     }
 
     #[test]
     fn should_move_two_boxes() {
-        let situation = WarehouseSituation::from("@OO.#\n\n");
+        let situation = WarehouseSituation::try_from("@OO.#\n\n").unwrap();
 
         let actual = situation
             .warehouse
             .attempt_to_move_box(Point::new(1, 0), Direction::E);
 
-        let expected = WarehouseSituation::from(".@OO#\n\n");
+        let expected = WarehouseSituation::try_from(".@OO#\n\n").unwrap();
 
         assert_eq!(expected.warehouse, actual);
     }
 
     #[test]
     fn should_not_move_boxes() {
-        let situation = WarehouseSituation::from("@OO#\n\n");
+        let situation = WarehouseSituation::try_from("@OO#\n\n").unwrap();
 
         let actual = situation
             .warehouse
             .attempt_to_move_box(Point::new(1, 0), Direction::E);
 
-        let expected = WarehouseSituation::from("@OO#\n\n");
+        let expected = WarehouseSituation::try_from("@OO#\n\n").unwrap();
 
         assert_eq!(expected.warehouse, actual);
     }
@@ -272,12 +426,24 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
     /// instructions do matter now
     #[test]
     fn should_apply_all_instructions() {
-        let situation = WarehouseSituation::from("@O..#\n\n>>");
+        let situation = WarehouseSituation::try_from("@O..#\n\n>>").unwrap();
 
         let actual = situation.apply_all_instructions();
 
-        let expected = WarehouseSituation::from("..@O#\n\n");
+        let expected = WarehouseSituation::try_from("..@O#\n\n").unwrap();
 
         assert_eq!(expected.warehouse, actual);
     }
+
+    #[test]
+    fn should_report_bad_input() {
+        let e = solve_day_15_part_01("#@.#\n#.x#\n\n<>").unwrap_err();
+        assert_eq!("day 15 (line 2, column 3): didn't expect 'x' in the warehouse", e.to_string());
+
+        let e = solve_day_15_part_01("#@.#\n\n<>\n<x").unwrap_err();
+        assert_eq!("day 15 (line 4, column 2): 'x' is not a move", e.to_string());
+
+        let e = solve_day_15_part_01("#@.#\n<>").unwrap_err();
+        assert_eq!("day 15: expected an empty line between warehouse and instructions", e.to_string());
+    }
 }
@@ -1,7 +1,9 @@
 use crate::day15::part1::*;
 use crate::day15::part2::DoubleWidthWarehouseCell::*;
-use crate::util::grid::{Direction, Grid};
+use crate::util::error::ParseError;
+use crate::util::grid::Grid;
 use crate::util::point::Point;
+use crate::util::render::Renderer;
 use std::collections::HashMap;
 use std::fmt;
 use std::fmt::{Debug, Display};
@@ -30,22 +32,14 @@ impl Display for DoubleWidthWarehouseCell {
 
 impl Display for DoubleWidthWarehouseGrid {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let max_x = self.grid.iter().map(|(point, _)| point.x).max().unwrap();
-        let max_y = self.grid.iter().map(|(point, _)| point.y).max().unwrap();
-
-        for y in 0..max_y + 1 {
-            for x in 0..max_x + 1 {
-                let target = Point::new(x, y);
-                if self.robot_pos == target {
-                    write!(f, "@")?;
-                } else {
-                    write!(f, "{}", self.grid.get(&target).unwrap())?;
-                }
+        let rendered = Renderer::default().render_with(&self.grid, |p, cell| {
+            if self.robot_pos == p {
+                "@".to_string()
+            } else {
+                cell.to_string()
             }
-            writeln!(f)?;
-        }
-
-        Ok(())
+        });
+        writeln!(f, "{rendered}")
     }
 }
 
@@ -60,17 +54,14 @@ impl DoubleWidthWarehouseCell {
     }
 }
 
-impl From<&str> for DoubleWidthWarehouseGrid {
-    fn from(value: &str) -> Self {
-        let char_grid = Grid::<char>::from(value).map(WarehouseCellParsing::from);
-        let robot_pos = *char_grid
-            .iter()
-            .find(|(_, &ref c)| c == &WarehouseCellParsing::Robot)
-            .unwrap()
-            .0
-            * DOUBLE_WIDTH_UPSCALE_FACTOR;
-
-        DoubleWidthWarehouseGrid::from((char_grid.map(WarehouseCell::from), robot_pos))
+impl TryFrom<&str> for DoubleWidthWarehouseGrid {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (char_grid, robot_pos) = parse_warehouse(value)?;
+        let robot_pos = robot_pos * DOUBLE_WIDTH_UPSCALE_FACTOR;
+
+        Ok(DoubleWidthWarehouseGrid::from((char_grid.map(WarehouseCell::from), robot_pos)))
     }
 }
 
@@ -267,46 +258,34 @@ impl Warehouse<DoubleWidthWarehouseCell> {
 
 fn get_warehouse_and_instructions(
     input: &str,
-) -> (
-    Warehouse<DoubleWidthWarehouseCell>,
-    Vec<RobotMoveInstruction>,
-) {
-    let (grid, instructions) = input
-        .split_once("\n\n")
-        .unwrap_or_else(|| input.split_once("\r\n\r\n").unwrap());
-
-    let warehouse = DoubleWidthWarehouseGrid::from(grid);
-
-    let instructions: Vec<RobotMoveInstruction> = instructions
-        .chars()
-        .filter_map(|c| match c {
-            '>' => Some(Direction::E),
-            '<' => Some(Direction::W),
-            '^' => Some(Direction::N),
-            'v' => Some(Direction::S),
-            _ => None,
-        })
-        .collect();
-    (warehouse, instructions)
+) -> Result<
+    (
+        Warehouse<DoubleWidthWarehouseCell>,
+        Vec<RobotMoveInstruction>,
+    ),
+    ParseError,
+> {
+    let (grid, instructions) = split_input(input)?;
+
+    let warehouse = DoubleWidthWarehouseGrid::try_from(grid.text)?;
+
+    let instructions: Vec<RobotMoveInstruction> = parse_instructions(instructions)?;
+    Ok((warehouse, instructions))
 }
 
-pub fn solve_day_15_part_02(input: &str) -> u32 {
-    let (warehouse, instructions) = get_warehouse_and_instructions(input);
-
-    println!("{warehouse}");
+pub fn solve_day_15_part_02(input: &str) -> Result<u32, ParseError> {
+    let (warehouse, instructions) = get_warehouse_and_instructions(input)?;
 
     let final_warehouse = instructions
         .into_iter()
         .fold(warehouse, |w, instruction| w.apply_instruction(instruction));
 
-    println!("{final_warehouse}");
-
-    final_warehouse
+    Ok(final_warehouse
         .grid
         .into_iter()
         .filter(|(_, cell)| cell == &BoxWest)
         .map(|(p, _)| p.x as u32 + (p.y as u32 * 100))
-        .sum()
+        .sum())
 }
 
 #[cfg(test)]
@@ -319,7 +298,7 @@ mod tests {
     fn should_solve_day_15_part_02() {
         let input = read_string("./src/day15/input.txt").unwrap();
 
-        let solution = solve_day_15_part_02(&input);
+        let solution = solve_day_15_part_02(&input).unwrap();
 
         println!("{solution}");
     }
@@ -350,12 +329,12 @@ vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
 v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^"
             .trim();
 
-        assert_eq!(9021, solve_day_15_part_02(input));
+        assert_eq!(9021, solve_day_15_part_02(input).unwrap());
     }
 
     #[test]
     fn should_extract_grid() {
-        let warehouse = DoubleWidthWarehouseGrid::from("#.O@");
+        let warehouse = DoubleWidthWarehouseGrid::try_from("#.O@").unwrap();
         let grid = warehouse.grid;
 
         assert_eq!(&Wall, grid.get(&Point::new(0, 0)).unwrap());
@@ -371,7 +350,7 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^"
 
     #[test]
     fn should_move_one_box_horizontally() {
-        let warehouse = DoubleWidthWarehouseGrid::from("#.O@").apply_instruction(Direction::W);
+        let warehouse = DoubleWidthWarehouseGrid::try_from("#.O@").unwrap().apply_instruction(Direction::W);
         let grid = warehouse.grid;
         let robot_pos = warehouse.robot_pos;
 
@@ -388,7 +367,7 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^"
 
     #[test]
     fn should_move_two_boxes_horizontally_left() {
-        let warehouse = DoubleWidthWarehouseGrid::from("#.OO@").apply_instruction(Direction::W);
+        let warehouse = DoubleWidthWarehouseGrid::try_from("#.OO@").unwrap().apply_instruction(Direction::W);
         let grid = warehouse.grid;
         let robot_pos = warehouse.robot_pos;
 
@@ -407,7 +386,7 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^"
 
     #[test]
     fn should_move_two_boxes_horizontally_right() {
-        let warehouse = DoubleWidthWarehouseGrid::from("#@OO.")
+        let warehouse = DoubleWidthWarehouseGrid::try_from("#@OO.").unwrap()
             .apply_instruction(Direction::E)
             .apply_instruction(Direction::E); // robot spawns on the left cell so we have to shift twice
         let grid = warehouse.grid;
@@ -436,7 +415,7 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^"
 #....#
 ######"
             .trim();
-        let warehouse = DoubleWidthWarehouseGrid::from(input);
+        let warehouse = DoubleWidthWarehouseGrid::try_from(input).unwrap();
         let actual = warehouse.select_vertical_tree_rec((Point::new(4, 2), BoxWest), Direction::S);
 
         assert_eq!(4, actual.unwrap().len());
@@ -456,7 +435,7 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^"
 <vv<<^^<<^^"
             .trim();
 
-        let (mut warehouse, _instructions) = get_warehouse_and_instructions(input);
+        let (mut warehouse, _instructions) = get_warehouse_and_instructions(input).unwrap();
 
         println!("0:\n{warehouse}");
         warehouse = warehouse.apply_instruction(Direction::W);
@@ -1,5 +1,11 @@
-use crate::util::grid::Grid;
+use std::collections::HashMap;
+
+use crate::util::error::ParseError;
+use crate::util::grid::{Direction, Grid, GridAccess};
 use crate::util::point::Point;
+use crate::util::search::a_star;
+
+use super::DAY;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Cells {
@@ -13,67 +19,68 @@ pub struct ReindeerOlympicMap {
     pub grid: Grid<Cells>,
 }
 
-impl From<&str> for ReindeerOlympicMap {
-    fn from(value: &str) -> Self {
-        let grid = Grid::from(value.trim());
-        let start: Point = *grid.iter().find(|(_, &c)| c == 'S').unwrap().0;
-        let end: Point = *grid.iter().find(|(_, &c)| c == 'E').unwrap().0;
-
-        let grid = grid.map(|c| match c {
-            '.' | 'S' | 'E' => Cells::Empty,
-            '#' => Cells::Wall,
-            other => panic!("Did not expect '{other}' in map input"),
-        });
-
-        ReindeerOlympicMap { start, end, grid }
-    }
-}
-
-/// we have to keep track of the traveldirection for each node in order to run the g-function
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub(super) enum TravelDirection {
-    N,
-    E,
-    S,
-    W,
-}
-
-#[derive(Debug, Clone, PartialEq)]
-struct PathNode {
-    p: Point,
-    g: u32,
-    h: u32,
-    dir: TravelDirection,
-}
-
-impl PathNode {
-    fn new(p: Point, g: u32, h: u32, dir: TravelDirection) -> Self {
-        PathNode { p, g, h, dir }
-    }
-
-    fn f(&self) -> u32 {
-        self.g + self.h
-    }
-
-    fn dir_to(&self, other: &Point) -> TravelDirection {
-        match other {
-            x if x == &self.p.right() => TravelDirection::E,
-            x if x == &self.p.left() => TravelDirection::W,
-            x if x == &self.p.down() => TravelDirection::S,
-            x if x == &self.p.up() => TravelDirection::N,
-            _ => panic!("Not a neighbor"),
+/// positions are relative to the trimmed input
+impl TryFrom<&str> for ReindeerOlympicMap {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut map = HashMap::new();
+        let mut start = None;
+        let mut end = None;
+
+        for (y, line) in value.trim().lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let p = Point::new(x as i32, y as i32);
+                let cell = match c {
+                    '.' => Cells::Empty,
+                    'S' => {
+                        start = Some(p);
+                        Cells::Empty
+                    }
+                    'E' => {
+                        end = Some(p);
+                        Cells::Empty
+                    }
+                    '#' => Cells::Wall,
+                    other => {
+                        return Err(ParseError::new(DAY, format!("did not expect '{other}' in map input"))
+                            .at(y + 1, x + 1))
+                    }
+                };
+                map.insert(p, cell);
+            }
         }
+
+        Ok(ReindeerOlympicMap {
+            start: start.ok_or(ParseError::new(DAY, "no start 'S' in map input"))?,
+            end: end.ok_or(ParseError::new(DAY, "no end 'E' in map input"))?,
+            grid: Grid::new(map),
+        })
     }
 }
 
-/// continuing the parents direction is cheap
-fn g(p: &Point, parent: &PathNode) -> (u32, TravelDirection) {
-    let new_dir = parent.dir_to(p);
-    if parent.dir == new_dir {
-        (parent.g + 1, new_dir)
-    } else {
-        (parent.g + 1001, new_dir)
-    }
+/// the reindeer's position and the direction it faces
+pub(super) type Reindeer = (Point, Direction);
+
+/// moving on costs 1 - every 90° turn before that costs another 1000
+pub(super) fn moves<'a>(
+    map: &'a ReindeerOlympicMap,
+    (p, dir): &Reindeer,
+) -> impl Iterator<Item = (Reindeer, u32)> + 'a {
+    let dir = *dir;
+    map.grid
+        .neighbors_towards(p, Direction::CARDINAL)
+        .filter(|(_, _, &c)| c == Cells::Empty)
+        .map(move |(d, n, _)| {
+            let cost = if d == dir {
+                1
+            } else if d == dir.opposite() {
+                2001
+            } else {
+                1001
+            };
+            ((n, d), cost)
+        })
 }
 
 fn h(n: &Point, goal: &Point) -> u32 {
@@ -83,57 +90,22 @@ fn h(n: &Point, goal: &Point) -> u32 {
 }
 
 fn a_star_pathfinding(map: ReindeerOlympicMap) -> Option<u32> {
-    let start_node = PathNode::new(map.start, 0, h(&map.start, &map.end), TravelDirection::E);
-
-    let mut open: Vec<PathNode> = vec![start_node];
-    let mut closed: Vec<PathNode> = Vec::new();
-
-    while !open.is_empty() {
-        let index = open
-            .iter()
-            .enumerate()
-            .min_by_key(|&(_, node)| node.f())
-            .map(|(i, _)| i)
-            .unwrap();
-        let curr = open.swap_remove(index);
-        closed.push(curr.clone());
-
-        if curr.p == map.end {
-            return Some(curr.f());
-        }
-
-        let children: Vec<PathNode> = map
-            .grid
-            .neighbors(&curr.p)
-            .filter_map(|(p, &c)| if c == Cells::Empty { Some(p) } else { None })
-            .filter(|&p| !closed.iter().any(|n| n.p == p))
-            .map(|p| {
-                let (g, dir) = g(&p, &curr);
-                let h = h(&p, &map.end);
-                PathNode::new(p, g, h, dir)
-            })
-            .filter(|n| {
-                if let Some(existing) = open.iter().find(|&existing| existing.p == n.p) {
-                    if existing.g < n.g {
-                        return false;
-                    }
-                }
-                true
-            })
-            .collect();
-
-        for n in children {
-            open.push(n.clone());
-        }
+    let search = a_star(
+        (map.start, Direction::E),
+        |reindeer| moves(&map, reindeer),
+        |(p, _)| h(p, &map.end),
+        |(p, _)| *p == map.end,
+    );
+
+    if search.cost().is_none() {
+        eprintln!("Cannot travel to {} from {}", map.end, map.start);
     }
-
-    eprintln!("Cannot travel to {} from {}", map.end, map.start);
-    None
+    search.cost()
 }
 
-pub fn solve_day_16_part_01(input: &str) -> u32 {
-    let map = ReindeerOlympicMap::from(input);
-    a_star_pathfinding(map).unwrap()
+pub fn solve_day_16_part_01(input: &str) -> Result<u32, ParseError> {
+    let map = ReindeerOlympicMap::try_from(input)?;
+    Ok(a_star_pathfinding(map).unwrap())
 }
 
 #[cfg(test)]
@@ -162,24 +134,33 @@ mod tests {
     fn should_solve_day_16_part_01() {
         let input = read_string("./src/day16/input.txt").unwrap();
 
-        let solution = solve_day_16_part_01(&input);
+        let solution = solve_day_16_part_01(&input).unwrap();
 
         println!("{solution}");
     }
 
     #[test]
     fn should_solve_day_16_part_01_sample() {
-        assert_eq!(7036, solve_day_16_part_01(TEST_INPUT.trim()));
+        assert_eq!(7036, solve_day_16_part_01(TEST_INPUT.trim()).unwrap());
     }
 
     #[test]
     fn should_parse() {
-        let map = ReindeerOlympicMap::from(TEST_INPUT.trim());
+        let map = ReindeerOlympicMap::try_from(TEST_INPUT.trim()).unwrap();
 
         assert_eq!(Point::new(1, 13), map.start);
         assert_eq!(Point::new(13, 1), map.end);
     }
 
+    #[test]
+    fn should_report_bad_map() {
+        let e = ReindeerOlympicMap::try_from("#####\n#S.E#\n##x##").err().unwrap();
+        assert_eq!("day 16 (line 3, column 3): did not expect 'x' in map input", e.to_string());
+
+        let e = ReindeerOlympicMap::try_from("#####\n#S..#\n#####").err().unwrap();
+        assert_eq!("day 16: no end 'E' in map input", e.to_string());
+    }
+
     #[test]
     fn should_solve_small_sample() {
         let input = "
@@ -189,7 +170,7 @@ mod tests {
 "
         .trim();
 
-        assert_eq!(2, solve_day_16_part_01(input));
+        assert_eq!(2, solve_day_16_part_01(input).unwrap());
     }
 
     #[test]
@@ -202,7 +183,7 @@ mod tests {
 "
         .trim();
 
-        assert_eq!(1003, solve_day_16_part_01(input));
+        assert_eq!(1003, solve_day_16_part_01(input).unwrap());
     }
 
     #[test]
@@ -216,6 +197,6 @@ mod tests {
 "
         .trim();
 
-        assert_eq!(2006, solve_day_16_part_01(input));
+        assert_eq!(2006, solve_day_16_part_01(input).unwrap());
     }
 }
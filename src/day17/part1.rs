@@ -1,54 +1,55 @@
+use crate::util::error::{parse_number, ParseError};
+use crate::util::parse::Fields;
+
+use super::DAY;
+
 type OperandValue = u8;
 
 #[derive(Debug, PartialEq, Eq, Default)]
 pub(super) struct TuringTape(pub Vec<(OpCode, OperandValue)>);
 
-impl From<&str> for TuringTape {
-    fn from(value: &str) -> Self {
-        let raw: Vec<u8> = value
-            .split(',')
-            .map(|el| el.parse::<u8>().unwrap())
-            .collect();
-        let v = raw
-            .chunks_exact(2)
-            .map(|chunk| match chunk {
-                [left, right] => (OpCode::from(left), *right),
-                _ => panic!("Expected pairs, but found a leftover element."),
-            })
-            .collect();
-        TuringTape(v)
+impl TryFrom<&str> for TuringTape {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        parse_tape(value, value)
     }
 }
 
-pub(super) fn parse(input: &str) -> (TuringTape, TuringState) {
-    let mut a_val = None;
-    let mut b_val = None;
-    let mut c_val = None;
-    let mut tape: TuringTape = TuringTape::default();
-
-    const REG_A_PREFIX: &str = "Register A: ";
-    const REG_B_PREFIX: &str = "Register B: ";
-    const REG_C_PREFIX: &str = "Register C: ";
-    const REG_TAPE_PREFIX: &str = "Program: ";
-    for line in input.trim().lines() {
-        if line.starts_with(REG_A_PREFIX) {
-            a_val = Some(line[REG_A_PREFIX.len()..].parse::<u32>().unwrap())
-        }
-        if line.starts_with(REG_B_PREFIX) {
-            b_val = Some(line[REG_B_PREFIX.len()..].parse::<u32>().unwrap())
-        }
-        if line.starts_with(REG_C_PREFIX) {
-            c_val = Some(line[REG_C_PREFIX.len()..].parse::<u32>().unwrap())
-        }
-        if line.starts_with(REG_TAPE_PREFIX) {
-            tape = TuringTape::from(&line[REG_TAPE_PREFIX.len()..]);
-        }
-    }
+/// `program` has to be a sub-slice of `line` - so errors can point at the column within `line`
+fn parse_tape(line: &str, program: &str) -> Result<TuringTape, ParseError> {
+    let raw: Vec<u8> = program
+        .split(',')
+        .map(|el| parse_number(DAY, el).map_err(|e| e.at_token(line, el)))
+        .collect::<Result<_, _>>()?;
+    let v = raw
+        .chunks(2)
+        .map(|chunk| match chunk {
+            [left, right] => Ok((OpCode::try_from(left)?, *right)),
+            _ => Err(ParseError::new(
+                DAY,
+                "expected pairs, but found a leftover element",
+            )),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(TuringTape(v))
+}
+
+pub(super) fn parse(input: &str) -> Result<(TuringTape, TuringState), ParseError> {
+    let fields = Fields::new(DAY, input, ":");
+    let tape = match fields.get("Program") {
+        Ok((line, program)) => parse_tape(line.text, program).map_err(|e| e.at_line(line.number))?,
+        Err(_) => TuringTape::default(),
+    };
 
-    (
+    Ok((
         tape,
-        TuringState::new(a_val.unwrap(), b_val.unwrap(), c_val.unwrap()),
-    )
+        TuringState::new(
+            fields.number("Register A")?,
+            fields.number("Register B")?,
+            fields.number("Register C")?,
+        ),
+    ))
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -130,18 +131,20 @@ pub(super) enum OpCode {
     Cdv,
 }
 
-impl From<&u8> for OpCode {
-    fn from(value: &u8) -> Self {
+impl TryFrom<&u8> for OpCode {
+    type Error = ParseError;
+
+    fn try_from(value: &u8) -> Result<Self, Self::Error> {
         match value {
-            0u8 => OpCode::Adv,
-            1u8 => OpCode::Bxl,
-            2u8 => OpCode::Bst,
-            3u8 => OpCode::Jnz,
-            4u8 => OpCode::Bxc,
-            5u8 => OpCode::Out,
-            6u8 => OpCode::Bdv,
-            7u8 => OpCode::Cdv,
-            _ => panic!("bad opcode '{value}'"),
+            0u8 => Ok(OpCode::Adv),
+            1u8 => Ok(OpCode::Bxl),
+            2u8 => Ok(OpCode::Bst),
+            3u8 => Ok(OpCode::Jnz),
+            4u8 => Ok(OpCode::Bxc),
+            5u8 => Ok(OpCode::Out),
+            6u8 => Ok(OpCode::Bdv),
+            7u8 => Ok(OpCode::Cdv),
+            _ => Err(ParseError::new(DAY, format!("bad opcode '{value}'"))),
         }
     }
 }
@@ -174,19 +177,19 @@ impl Operand for ComboOperand {
     }
 }
 
-pub fn solve_day_17_part_01(input: &str) -> String {
-    let (tape, mut state) = parse(input);
+pub fn solve_day_17_part_01(input: &str) -> Result<String, ParseError> {
+    let (tape, mut state) = parse(input)?;
 
     while let Some((opcode, operand)) = tape.0.get(state.pointer) {
         state = state.advance(opcode, operand);
     }
 
-    state
+    Ok(state
         .output
         .iter()
         .map(|el| el.to_string())
         .collect::<Vec<_>>()
-        .join(",")
+        .join(","))
 }
 
 #[cfg(test)]
@@ -224,7 +227,7 @@ Program: 0,1,5,4,3,0
     fn should_solve_day_17_part_01() {
         let input = read_string("./src/day17/input.txt").unwrap();
 
-        let solution = solve_day_17_part_01(&input);
+        let solution = solve_day_17_part_01(&input).unwrap();
 
         assert_eq!("7,1,3,4,1,2,6,7,1", solution);
     }
@@ -235,7 +238,7 @@ Program: 0,1,5,4,3,0
 
         assert_eq!(
             "4,6,3,5,6,3,5,2,1,0".to_string(),
-            solve_day_17_part_01(input)
+            solve_day_17_part_01(input).unwrap()
         );
     }
 
@@ -243,9 +246,27 @@ Program: 0,1,5,4,3,0
     fn should_parse() {
         let input = EXAMPLE_INPUT.trim();
         let state = TuringState::new_all(729, 0, 0, 0, Vec::new());
-        let tape: TuringTape = TuringTape::from("0,1,5,4,3,0");
+        let tape: TuringTape = TuringTape::try_from("0,1,5,4,3,0").unwrap();
+
+        assert_eq!((tape, state), parse(input).unwrap());
+    }
+
+    #[test]
+    fn should_report_bad_program() {
+        let e = TuringTape::try_from("0,1,5").unwrap_err();
+        assert_eq!(
+            "day 17: expected pairs, but found a leftover element",
+            e.to_string()
+        );
+
+        let e = parse("Register A: 729\nRegister B: 0\n\nProgram: 0,1,x,4").unwrap_err();
+        assert_eq!(
+            "day 17 (line 4, column 14): 'x' is not a valid number",
+            e.to_string()
+        );
 
-        assert_eq!((tape, state), parse(input));
+        let e = parse("Register A: 729\nRegister B: 0\n\nProgram: 0,1,5,4").unwrap_err();
+        assert_eq!("day 17: missing 'Register C:'", e.to_string());
     }
 
     mod advance {
@@ -255,7 +276,7 @@ Program: 0,1,5,4,3,0
         fn should_match_example_1() {
             // arrange
             let state = TuringState::new(0, 0, 9);
-            let tape: TuringTape = TuringTape::from("2,6");
+            let tape: TuringTape = TuringTape::try_from("2,6").unwrap();
 
             // act
             let state = {
@@ -282,7 +303,7 @@ Register C: 0
 Program: 5,0,5,1,5,4
 ";
 
-            assert_eq!("0,1,2".to_string(), solve_day_17_part_01(input));
+            assert_eq!("0,1,2".to_string(), solve_day_17_part_01(input).unwrap());
         }
 
         #[test]
@@ -295,7 +316,10 @@ Register C: 0
 Program: 0,3,5,4,3,0
 ";
 
-            assert_eq!("0,3,5,4,3,0".to_string(), solve_day_17_part_01(input));
+            assert_eq!(
+                "0,3,5,4,3,0".to_string(),
+                solve_day_17_part_01(input).unwrap()
+            );
         }
     }
 
@@ -1,5 +1,10 @@
 use std::fmt;
 
+use crate::util::error::ParseError;
+use crate::util::parse::Fields;
+
+use super::DAY;
+
 // wasted too much time with stupid reverse engineering (see https://blog.jverkamp.com/2024/12/17/aoc-2024-day-17-virtual-machininator/)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Instruction {
@@ -186,48 +191,23 @@ impl Machine {
     }
 }
 
-pub fn parse(input: &str) -> Machine {
-    let mut lines = input.lines();
-    let a = lines
-        .next()
-        .unwrap()
-        .rsplit_once(" ")
-        .unwrap()
-        .1
-        .parse()
-        .unwrap();
-
-    let b = lines
-        .next()
-        .unwrap()
-        .rsplit_once(" ")
-        .unwrap()
-        .1
-        .parse()
-        .unwrap();
-
-    let c = lines
-        .next()
-        .unwrap()
-        .rsplit_once(" ")
-        .unwrap()
-        .1
-        .parse()
-        .unwrap();
-
-    lines.next(); // Skip the empty line
-
-    let ram = lines
-        .next()
-        .unwrap()
-        .rsplit_once(" ")
-        .unwrap()
-        .1
-        .split(",")
-        .map(|s| s.parse().unwrap())
-        .collect();
-
-    Machine {
+pub fn parse(input: &str) -> Result<Machine, ParseError> {
+    let fields = Fields::new(DAY, input, ":");
+    let (line, program) = fields.get("Program")?;
+    let ram = program
+        .split(',')
+        .map(|token| match line.number::<u8>(token)? {
+            v @ 0..=7 => Ok(v),
+            v => Err(line.error_at(token, format!("'{v}' is not a 3-bit number"))),
+        })
+        .collect::<Result<_, _>>()?;
+    let (a, b, c) = (
+        fields.number("Register A")?,
+        fields.number("Register B")?,
+        fields.number("Register C")?,
+    );
+
+    Ok(Machine {
         a,
         b,
         c,
@@ -235,7 +215,7 @@ pub fn parse(input: &str) -> Machine {
         ram,
         halted: false,
         output: Vec::new(),
-    }
+    })
 }
 
 pub fn solve_day_17_part_02(input: &Machine) -> u128 {
@@ -272,8 +252,15 @@ mod tests {
     fn should_solve_day_17_part_02() {
         let input = read_string("./src/day17/input.txt").unwrap();
 
-        let machine = parse(&input);
+        let machine = parse(&input).unwrap();
 
         assert_eq!(109019476330651, solve_day_17_part_02(&machine));
     }
+
+    #[test]
+    fn should_report_bad_program() {
+        let e = parse("Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,8,4").unwrap_err();
+
+        assert_eq!("day 17 (line 5, column 14): '8' is not a 3-bit number", e.to_string());
+    }
 }
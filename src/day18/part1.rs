@@ -1,8 +1,7 @@
+use crate::util::error::{parse_number, ParseError};
 use crate::util::point::Point;
-use std::{
-    cmp::{Ordering, Reverse},
-    collections::{BinaryHeap, HashMap, HashSet},
-};
+use crate::util::search::a_star;
+use std::collections::HashSet;
 
 #[derive(Debug)]
 pub struct PushdownAutomatonMap {
@@ -12,15 +11,30 @@ pub struct PushdownAutomatonMap {
     map_length: u8,
 }
 
+use super::DAY;
+
+/// the falling bytes in order - one `x,y` per line
+pub(super) fn parse_bytes(input: &str) -> Result<Vec<Point>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| {
+            let (x, y) = l
+                .trim()
+                .split_once(",")
+                .ok_or(ParseError::new(DAY, "expected 'x,y'").at_line(i + 1))?;
+            Ok(Point::new(
+                parse_number(DAY, x).map_err(|e| e.at_token(l, x).at_line(i + 1))?,
+                parse_number(DAY, y).map_err(|e| e.at_token(l, y).at_line(i + 1))?,
+            ))
+        })
+        .collect()
+}
+
 impl PushdownAutomatonMap {
-    pub fn new(input: &str, size: u8, n: u16) -> Self {
-        let corrupt = input
-            .trim()
-            .lines()
-            .take(n as usize)
-            .map(|l| l.trim().split_once(",").expect("should have comma"))
-            .map(|(x, y)| Point::new(x.parse().unwrap(), y.parse().unwrap()))
-            .collect();
+    pub fn new(bytes: &[Point], size: u8, n: u16) -> Self {
+        let corrupt = bytes.iter().take(n as usize).copied().collect();
 
         let goal_x_y: i32 = size as i32 - 1;
         PushdownAutomatonMap {
@@ -32,113 +46,32 @@ impl PushdownAutomatonMap {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct PathNode {
-    p: Point,
-    g: u32,
-    h: u32,
-}
-
-impl PartialOrd for PathNode {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        let self_f = self.f();
-        let other_f = other.f();
-
-        let o = if self_f < other_f {
-            Ordering::Less
-        } else if self_f > other_f {
-            Ordering::Greater
-        } else {
-            Ordering::Equal
-        };
-        Some(o)
-    }
-}
-impl Ord for PathNode {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        let self_f = self.f();
-        let other_f = other.f();
-
-        if self_f < other_f {
-            Ordering::Less
-        } else if self_f > other_f {
-            Ordering::Greater
-        } else {
-            Ordering::Equal
-        }
-    }
-}
-
-impl PathNode {
-    fn new(p: Point, g: u32, h: u32) -> Self {
-        PathNode { p, g, h }
-    }
-
-    fn f(&self) -> u32 {
-        self.g + self.h
-    }
-}
-
-fn g(parent: &PathNode) -> u32 {
-    parent.g + 1
-}
-
 fn h(n: &Point, goal: &Point) -> u32 {
-    let delta_x = (n.x - goal.x).abs() as u32;
-    let delta_y = (n.y - goal.y).abs() as u32;
-    delta_x + delta_y
+    n.manhattan(goal) as u32
 }
 
 pub fn a_star_pathfinding(map: PushdownAutomatonMap) -> Option<u32> {
-    let start_node = PathNode::new(map.start, 0, h(&map.start, &map.goal));
-
-    let mut open = BinaryHeap::new();
-    open.push(Reverse(start_node));
-
-    // best g cost found so far for each point.
-    let mut best_cost: HashMap<Point, u32> = HashMap::new();
-    best_cost.insert(map.start, 0);
-
-    let mut closed: HashSet<Point> = HashSet::new();
-
-    while let Some(Reverse(current)) = open.pop() {
-        let curr = current;
-        closed.insert(curr.p);
-
-        if curr.p == map.goal {
-            return Some(curr.f());
-        }
-
-        curr.p
-            .neighbors()
-            .into_iter()
-            .filter(|p| {
-                !map.corrupt.contains(p)
-                    && p.x >= 0
-                    && p.x < map.map_length as i32
-                    && p.y >= 0
-                    && p.y < map.map_length as i32
-            })
-            .filter(|p| !closed.contains(p))
-            .filter_map(|p| {
-                let new_g = g(&curr); // new cost (typically, curr.g + step_cost)
-                if best_cost.get(&p).map_or(true, |&c| new_g < c) {
-                    best_cost.insert(p, new_g);
-                    let new_h = h(&p, &map.goal);
-                    let child = PathNode::new(p, new_g, new_h);
-                    Some(Reverse(child))
-                } else {
-                    None
-                }
-            })
-            .for_each(|child| open.push(child));
-    }
-    None
+    let within_map = |p: &Point| {
+        p.x >= 0 && p.x < map.map_length as i32 && p.y >= 0 && p.y < map.map_length as i32
+    };
+
+    a_star(
+        map.start,
+        |p| {
+            p.neighbors()
+                .into_iter()
+                .filter(|n| !map.corrupt.contains(n) && within_map(n))
+                .map(|n| (n, 1))
+        },
+        |p| h(p, &map.goal),
+        |p| *p == map.goal,
+    )
+    .cost()
 }
 
-pub fn solve_day_18_part_01(input: &str, map_size: u8, n: u16) -> u32 {
-    let map = PushdownAutomatonMap::new(input, map_size, n);
-    a_star_pathfinding(map).unwrap()
+pub fn solve_day_18_part_01(input: &str, map_size: u8, n: u16) -> Result<u32, ParseError> {
+    let map = PushdownAutomatonMap::new(&parse_bytes(input)?, map_size, n);
+    Ok(a_star_pathfinding(map).unwrap())
 }
 
 #[cfg(test)]
@@ -150,7 +83,7 @@ mod tests {
     fn should_solve_day_18_part_01() {
         let input = read_string("./src/day18/input.txt").unwrap();
 
-        let solution = solve_day_18_part_01(&input, 71, 1024);
+        let solution = solve_day_18_part_01(&input, 71, 1024).unwrap();
 
         assert_eq!(288, solution);
     }
@@ -172,6 +105,18 @@ mod tests {
 5,1"
         .trim();
 
-        assert_eq!(22, solve_day_18_part_01(input, 7, 12));
+        assert_eq!(22, solve_day_18_part_01(input, 7, 12).unwrap());
+    }
+
+    #[test]
+    fn should_report_bad_byte() {
+        let e = solve_day_18_part_01("5,4\n4;2", 7, 12).unwrap_err();
+        assert_eq!("day 18 (line 2): expected 'x,y'", e.to_string());
+
+        let e = solve_day_18_part_01("5,4\n4,-", 7, 12).unwrap_err();
+        assert_eq!(
+            "day 18 (line 2, column 3): '-' is not a valid number",
+            e.to_string()
+        );
     }
 }
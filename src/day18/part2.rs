@@ -1,20 +1,24 @@
-use super::part1::{a_star_pathfinding, PushdownAutomatonMap};
+use super::part1::{a_star_pathfinding, parse_bytes, PushdownAutomatonMap};
+use crate::util::error::ParseError;
+use crate::util::point::Point;
 
-pub fn solve_day_18_part_02(input: &str, map_size: u8) -> &str {
+/// returns the coordinate of the first byte that cuts off the exit
+pub fn solve_day_18_part_02(input: &str, map_size: u8) -> Result<Point, ParseError> {
+    let bytes = parse_bytes(input)?;
     let mut lower_boundary = 0; // always points at solvable maze
-    let mut upper_boundary = input.lines().count() - 1; // always points at unsolvable maze
+    let mut upper_boundary = bytes.len() - 1; // always points at unsolvable maze
     let next_index = |l, u| (l + u) / 2;
 
     while lower_boundary != upper_boundary - 1 {
         let n = next_index(lower_boundary, upper_boundary);
-        let map = PushdownAutomatonMap::new(input, map_size, n as u16);
+        let map = PushdownAutomatonMap::new(&bytes, map_size, n as u16);
         match a_star_pathfinding(map) {
             Some(_) => lower_boundary = n,
             None => upper_boundary = n,
         }
     }
 
-    input.lines().nth(upper_boundary - 1).expect("must exist")
+    Ok(bytes[upper_boundary - 1])
 }
 
 #[cfg(test)]
@@ -26,9 +30,9 @@ mod tests {
     fn should_solve_day_18_part_02() {
         let input = read_string("./src/day18/input.txt").unwrap();
 
-        let solution = solve_day_18_part_02(&input, 71);
+        let solution = solve_day_18_part_02(&input, 71).unwrap();
 
-        assert_eq!("52,5", solution);
+        assert_eq!(Point::new(52, 5), solution);
     }
 
     #[test]
@@ -61,6 +65,6 @@ mod tests {
 2,0"
         .trim();
 
-        assert_eq!("6,1", solve_day_18_part_02(input, 7));
+        assert_eq!(Point::new(6, 1), solve_day_18_part_02(input, 7).unwrap());
     }
 }
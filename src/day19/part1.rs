@@ -1,5 +1,9 @@
 use std::cmp::Reverse;
 
+use crate::util::error::ParseError;
+
+use super::DAY;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum StripColor {
     White,
@@ -10,15 +14,15 @@ pub enum StripColor {
 }
 
 impl TryFrom<char> for StripColor {
-    type Error = String;
-    fn try_from(value: char) -> Result<Self, String> {
+    type Error = ParseError;
+    fn try_from(value: char) -> Result<Self, ParseError> {
         match value {
             'w' => Ok(Self::White),
             'u' => Ok(Self::Blue),
             'b' => Ok(Self::Black),
             'r' => Ok(Self::Red),
             'g' => Ok(Self::Green),
-            _ => Err(format!("unexpected '{}'", value)),
+            _ => Err(ParseError::new(DAY, format!("unexpected '{}'", value))),
         }
     }
 }
@@ -26,44 +30,45 @@ impl TryFrom<char> for StripColor {
 pub struct AvailableTowels(pub Vec<Vec<StripColor>>);
 pub struct DesignGoals(pub Vec<Vec<StripColor>>);
 
-pub fn parse(input: &str) -> (AvailableTowels, DesignGoals) {
-    let mut lines = input.trim().lines();
+/// the colors of a towel or design - `stripes` has to be a sub-slice of `line` (for the column)
+fn parse_stripes(line: &str, stripes: &str) -> Result<Vec<StripColor>, ParseError> {
+    stripes
+        .char_indices()
+        .map(|(i, c)| StripColor::try_from(c).map_err(|e| e.at_token(line, &stripes[i..])))
+        .collect()
+}
 
-    let first_line = lines.next().expect("empty input");
+pub fn parse(input: &str) -> Result<(AvailableTowels, DesignGoals), ParseError> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .skip_while(|(_, line)| line.trim().is_empty());
+
+    let (i, first_line) = lines.next().ok_or(ParseError::new(DAY, "empty input"))?;
     let mut towels: Vec<Vec<StripColor>> = first_line
         .split(',')
-        .map(|seq| {
-            seq.trim()
-                .chars()
-                .map(|c| StripColor::try_from(c).unwrap())
-                .collect()
-        })
-        .collect();
+        .map(|seq| parse_stripes(first_line, seq.trim()).map_err(|e| e.at_line(i + 1)))
+        .collect::<Result<_, _>>()?;
 
     // minor optimization
     towels.sort_by_key(|v| Reverse(v.len()));
 
-    lines.next();
-
     let goals = lines
-        .map(|line| {
-            line.chars()
-                .map(|c| StripColor::try_from(c).unwrap())
-                .collect()
-        })
-        .collect();
-
-    (AvailableTowels(towels), DesignGoals(goals))
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| parse_stripes(line, line.trim()).map_err(|e| e.at_line(i + 1)))
+        .collect::<Result<_, _>>()?;
+
+    Ok((AvailableTowels(towels), DesignGoals(goals)))
 }
 
-pub fn solve_day_19_part_01(input: &str) -> usize {
-    let (towels, goals) = parse(input);
+pub fn solve_day_19_part_01(input: &str) -> Result<usize, ParseError> {
+    let (towels, goals) = parse(input)?;
 
-    goals
+    Ok(goals
         .0
         .into_iter()
         .filter(|goal| can_be_achieved(goal, &towels))
-        .count()
+        .count())
 }
 
 fn can_be_achieved(goal: &[StripColor], towels: &AvailableTowels) -> bool {
@@ -91,7 +96,7 @@ mod tests {
     fn should_solve_day_19_part_01() {
         let input = read_string("./src/day19/input.txt").unwrap();
 
-        let solution = solve_day_19_part_01(&input);
+        let solution = solve_day_19_part_01(&input).unwrap();
 
         assert_eq!(263, solution);
     }
@@ -110,6 +115,15 @@ brgr
 bbrgwb"
             .trim();
 
-        assert_eq!(6, solve_day_19_part_01(input));
+        assert_eq!(6, solve_day_19_part_01(input).unwrap());
+    }
+
+    #[test]
+    fn should_report_unknown_color() {
+        let e = solve_day_19_part_01("r, wr, b\n\nbrwrr\nbgxr").unwrap_err();
+        assert_eq!("day 19 (line 4, column 3): unexpected 'x'", e.to_string());
+
+        let e = solve_day_19_part_01("r, wx, b\n\nbrwrr").unwrap_err();
+        assert_eq!("day 19 (line 1, column 5): unexpected 'x'", e.to_string());
     }
 }
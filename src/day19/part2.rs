@@ -1,18 +1,19 @@
 use std::collections::HashMap;
 
 use crate::day19::part1::*;
+use crate::util::error::ParseError;
 
-pub fn solve_day_19_part_02(input: &str) -> usize {
-    let (towels, goals) = parse(input);
+pub fn solve_day_19_part_02(input: &str) -> Result<usize, ParseError> {
+    let (towels, goals) = parse(input)?;
 
-    goals
+    Ok(goals
         .0
         .into_iter()
         .map(|goal| {
             let mut memo: HashMap<&[StripColor], usize> = HashMap::new();
             can_be_achieved(&goal, &towels, &mut memo)
         })
-        .sum()
+        .sum())
 }
 
 fn can_be_achieved<'a>(
@@ -47,7 +48,7 @@ mod tests {
     fn should_solve_day_19_part_02() {
         let input = read_string("./src/day19/input.txt").unwrap();
 
-        let solution = solve_day_19_part_02(&input);
+        let solution = solve_day_19_part_02(&input).unwrap();
 
         assert_eq!(723524534506343, solution);
     }
@@ -66,6 +67,6 @@ brgr
 bbrgwb"
             .trim();
 
-        assert_eq!(16, solve_day_19_part_02(input));
+        assert_eq!(16, solve_day_19_part_02(input).unwrap());
     }
 }
@@ -1,2 +1,12 @@
+use crate::day16::part1::ReindeerOlympicMap;
+use crate::util::error::ParseError;
+
 pub mod part1;
 pub mod part2;
+
+const DAY: u8 = 20;
+
+/// same maze format as on day 16 - only the day of the error is adjusted
+fn parse_maze(input: &str) -> Result<ReindeerOlympicMap, ParseError> {
+    ReindeerOlympicMap::try_from(input).map_err(|e| ParseError { day: DAY, ..e })
+}
@@ -1,11 +1,10 @@
 use crate::day16::part1::*;
+use crate::day20::parse_maze;
+use crate::util::error::ParseError;
+use crate::util::grid::GridAccess;
 use crate::util::point::Point;
-use std::{
-    cmp::{Ordering, Reverse},
-    collections::{BinaryHeap, HashMap, HashSet},
-    mem,
-    rc::Rc,
-};
+use crate::util::search::a_star;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum CheatingStep {
@@ -47,242 +46,89 @@ impl Cheating {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct PathNode {
-    p: Point,
-    f: u32,
-    g: u32,
-    h: u32,
-    cheating: Cheating,
-}
-
-impl Ord for PathNode {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.f.cmp(&self.f).then_with(|| other.h.cmp(&self.h))
-    }
-}
-
-impl PartialOrd for PathNode {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl PathNode {
-    fn new(p: Point, g: u32, h: u32, cheating: Cheating) -> Self {
-        PathNode {
-            p,
-            g,
-            h,
-            f: g + h,
-            cheating,
-        }
-    }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub(super) struct PathNodeWithParent {
-    p: Point,
-    f: u32,
-    g: u32,
-    h: u32,
-    parent: Option<Rc<PathNodeWithParent>>,
-}
-
-impl Ord for PathNodeWithParent {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.f.cmp(&self.f).then_with(|| other.h.cmp(&self.h))
-    }
-}
-
-impl PartialOrd for PathNodeWithParent {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-/// needed since the recursive dropping of Parents causes stack-overflow - Other option would be
-/// Weak<PathNodeWithParent> but that's more of a headache
-impl Drop for PathNodeWithParent {
-    fn drop(&mut self) {
-        let mut next: Option<Rc<PathNodeWithParent>> = mem::take(&mut self.parent);
-
-        while let Some(rc_node) = next {
-            let mut node = match Rc::try_unwrap(rc_node) {
-                Ok(inner) => inner,
-                Err(_) => break,
-            };
-
-            next = mem::take(&mut node.parent);
-        }
-    }
-}
-
-impl PathNodeWithParent {
-    fn new_start(p: Point, g: u32, h: u32) -> Self {
-        PathNodeWithParent {
-            p,
-            g,
-            h,
-            f: g + h,
-            parent: None,
-        }
-    }
-
-    fn new(p: Point, g: u32, h: u32, parent: Rc<PathNodeWithParent>) -> Self {
-        PathNodeWithParent {
-            p,
-            g,
-            h,
-            f: g + h,
-            parent: Some(parent),
-        }
-    }
-
-    pub(super) fn collect_cost_map(self: &Rc<Self>) -> HashMap<Point, u32> {
-        let mut node: Rc<PathNodeWithParent> = Rc::clone(self);
-        let mut map: HashMap<Point, u32> = HashMap::new();
-        map.insert(self.p, self.g);
-
-        while let Some(parent) = &node.parent {
-            map.insert(parent.p, parent.g);
-            node = Rc::clone(parent);
-        }
-
-        map
-    }
-}
-
 pub(super) fn h(n: &Point, goal: &Point) -> u32 {
-    let delta_x = (n.x - goal.x).abs() as u32;
-    let delta_y = (n.y - goal.y).abs() as u32;
-    delta_x + delta_y
+    n.manhattan(goal) as u32
 }
 
+/// cost to reach each point on the cheapest path through the maze
 pub(super) fn a_star_pathfinding(map: &ReindeerOlympicMap) -> HashMap<Point, u32> {
-    let start_node = Rc::new(PathNodeWithParent::new_start(
+    let search = a_star(
         map.start,
-        0,
-        h(&map.start, &map.end),
-    ));
-
-    let mut open: BinaryHeap<Reverse<Rc<PathNodeWithParent>>> = BinaryHeap::new();
-    open.push(Reverse(start_node.clone()));
-
-    // best g cost found so far for each point.
-    let mut best_cost: HashMap<Point, u32> = HashMap::new();
-    best_cost.insert(map.start, 0);
-
-    let mut closed: HashSet<Point> = HashSet::new();
-
-    while let Some(Reverse(current)) = open.pop() {
-        closed.insert(current.p);
-
-        if current.p == map.end {
-            return current.collect_cost_map();
-        }
-
-        map.grid
-            .neighbors(&current.p)
-            .into_iter()
-            .filter_map(|(p, c)| match c {
-                Cells::Wall => None,
-                Cells::Empty => Some(p),
-            })
-            .filter(|p| !closed.contains(p))
-            .filter_map(|p| {
-                let new_g = current.g + 1;
-                if best_cost.get(&p).map_or(true, |&c| new_g < c) {
-                    best_cost.insert(p, new_g);
-                    let new_h = h(&p, &map.end);
-                    let child = PathNodeWithParent::new(p, new_g, new_h, current.clone());
-                    Some(Reverse(Rc::new(child)))
-                } else {
-                    None
-                }
-            })
-            .for_each(|child| open.push(child));
-    }
+        |p| {
+            map.grid
+                .neighbors(p)
+                .filter(|(_, &c)| c == Cells::Empty)
+                .map(|(n, _)| (n, 1))
+        },
+        |p| h(p, &map.end),
+        |p| *p == map.end,
+    );
 
-    panic!("No solution found for the maze");
+    search
+        .path()
+        .expect("No solution found for the maze")
+        .into_iter()
+        .map(|p| (p, search.distance(&p).unwrap()))
+        .collect()
 }
 
+/// the cost of the whole race for every cheat that saves at least `offset` - a cheat ends on
+/// the first empty cell after the wall, the rest of the race is known from `cost_map`
 fn a_star_pathfinding_distribution(
     map: &ReindeerOlympicMap,
     cost_map: &HashMap<Point, u32>,
     offset: u32,
     lowest_cost_no_cheating: u32,
 ) -> HashMap<Cheating, u32> {
-    let start_node = PathNode::new(
-        map.start,
-        0,
-        h(&map.start, &map.end),
-        Cheating::new(None, None),
-    );
-
-    let mut open = BinaryHeap::new();
-    open.push(Reverse(start_node));
-
-    let mut closed: HashSet<(Point, Cheating)> = HashSet::new();
-    let mut cost_per_cheat: HashMap<Cheating, u32> = HashMap::new();
-
-    while let Some(Reverse(current)) = open.pop() {
-        closed.insert((current.p, current.cheating));
-
-        if current.p == map.end {
-            cost_per_cheat.insert(current.cheating, current.g);
-        }
-
-        map.grid
-            .neighbors(&current.p)
-            .into_iter()
-            .filter_map(|(p, c)| match c {
-                Cells::Wall => match current.cheating.cheating_state() {
-                    CheatingStep::NotYet => Some((p, Cheating::new(Some(current.p), None))),
-                    CheatingStep::FirstCheatingMove => None,
-                    CheatingStep::Cheated => None,
-                },
-                Cells::Empty => match current.cheating.cheating_state() {
-                    CheatingStep::FirstCheatingMove => {
-                        Some((p, Cheating::new(current.cheating.start, Some(p))))
+    let search = a_star(
+        (map.start, Cheating::new(None, None)),
+        |&(p, cheating)| {
+            let finished =
+                cheating.cheating_state() == CheatingStep::Cheated && cost_map.contains_key(&p);
+            map.grid
+                .neighbors(&p)
+                .filter(move |_| !finished)
+                .filter_map(move |(n, c)| {
+                    match (c, cheating.cheating_state()) {
+                        (Cells::Wall, CheatingStep::NotYet) => Some(Cheating::new(Some(p), None)),
+                        (Cells::Wall, _) => None,
+                        (Cells::Empty, CheatingStep::FirstCheatingMove) => {
+                            Some(Cheating::new(cheating.start, Some(n)))
+                        }
+                        (Cells::Empty, _) => Some(cheating),
                     }
-                    _ => Some((p, current.cheating)),
-                },
-            })
-            .filter(|(p, c)| !closed.contains(&(*p, *c)))
-            .for_each(|(p, c)| {
-                let new_g = current.g + 1;
-                let new_h = h(&p, &map.end);
-                let child = PathNode::new(p, new_g, new_h, c);
+                    .map(|cheating| ((n, cheating), 1))
+                })
+        },
+        |(p, _)| h(p, &map.end),
+        |_| false,
+    );
 
-                if child.cheating.cheating_state() == CheatingStep::Cheated
-                    && cost_map.contains_key(&p)
-                {
-                    let best_cost_without_cheating = *cost_map.get(&p).unwrap();
-                    if child.g + offset <= best_cost_without_cheating {
-                        let cost = child.g + lowest_cost_no_cheating - best_cost_without_cheating;
-                        cost_per_cheat.insert(child.cheating, cost);
-                    }
-                } else {
-                    open.push(Reverse(child))
+    search
+        .distances()
+        .iter()
+        .filter_map(|(&(p, cheating), &g)| {
+            let cost = match cost_map.get(&p) {
+                Some(&best) if cheating.cheating_state() == CheatingStep::Cheated => {
+                    (g + offset <= best).then(|| g + lowest_cost_no_cheating - best)?
                 }
-            });
-    }
-
-    cost_per_cheat
-        .into_iter()
-        .filter(|(_, v)| *v <= lowest_cost_no_cheating - offset)
+                _ if p == map.end => g,
+                _ => return None,
+            };
+            Some((cheating, cost))
+        })
+        .filter(|(_, cost)| *cost <= lowest_cost_no_cheating - offset)
         .collect()
 }
 
-pub fn solve_day_20_part_01(input: &str, offset: u32) -> usize {
-    let map = ReindeerOlympicMap::from(input);
+pub fn solve_day_20_part_01(input: &str, offset: u32) -> Result<usize, ParseError> {
+    let map = parse_maze(input)?;
     let cost_map = a_star_pathfinding(&map);
     let lowest_cost_no_cheating = *cost_map.values().max().unwrap();
     let cost_per_cheat =
         a_star_pathfinding_distribution(&map, &cost_map, offset, lowest_cost_no_cheating);
     let cheats: HashSet<Cheating> = cost_per_cheat.keys().map(|c| *c).collect();
-    cheats.len()
+    Ok(cheats.len())
 }
 
 #[cfg(test)]
@@ -299,7 +145,7 @@ mod tests {
 #E..#
 #####"
             .trim();
-        let actual = solve_day_20_part_01(&map, 2);
+        let actual = solve_day_20_part_01(&map, 2).unwrap();
 
         assert_eq!(2, actual);
     }
@@ -325,28 +171,28 @@ mod tests {
     fn should_solve_day_20_part_01() {
         let input = read_string("./src/day20/input.txt").unwrap();
 
-        let solution = solve_day_20_part_01(&input, 100);
+        let solution = solve_day_20_part_01(&input, 100).unwrap();
 
         assert_eq!(1321, solution);
     }
 
     #[test]
     fn should_solve_example() {
-        let solution = solve_day_20_part_01(SAMPLE_MAZE, 1);
+        let solution = solve_day_20_part_01(SAMPLE_MAZE, 1).unwrap();
 
         assert_eq!(44, solution);
     }
 
     #[test]
     fn should_find_best_solution_without_cheating() {
-        let map = ReindeerOlympicMap::from(SAMPLE_MAZE.trim());
+        let map = ReindeerOlympicMap::try_from(SAMPLE_MAZE.trim()).unwrap();
         let cost = a_star_pathfinding(&map);
         assert_eq!(&84, cost.get(&map.end).unwrap());
     }
 
     #[test]
     fn should_find_best_solution_without_cheating_variation() {
-        let map = ReindeerOlympicMap::from(
+        let map = ReindeerOlympicMap::try_from(
             "
 ###############
 #...#.........#
@@ -364,7 +210,8 @@ mod tests {
 #...#...#...###
 ###############"
                 .trim(),
-        );
+        )
+        .unwrap();
         let cost = a_star_pathfinding(&map);
         assert_eq!(&72, cost.get(&map.end).unwrap());
     }
@@ -388,14 +235,14 @@ mod tests {
 #............E#
 ###############"
             .trim();
-        let actual = solve_day_20_part_01(&map, 1);
+        let actual = solve_day_20_part_01(&map, 1).unwrap();
 
         assert_eq!(12 * 6, actual);
     }
 
     #[test]
     fn should_find_best_solution_with_cheating() {
-        let map = ReindeerOlympicMap::from(SAMPLE_MAZE.trim());
+        let map = ReindeerOlympicMap::try_from(SAMPLE_MAZE.trim()).unwrap();
         let mut cost_map = HashMap::new();
         cost_map.insert(map.end, 84);
         let cost = a_star_pathfinding_distribution(&map, &cost_map, 0, 84);
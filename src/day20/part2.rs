@@ -1,26 +1,26 @@
 use crate::{
-    day16::part1::{Cells, ReindeerOlympicMap},
-    util::point::Point,
+    day16::part1::Cells,
+    day20::parse_maze,
+    util::{
+        error::ParseError,
+        point::{Point, Vec2},
+    },
 };
 
 use super::part1::a_star_pathfinding;
 use std::sync::LazyLock;
 
-static MANHATTAN_OFFSETS_20: LazyLock<Vec<(Point, u8)>> = LazyLock::new(|| {
-    let r = 20i32;
-    let mut offsets = Vec::new();
-    for dx in -r..=r {
-        let max_dy = r - dx.abs();
-        for dy in -max_dy..=max_dy {
-            let cost = (dx.abs() + dy.abs()) as u8;
-            offsets.push((Point::new(dx, dy), cost));
-        }
-    }
-    offsets
+static MANHATTAN_OFFSETS_20: LazyLock<Vec<(Vec2, u8)>> = LazyLock::new(|| {
+    let origin = Point::new(0, 0);
+    origin
+        .proximity_manhattan(20)
+        .into_iter()
+        .map(|p| (p - origin, p.manhattan(&origin) as u8))
+        .collect()
 });
 
-pub fn solve_day_20_part_02(input: &str, threshold: u32) -> usize {
-    let map = ReindeerOlympicMap::from(input);
+pub fn solve_day_20_part_02(input: &str, threshold: u32) -> Result<usize, ParseError> {
+    let map = parse_maze(input)?;
     let costs = a_star_pathfinding(&map);
 
     assert_eq!(
@@ -31,10 +31,7 @@ pub fn solve_day_20_part_02(input: &str, threshold: u32) -> usize {
     let mut counter = 0;
     for (cheat_start_point, cheat_start_cost) in &costs {
         for (offset, offset_cost) in &*MANHATTAN_OFFSETS_20 {
-            let neighbor = Point::new(
-                cheat_start_point.x + offset.x,
-                cheat_start_point.y + offset.y,
-            );
+            let neighbor = *cheat_start_point + *offset;
 
             if let Some(&cheat_end_cost) = costs.get(&neighbor) {
                 let cheat_benefit = cheat_end_cost
@@ -48,7 +45,7 @@ pub fn solve_day_20_part_02(input: &str, threshold: u32) -> usize {
         }
     }
 
-    counter
+    Ok(counter)
 }
 
 #[cfg(test)]
@@ -60,7 +57,7 @@ mod tests {
     fn should_solve_day_20_part_02() {
         let input = read_string("./src/day20/input.txt").unwrap();
 
-        let solution = solve_day_20_part_02(&input, 100);
+        let solution = solve_day_20_part_02(&input, 100).unwrap();
 
         assert_eq!(971737, solution);
     }
@@ -85,6 +82,6 @@ mod tests {
 ###############"
             .trim();
         let expected = 32 + 31 + 29 + 39 + 25 + 23 + 20 + 19 + 12 + 14 + 12 + 22 + 4 + 3;
-        assert_eq!(expected, solve_day_20_part_02(input, 50));
+        assert_eq!(expected, solve_day_20_part_02(input, 50).unwrap());
     }
 }
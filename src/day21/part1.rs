@@ -1,11 +1,14 @@
-use crate::util::grid::Grid;
+use crate::util::error::{parse_number, ParseError};
+use crate::util::grid::{Direction, Grid, GridAccess};
 use crate::util::point::Point;
-use std::cmp::{Ordering, Reverse};
-use std::collections::{BinaryHeap, HashMap};
+use crate::util::search::dijkstra;
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::hash::Hash;
 use std::sync::LazyLock;
 
+use super::DAY;
+
 pub(super) trait Transpileable {
     fn transpile(&self) -> DirectionKeySequence;
     fn len(&self) -> usize;
@@ -28,21 +31,23 @@ enum DoorKey {
     K9,
 }
 
-impl From<char> for DoorKey {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for DoorKey {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            'A' => DoorKey::A,
-            '0' => DoorKey::K0,
-            '1' => DoorKey::K1,
-            '2' => DoorKey::K2,
-            '3' => DoorKey::K3,
-            '4' => DoorKey::K4,
-            '5' => DoorKey::K5,
-            '6' => DoorKey::K6,
-            '7' => DoorKey::K7,
-            '8' => DoorKey::K8,
-            '9' => DoorKey::K9,
-            _ => panic!("unexpected value '{value}'"),
+            'A' => Ok(DoorKey::A),
+            '0' => Ok(DoorKey::K0),
+            '1' => Ok(DoorKey::K1),
+            '2' => Ok(DoorKey::K2),
+            '3' => Ok(DoorKey::K3),
+            '4' => Ok(DoorKey::K4),
+            '5' => Ok(DoorKey::K5),
+            '6' => Ok(DoorKey::K6),
+            '7' => Ok(DoorKey::K7),
+            '8' => Ok(DoorKey::K8),
+            '9' => Ok(DoorKey::K9),
+            _ => Err(ParseError::new(DAY, format!("unexpected value '{value}'"))),
         }
     }
 }
@@ -52,19 +57,36 @@ impl From<char> for DoorKey {
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub(super) struct DoorCode([DoorKey; 4]);
 
-impl From<&str> for DoorCode {
-    fn from(value: &str) -> Self {
-        DoorCode(
-            value
-                .chars()
-                .map(DoorKey::from)
-                .collect::<Vec<DoorKey>>()
-                .try_into()
-                .expect("incorrect length"),
-        )
+impl TryFrom<&str> for DoorCode {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let keys = value
+            .char_indices()
+            .map(|(i, c)| DoorKey::try_from(c).map_err(|e| e.at_column(i + 1)))
+            .collect::<Result<Vec<DoorKey>, _>>()?;
+        let len = keys.len();
+
+        Ok(DoorCode(keys.try_into().map_err(|_| {
+            ParseError::new(DAY, format!("a code has 4 keys but found {len}"))
+        })?))
     }
 }
 
+/// every code incl. its numeric part (the first 3 keys)
+pub(super) fn parse_codes(input: &str) -> Result<Vec<(u128, DoorCode)>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let code = DoorCode::try_from(line).map_err(|e| e.at_line(i + 1))?;
+            let numeric_part = parse_number(DAY, &line[..3]).map_err(|e| e.at(i + 1, 1))?;
+            Ok((numeric_part, code))
+        })
+        .collect()
+}
+
 impl Transpileable for DoorCode {
     fn transpile(&self) -> DirectionKeySequence {
         let mut pos = DoorKey::A;
@@ -136,11 +158,11 @@ const NUMBER_KEYPAD: LazyLock<HashMap<(DoorKey, DoorKey), Vec<DirectionKey>>> =
 
         let grid = grid.filter_map(|c| match c {
             '.' => None,
-            _ => Some(DoorKey::from(c)),
+            _ => DoorKey::try_from(c).ok(),
         });
 
         for (point, key) in grid.iter() {
-            assert_eq!(*point, key.get_pos(), "{:?}", key);
+            assert_eq!(point, key.get_pos(), "{:?}", key);
         }
         assert_eq!(grid.iter().count(), 11);
 
@@ -163,7 +185,7 @@ pub(super) const DIRECTION_KEYPAD: LazyLock<
     });
 
     for (point, key) in grid.iter() {
-        assert_eq!(*point, key.get_pos(), "{:?}", key);
+        assert_eq!(point, key.get_pos(), "{:?}", key);
     }
     assert_eq!(grid.iter().count(), 5);
 
@@ -175,15 +197,8 @@ impl<K: KeypadKey> Grid<K> {
         &self,
         p: &Point,
     ) -> impl Iterator<Item = (Point, &K, DirectionKey)> {
-        [
-            (p.left(), DirectionKey::Left),
-            (p.down(), DirectionKey::Down),
-            (p.up(), DirectionKey::Up),
-            (p.right(), DirectionKey::Right),
-        ]
-        .into_iter()
-        .map(|(n, key)| (n, self.get(&n), key))
-        .filter_map(|(n, c, key)| c.map(|inner_c| (n, inner_c, key)))
+        self.neighbors_towards(p, [Direction::W, Direction::S, Direction::N, Direction::E])
+            .filter_map(|(dir, n, c)| DirectionKey::try_from(dir).ok().map(|key| (n, c, key)))
     }
 }
 
@@ -202,31 +217,14 @@ fn get_best_paths<T>(grid: &Grid<T>) -> HashMap<(T, T), Vec<DirectionKey>>
 where
     T: KeypadKey + Clone + Eq + Hash,
 {
-    #[derive(Clone)]
-    struct State {
-        cost: u32,
-        pos: Point,
-        last: Option<DirectionKey>,
-        path: Vec<DirectionKey>,
-    }
-
-    impl Eq for State {}
-
-    impl PartialEq for State {
-        fn eq(&self, other: &Self) -> bool {
-            self.cost == other.cost
-        }
-    }
-
-    impl Ord for State {
-        fn cmp(&self, other: &Self) -> Ordering {
-            other.cost.cmp(&self.cost)
-        }
-    }
-
-    impl PartialOrd for State {
-        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-            Some(self.cmp(other))
+    /// how much a key costs later in the round-trip between 'A's
+    fn weight(key: DirectionKey) -> u32 {
+        match key {
+            DirectionKey::Left => 4,
+            DirectionKey::Down => 3,
+            DirectionKey::Up => 2,
+            DirectionKey::Right => 1,
+            DirectionKey::A => panic!("Invalid state"),
         }
     }
 
@@ -234,76 +232,48 @@ where
         if Some(new) == prev {
             1
         } else {
-            let dir = match new {
-                DirectionKey::Left => 4,
-                DirectionKey::Down => 3,
-                DirectionKey::Up => 2,
-                DirectionKey::Right => 1,
-                DirectionKey::A => panic!("Invalid state"),
-            };
-            10 + dir * traveled as u32
+            10 + weight(new) * traveled as u32
         }
     }
 
     let mut best_paths = HashMap::new();
 
-    for (start_point, start_key_ref) in grid.iter() {
-        let start_key = start_key_ref.clone();
-        let init_point = *start_point;
-        let mut best_cost: HashMap<(Point, Option<DirectionKey>), u32> = HashMap::new();
-        let mut best_at_point: HashMap<Point, (u32, Vec<DirectionKey>)> = HashMap::new();
-        let mut heap = BinaryHeap::new();
-        let init_state = State {
-            cost: 0,
-            pos: init_point,
-            last: None,
-            path: Vec::new(),
-        };
-        best_cost.insert((init_point, None), 0);
-        best_at_point.insert(init_point, (0, Vec::new()));
-        heap.push(Reverse(init_state));
-
-        while let Some(Reverse(state)) = heap.pop() {
-            if let Some(&recorded) = best_cost.get(&(state.pos, state.last)) {
-                if state.cost > recorded {
-                    continue;
-                }
-            }
-            best_at_point
-                .entry(state.pos)
-                .and_modify(|(cost, path)| {
-                    if state.cost < *cost {
-                        *cost = state.cost;
-                        *path = state.path.clone();
-                    }
-                })
-                .or_insert((state.cost, state.path.clone()));
-
-            for (n_pos, _, direction) in grid.neighbors_with_direction_key(&state.pos) {
-                let new_cost =
-                    state.cost + transition_cost(state.last, direction, state.path.len());
-                let mut new_path = state.path.clone();
-                new_path.push(direction);
-                let new_state = State {
-                    cost: new_cost,
-                    pos: n_pos,
-                    last: Some(direction),
-                    path: new_path,
-                };
-                let key = (n_pos, Some(direction));
-                if best_cost
-                    .get(&key)
-                    .map_or(true, |&existing| new_cost < existing)
-                {
-                    best_cost.insert(key, new_cost);
-                    heap.push(Reverse(new_state));
-                }
-            }
-        }
+    // every step costs something, so a cheapest path never visits a key twice
+    let keys = grid.iter().count();
+    for (start_point, start_key) in grid.iter() {
+        // the cost depends on how far we got, so that's part of the state
+        let search = dijkstra(
+            (start_point, None, 0),
+            |&(p, last, traveled)| {
+                grid.neighbors_with_direction_key(&p)
+                    .filter(move |_| traveled + 1 < keys)
+                    .map(move |(n, _, direction)| {
+                        let cost = transition_cost(last, direction, traveled);
+                        ((n, Some(direction), traveled + 1), cost)
+                    })
+            },
+            |_| false,
+        );
 
-        for (p, (_cost, path)) in best_at_point.iter() {
-            if let Some(dest_key) = grid.get(p) {
-                best_paths.insert((start_key.clone(), dest_key.clone()), path.clone());
+        for (dest_point, dest_key) in grid.iter() {
+            // equally cheap paths press the heavier keys first - that's the **<v^>** from above
+            let best = search
+                .distances()
+                .iter()
+                .filter(|((p, _, _), _)| *p == dest_point)
+                .map(|(state, &cost)| {
+                    let path: Vec<DirectionKey> = search
+                        .path_to(state)
+                        .expect("every settled state has a path")
+                        .into_iter()
+                        .filter_map(|(_, key, _)| key)
+                        .collect();
+                    let weights: Vec<u32> = path.iter().map(|&key| weight(key)).collect();
+                    (cost, weights, path)
+                })
+                .min_by(|a, b| a.0.cmp(&b.0).then_with(|| b.1.cmp(&a.1)));
+            if let Some((_, _, path)) = best {
+                best_paths.insert((start_key.clone(), dest_key.clone()), path);
             }
         }
     }
@@ -334,6 +304,21 @@ impl From<char> for DirectionKey {
     }
 }
 
+/// only cardinal directions have a key - diagonals are handed back
+impl TryFrom<Direction> for DirectionKey {
+    type Error = Direction;
+
+    fn try_from(value: Direction) -> Result<Self, Self::Error> {
+        match value {
+            Direction::N => Ok(DirectionKey::Up),
+            Direction::S => Ok(DirectionKey::Down),
+            Direction::W => Ok(DirectionKey::Left),
+            Direction::E => Ok(DirectionKey::Right),
+            _ => Err(value),
+        }
+    }
+}
+
 impl Display for DirectionKey {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -382,30 +367,25 @@ impl Transpileable for DirectionKeySequence {
     }
 }
 
-pub(super) fn compile(line: &str, intermediate_robots: u8) -> u128 {
-    let mut code: Box<dyn Transpileable> = Box::new(DoorCode::from(line));
+pub(super) fn compile(code: &DoorCode, intermediate_robots: u8) -> u128 {
+    let mut code = code.transpile();
 
-    for _ in 0..intermediate_robots + 1 {
-        code = Box::new(code.transpile());
+    for _ in 0..intermediate_robots {
+        code = code.transpile();
     }
 
     code.len() as u128
 }
 
-pub fn solve_day_21_slow(input: &str, intermediate_robots: u8) -> u128 {
-    input
-        .lines()
-        .map(|line| {
-            let numeric_part = &line[..3];
-            let numeric_part = numeric_part
-                .parse::<u128>()
-                .expect(&format!("Couln't parse '{numeric_part}'"));
-
-            let transpiliation_length = compile(line, intermediate_robots);
+pub fn solve_day_21_slow(input: &str, intermediate_robots: u8) -> Result<u128, ParseError> {
+    Ok(parse_codes(input)?
+        .iter()
+        .map(|(numeric_part, code)| {
+            let transpiliation_length = compile(code, intermediate_robots);
 
             numeric_part * transpiliation_length
         })
-        .sum()
+        .sum())
 }
 
 #[cfg(test)]
@@ -418,7 +398,7 @@ mod tests {
     fn should_solve_part_1() {
         let input = read_string("./src/day21/input.txt").unwrap();
 
-        let solution = solve_day_21_slow(&input.trim(), 2);
+        let solution = solve_day_21_slow(&input.trim(), 2).unwrap();
 
         assert_eq!(169390, solution);
     }
@@ -433,7 +413,7 @@ mod tests {
 379A"
             .trim();
 
-        let solution = solve_day_21_slow(&input, 2);
+        let solution = solve_day_21_slow(&input, 2).unwrap();
 
         assert_eq!(126384, solution);
     }
@@ -442,32 +422,33 @@ mod tests {
     fn debug() {
         assert_eq!(
             "<vA<AA>>^AvAA<^A>A<v<A>>^AvA^A<vA>^A<v<A>^A>AAvA^A<v<A>A>^AAAvA<^A>A".len() as u128,
-            compile("029A", 2)
+            compile(&"029A".try_into().unwrap(), 2)
         );
         assert_eq!(
             "<v<A>>^AAAvA^A<vA<AA>>^AvAA<^A>A<v<A>A>^AAAvA<^A>A<vA>^A<A>A".len() as u128,
-            compile("980A", 2)
+            compile(&"980A".try_into().unwrap(), 2)
         );
         assert_eq!(
             "<v<A>>^A<vA<A>>^AAvAA<^A>A<v<A>>^AAvA^A<vA>^AA<A>A<v<A>A>^AAAvA<^A>A".len() as u128,
-            compile("179A", 2)
+            compile(&"179A".try_into().unwrap(), 2)
         );
         assert_eq!(
             "<v<A>>^AA<vA<A>>^AAvAA<^A>A<vA>^A<A>A<vA>^A<A>A<v<A>A>^AAvA<^A>A".len() as u128,
-            compile("456A", 2)
+            compile(&"456A".try_into().unwrap(), 2)
         );
         assert_eq!(
             "<v<A>>^AvA^A<vA<AA>>^AAvA<^A>AAvA^A<vA>^AA<A>A<v<A>A>^AAAvA<^A>A".len() as u128,
-            compile("379A", 2),
+            compile(&"379A".try_into().unwrap(), 2),
         );
     }
 
     #[test]
     fn should_transpile_doorcode() {
-        let code: DoorCode = "029A".into();
+        let code: DoorCode = "029A".try_into().unwrap();
         let transpiled = code.transpile();
 
-        assert_eq!("<A^A>^^AvvvA", stringify(transpiled.0));
+        // "<A^A>^^AvvvA" and "<A^A^>^AvvvA" are just as short - ties go to the heavier keys first
+        assert_eq!("<A^A^^>AvvvA", stringify(transpiled.0));
     }
 
     #[test]
@@ -478,6 +459,12 @@ mod tests {
         assert_eq!("v<<A>>^A<A>AvA<^AA>A<vAAA^>A", stringify(transpiled.0));
     }
 
+    #[test]
+    fn should_only_have_keys_for_cardinal_directions() {
+        assert_eq!(Ok(DirectionKey::Left), DirectionKey::try_from(Direction::W));
+        assert_eq!(Err(Direction::NE), DirectionKey::try_from(Direction::NE));
+    }
+
     #[test]
     fn best_paths() {
         let map = &DIRECTION_KEYPAD;
@@ -1,5 +1,6 @@
-use super::part1::{DirectionKey, DoorCode, Transpileable, DIRECTION_KEYPAD};
-use memoize::memoize;
+use super::part1::{parse_codes, DirectionKey, DoorCode, Transpileable, DIRECTION_KEYPAD};
+use crate::util::error::ParseError;
+use crate::util::memo::Memo;
 
 fn prepend(dirs: &[DirectionKey], elt: DirectionKey) -> Vec<DirectionKey> {
     let mut v = Vec::with_capacity(dirs.len() + 1);
@@ -8,42 +9,41 @@ fn prepend(dirs: &[DirectionKey], elt: DirectionKey) -> Vec<DirectionKey> {
     v
 }
 
-#[memoize]
-fn sequence_length(dirs: Vec<DirectionKey>, remaining: u8) -> u128 {
+type SequenceMemo = Memo<(Vec<DirectionKey>, u8), u128>;
+
+fn sequence_length(memo: &mut SequenceMemo, dirs: Vec<DirectionKey>, remaining: u8) -> u128 {
     if remaining == 0 {
         return dirs.len() as u128;
     }
-    let with_start = prepend(&dirs, DirectionKey::A);
-    with_start
-        .windows(2)
-        .map(|pair| {
-            let mut replacement = DIRECTION_KEYPAD.get(&(pair[0], pair[1])).unwrap().clone();
-            replacement.push(DirectionKey::A);
-            sequence_length(replacement, remaining - 1)
-        })
-        .sum()
+    memo.compute((dirs.clone(), remaining), |memo| {
+        let with_start = prepend(&dirs, DirectionKey::A);
+        with_start
+            .windows(2)
+            .map(|pair| {
+                let mut replacement = DIRECTION_KEYPAD.get(&(pair[0], pair[1])).unwrap().clone();
+                replacement.push(DirectionKey::A);
+                sequence_length(memo, replacement, remaining - 1)
+            })
+            .sum()
+    })
 }
 
-pub(super) fn compile_fast(line: &str, intermediate_robots: u8) -> u128 {
-    let code = DoorCode::from(line);
+fn compile_fast(memo: &mut SequenceMemo, code: &DoorCode, intermediate_robots: u8) -> u128 {
     let transpiled = code.transpile();
-    sequence_length(transpiled.0, intermediate_robots)
+    sequence_length(memo, transpiled.0, intermediate_robots)
 }
 
-pub fn solve_day_21_fast(input: &str, intermediate_robots: u8) -> u128 {
-    input
-        .lines()
-        .map(|line| {
-            let numeric_part = &line[..3];
-            let numeric_part = numeric_part
-                .parse::<u128>()
-                .expect(&format!("Couln't parse '{numeric_part}'"));
-
-            let transpiliation_length = compile_fast(line, intermediate_robots);
+pub fn solve_day_21_fast(input: &str, intermediate_robots: u8) -> Result<u128, ParseError> {
+    // the key sequences of all codes overlap - one memo for all of them
+    let mut memo = Memo::new();
+    Ok(parse_codes(input)?
+        .iter()
+        .map(|(numeric_part, code)| {
+            let transpiliation_length = compile_fast(&mut memo, code, intermediate_robots);
 
             numeric_part * transpiliation_length
         })
-        .sum()
+        .sum())
 }
 
 #[cfg(test)]
@@ -56,14 +56,23 @@ mod tests {
     fn should_solve_part_2() {
         let input = read_string("./src/day21/input.txt").unwrap();
 
-        let solution = solve_day_21_fast(&input.trim(), 25);
+        let solution = solve_day_21_fast(&input.trim(), 25).unwrap();
 
         assert_eq!(210686850124870, solution);
     }
 
     #[test]
     fn compile_slow_should_equal_compile_fast() {
-        let input = "029A";
-        assert_eq!(compile(input, 2), compile_fast(input, 2));
+        let input = "029A".try_into().unwrap();
+        assert_eq!(compile(&input, 2), compile_fast(&mut Memo::new(), &input, 2));
+    }
+
+    #[test]
+    fn should_report_bad_code() {
+        let e = solve_day_21_fast("029A\n98BA", 2).unwrap_err();
+        assert_eq!("day 21 (line 2, column 3): unexpected value 'B'", e.to_string());
+
+        let e = solve_day_21_fast("029A\n980", 2).unwrap_err();
+        assert_eq!("day 21 (line 2): a code has 4 keys but found 3", e.to_string());
     }
 }
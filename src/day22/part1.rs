@@ -1,5 +1,9 @@
 use std::ops::{Div, Mul};
 
+use crate::util::error::{parse_number, ParseError};
+
+use super::DAY;
+
 const PRUNE_VAL: u64 = 16777216;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -53,13 +57,21 @@ impl Div<u64> for Secret {
     }
 }
 
-pub fn solve_day_22_part_01(input: &str) -> u64 {
+/// the initial secret of every buyer - one per line
+pub(super) fn parse_secrets(input: &str) -> Result<Vec<u64>, ParseError> {
     input
-        .trim()
         .lines()
-        .flat_map(|line| line.parse::<u64>())
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| parse_number(DAY, line).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+pub fn solve_day_22_part_01(input: &str) -> Result<u64, ParseError> {
+    Ok(parse_secrets(input)?
+        .into_iter()
         .map(|v| Secret::new(v).evolve_n_times(2000).v())
-        .sum()
+        .sum())
 }
 
 #[cfg(test)]
@@ -71,7 +83,7 @@ mod tests {
     fn should_solve_day_22_part_01() {
         let input = read_string("./src/day22/input.txt").unwrap();
 
-        let solution = solve_day_22_part_01(&input);
+        let solution = solve_day_22_part_01(&input).unwrap();
 
         assert_eq!(13022553808, solution);
     }
@@ -114,4 +126,11 @@ mod tests {
         assert_eq!(Secret::new(16777216).prune().v(), 0);
         assert_eq!(Secret::new(10) / 3, 3);
     }
+
+    #[test]
+    fn should_report_bad_secret() {
+        let e = solve_day_22_part_01("1\n10\n100x").unwrap_err();
+
+        assert_eq!("day 22 (line 3): '100x' is not a valid number", e.to_string());
+    }
 }
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
-use super::part1::Secret;
+use super::part1::{parse_secrets, Secret};
+use crate::util::error::ParseError;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 struct Window {
@@ -38,11 +39,9 @@ impl Secret {
     }
 }
 
-pub fn solve_day_22_part_02(input: &str) -> u32 {
-    *input
-        .trim()
-        .lines()
-        .flat_map(|line| line.parse::<u64>())
+pub fn solve_day_22_part_02(input: &str) -> Result<u32, ParseError> {
+    Ok(*parse_secrets(input)?
+        .into_iter()
         .fold(
             HashMap::new(),
             |mut global_map: HashMap<[i8; 4], u32>, v| {
@@ -75,7 +74,7 @@ pub fn solve_day_22_part_02(input: &str) -> u32 {
         )
         .values()
         .max()
-        .unwrap_or(&0)
+        .unwrap_or(&0))
 }
 
 #[cfg(test)]
@@ -87,7 +86,7 @@ mod tests {
     fn should_solve_day_22_part_02() {
         let input = read_string("./src/day22/input.txt").unwrap();
 
-        let solution = solve_day_22_part_02(&input);
+        let solution = solve_day_22_part_02(&input).unwrap();
 
         assert_eq!(1555, solution);
     }
@@ -100,7 +99,7 @@ mod tests {
 2024"
             .trim();
 
-        assert_eq!(23, solve_day_22_part_02(input));
+        assert_eq!(23, solve_day_22_part_02(input).unwrap());
     }
 
     #[test]
@@ -1,2 +1,18 @@
+use crate::util::error::ParseError;
+use crate::util::parse::Line;
+
 pub mod part1;
 pub mod part2;
+
+const DAY: u8 = 24;
+
+/// `x00: 1`
+fn parse_initial_value<'a>(line: &Line<'a>) -> Result<(&'a str, bool), ParseError> {
+    let (key, value) = line.key_value(": ")?;
+
+    match value {
+        "0" => Ok((key, false)),
+        "1" => Ok((key, true)),
+        other => Err(line.error_at(value, format!("expected 0 or 1 but got '{other}'"))),
+    }
+}
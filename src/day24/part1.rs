@@ -1,4 +1,9 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+use crate::util::error::ParseError;
+use crate::util::parse::{sections, Section};
+
+use super::{parse_initial_value, DAY};
 
 enum LogicOperator {
     OR,
@@ -16,6 +21,20 @@ impl LogicOperator {
     }
 }
 
+/// `z` wires are the bits of the result, so the number behind the `z` has to fit into a `u64`
+fn output_bit(wire: &str) -> Result<Option<u32>, ParseError> {
+    let Some(bit) = wire.strip_prefix('z') else {
+        return Ok(None);
+    };
+    match bit.parse::<u32>() {
+        Ok(bit) if bit < u64::BITS => Ok(Some(bit)),
+        _ => Err(ParseError::new(
+            DAY,
+            format!("'{wire}' is not an output wire between z00 and z63"),
+        )),
+    }
+}
+
 struct UnresolvedExpression {
     left: String,
     right: String,
@@ -23,56 +42,76 @@ struct UnresolvedExpression {
     operator: LogicOperator,
 }
 
-impl From<&str> for UnresolvedExpression {
-    fn from(s: &str) -> Self {
-        let (left, tail) = s.trim().split_once(" ").expect(&format!("Invalid: '{s}'"));
-        let (operator, tail) = tail.split_once(" ").expect(&format!("Invalid: '{s}'"));
-        let (right, tail) = tail.split_once(" ").expect(&format!("Invalid: '{s}'"));
-        let out = tail.replace("-> ", "");
+/// `x00 AND y00 -> z00` - the error has no line number since it only sees the line itself
+impl TryFrom<&str> for UnresolvedExpression {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let [left, operator, right, arrow, out] = s.split_whitespace().collect::<Vec<_>>()[..]
+        else {
+            return Err(ParseError::new(
+                DAY,
+                format!(
+                    "expected '<wire> <op> <wire> -> <wire>' but got '{}'",
+                    s.trim()
+                ),
+            ));
+        };
+        if arrow != "->" {
+            return Err(
+                ParseError::new(DAY, format!("expected '->' but got '{arrow}'")).at_token(s, arrow),
+            );
+        }
 
         let operator = match operator {
             "AND" => LogicOperator::AND,
             "XOR" => LogicOperator::XOR,
             "OR" => LogicOperator::OR,
-            _ => panic!("'{operator}' is not an operator"),
+            _ => {
+                return Err(
+                    ParseError::new(DAY, format!("'{operator}' is not an operator"))
+                        .at_token(s, operator),
+                )
+            }
         };
 
-        UnresolvedExpression {
+        output_bit(out).map_err(|e| e.at_token(s, out))?;
+
+        Ok(UnresolvedExpression {
             left: left.to_string(),
             right: right.to_string(),
-            out,
+            out: out.to_string(),
             operator,
-        }
+        })
     }
 }
 
-fn parse(input: &str) -> (HashMap<String, bool>, Vec<UnresolvedExpression>) {
-    input
-        .trim()
-        .split_once("\n\n")
-        .or_else(|| input.split_once("\n\r\n\r"))
-        .map(|(initial_values, expressions)| {
-            let initial_values = initial_values
-                .lines()
-                .map(|line| {
-                    line.split_once(": ")
-                        .map(|(key, value)| (key.to_string(), value.trim() == "1"))
-                        .expect(&format!("{} should contain ': '", line))
-                })
-                .collect();
-
-            let expressions = expressions
-                .lines()
-                .map(UnresolvedExpression::from)
-                .collect();
-
-            (initial_values, expressions)
+fn parse(input: &str) -> Result<(HashMap<String, bool>, Vec<UnresolvedExpression>), ParseError> {
+    let sections = sections(DAY, input);
+    let Some((initial_values, expressions)) = sections.split_first() else {
+        return Ok((HashMap::new(), Vec::new()));
+    };
+
+    let initial_values = initial_values
+        .lines()
+        .map(|line| {
+            let (key, value) = parse_initial_value(&line)?;
+            output_bit(key).map_err(|e| line.error_at(key, e.reason))?;
+            Ok((key.to_string(), value))
         })
-        .expect("Doesn't contain empty line")
+        .collect::<Result<_, _>>()?;
+
+    let expressions = expressions
+        .iter()
+        .flat_map(Section::lines)
+        .map(|line| UnresolvedExpression::try_from(line.text).map_err(|e| e.at_line(line.number)))
+        .collect::<Result<_, _>>()?;
+
+    Ok((initial_values, expressions))
 }
 
-pub fn solve_day_24_part_01(input: &str) -> u64 {
-    let (mut values, mut expressions) = parse(input);
+pub fn solve_day_24_part_01(input: &str) -> Result<u64, ParseError> {
+    let (mut values, mut expressions) = parse(input)?;
 
     while !expressions.is_empty() {
         let mut solveables = Vec::new();
@@ -87,6 +126,10 @@ pub fn solve_day_24_part_01(input: &str) -> u64 {
             })
             .collect();
 
+        if solveables.is_empty() {
+            return Err(stuck(&values, &expressions));
+        }
+
         for UnresolvedExpression {
             out,
             left,
@@ -100,16 +143,29 @@ pub fn solve_day_24_part_01(input: &str) -> u64 {
         }
     }
 
-    values
+    Ok(values
         .into_iter()
-        .filter(|(key, _value)| key.starts_with('z'))
-        .fold(0u64, |aggr, (next_key, next_value)| {
-            if !next_value {
-                return aggr;
-            }
-            let v: u32 = next_key[1..].parse().expect("can't parse");
-            aggr + 2u64.pow(v)
-        })
+        .filter(|(_key, value)| *value)
+        .filter_map(|(key, _value)| output_bit(&key).ok().flatten())
+        .fold(0u64, |aggr, bit| aggr | 1 << bit))
+}
+
+/// none of the remaining gates has both inputs - either an input is never set at all or the
+/// gates wait on each other
+fn stuck(values: &HashMap<String, bool>, expressions: &[UnresolvedExpression]) -> ParseError {
+    let outputs: HashSet<&str> = expressions.iter().map(|e| e.out.as_str()).collect();
+    let never_set = expressions
+        .iter()
+        .flat_map(|e| [&e.left, &e.right])
+        .find(|wire| !values.contains_key(*wire) && !outputs.contains(wire.as_str()));
+
+    match never_set {
+        Some(wire) => ParseError::new(DAY, format!("wire '{wire}' is never set")),
+        None => ParseError::new(
+            DAY,
+            format!("{} gates wait on each other in a loop", expressions.len()),
+        ),
+    }
 }
 
 #[cfg(test)]
@@ -121,7 +177,7 @@ mod tests {
     fn should_solve_day_24_part_01() {
         let input = read_string("./src/day24/input.txt").unwrap();
 
-        let solution = solve_day_24_part_01(&input);
+        let solution = solve_day_24_part_01(&input).unwrap();
 
         assert_eq!(46463754151024, solution);
     }
@@ -141,6 +197,62 @@ x01 XOR y01 -> z01
 x02 OR y02 -> z02"
             .trim();
 
-        assert_eq!(4, solve_day_24_part_01(input));
+        assert_eq!(4, solve_day_24_part_01(input).unwrap());
+    }
+
+    #[test]
+    fn should_report_bad_expression() {
+        let e = solve_day_24_part_01("x00: 1\ny00: 0\n\nx00 NAND y00 -> z00").unwrap_err();
+        assert_eq!(
+            "day 24 (line 4, column 5): 'NAND' is not an operator",
+            e.to_string()
+        );
+
+        let e = solve_day_24_part_01("x00: 1\ny00: 2\n\nx00 AND y00 -> z00").unwrap_err();
+        assert_eq!(
+            "day 24 (line 2, column 6): expected 0 or 1 but got '2'",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn should_report_unsolvable_gates() {
+        let e = solve_day_24_part_01("x00: 1\n\nx00 AND y00 -> z00").unwrap_err();
+        assert_eq!("day 24: wire 'y00' is never set", e.to_string());
+
+        let e = solve_day_24_part_01("x00: 1\n\nx00 AND a -> b\nx00 OR b -> a\nb XOR a -> z00")
+            .unwrap_err();
+        assert_eq!(
+            "day 24: 3 gates wait on each other in a loop",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn should_report_bad_output_wire() {
+        let e = solve_day_24_part_01("x00: 1\ny00: 0\n\nx00 AND y00 -> z64").unwrap_err();
+        assert_eq!(
+            "day 24 (line 4, column 16): 'z64' is not an output wire between z00 and z63",
+            e.to_string()
+        );
+
+        let e = solve_day_24_part_01("x00: 1\ny00: 0\n\nx00 AND y00 -> zed").unwrap_err();
+        assert_eq!(
+            "day 24 (line 4, column 16): 'zed' is not an output wire between z00 and z63",
+            e.to_string()
+        );
+
+        let e = solve_day_24_part_01("zx: 1\n\nzx AND zx -> z00").unwrap_err();
+        assert_eq!(
+            "day 24 (line 1, column 1): 'zx' is not an output wire between z00 and z63",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn should_set_the_highest_output_bit() {
+        let input = "x00: 1\ny00: 1\n\nx00 AND y00 -> z63";
+
+        assert_eq!(1 << 63, solve_day_24_part_01(input).unwrap());
     }
 }
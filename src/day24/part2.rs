@@ -1,6 +1,11 @@
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
+use crate::util::error::ParseError;
+
+use super::{parse_initial_value, DAY};
+use crate::util::parse::lines;
+
 // copied from:
 // https://github.com/jpverkamp/advent-of-code/blob/master/2024/src/day24.rs
 // most aoc solutions seem to either A) visualize the tree with graphviz and manually try to spot
@@ -13,13 +18,15 @@ pub enum Operator {
     Xor,
 }
 
-impl From<&str> for Operator {
-    fn from(value: &str) -> Self {
+impl TryFrom<&str> for Operator {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
         match value.to_ascii_uppercase().as_str() {
-            "AND" => Self::And,
-            "OR" => Self::Or,
-            "XOR" => Self::Xor,
-            _ => panic!("Invalid operator: {}", value),
+            "AND" => Ok(Self::And),
+            "OR" => Ok(Self::Or),
+            "XOR" => Ok(Self::Xor),
+            _ => Err(ParseError::new(DAY, format!("Invalid operator: {}", value))),
         }
     }
 }
@@ -35,31 +42,33 @@ pub struct Machine<'input> {
     wires: HashMap<&'input str, Wire<'input>>,
 }
 
-impl<'input> From<&'input str> for Machine<'input> {
-    fn from(input: &'input str) -> Self {
+impl<'input> TryFrom<&'input str> for Machine<'input> {
+    type Error = ParseError;
+
+    fn try_from(input: &'input str) -> Result<Self, Self::Error> {
         let mut wires = HashMap::new();
 
-        for line in input.lines() {
-            if line.contains(':') {
-                let (key, value) = line.split_once(": ").unwrap();
-                let value = value == "1";
+        for line in lines(DAY, input) {
+            if line.text.contains(':') {
+                let (key, value) = parse_initial_value(&line)?;
 
                 wires.insert(key, Wire::Input(value));
             }
 
-            if line.contains("->") {
-                let mut parts = line.split_ascii_whitespace();
-                let arg0 = parts.next().unwrap();
-                let op = parts.next().unwrap().into();
-                let arg1 = parts.next().unwrap();
-                parts.next(); // Skip the ->
-                let result = parts.next().unwrap();
+            if line.text.contains("->") {
+                let [arg0, op, arg1, _, result] =
+                    line.text.split_ascii_whitespace().collect::<Vec<_>>()[..]
+                else {
+                    return Err(line.error("expected '<wire> <op> <wire> -> <wire>'"));
+                };
+                let op = Operator::try_from(op)
+                    .map_err(|e| e.at_token(line.text, op).at_line(line.number))?;
 
                 wires.insert(result, Wire::Function(op, arg0, arg1));
             }
         }
 
-        Self { wires }
+        Ok(Self { wires })
     }
 }
 
@@ -273,8 +282,8 @@ impl<'input> Machine<'input> {
     }
 }
 
-pub fn solve_day_24_part_02(input: &str) -> String {
-    let machine = Machine::from(input);
+pub fn solve_day_24_part_02(input: &str) -> Result<String, ParseError> {
+    let machine = Machine::try_from(input)?;
     let bits = machine.wires().filter(|w| w.starts_with('x')).count();
 
     fn find_op<'input>(
@@ -360,11 +369,11 @@ pub fn solve_day_24_part_02(input: &str) -> String {
         carry = if carry.is_some() { next_carry } else { next };
     }
 
-    swaps
+    Ok(swaps
         .iter()
         .flat_map(|(a, b)| vec![a.unwrap(), b.unwrap()])
         .sorted()
-        .join(",")
+        .join(","))
 }
 
 #[cfg(test)]
@@ -376,7 +385,7 @@ mod tests {
     fn should_solve_day_24_part_02() {
         let input = read_string("./src/day24/input.txt").unwrap();
 
-        let solution = solve_day_24_part_02(&input);
+        let solution = solve_day_24_part_02(&input).unwrap();
 
         assert_eq!("cqk,fph,gds,jrs,wrk,z15,z21,z34", &solution);
     }
@@ -1 +1,3 @@
 pub mod part1;
+
+const DAY: u8 = 25;
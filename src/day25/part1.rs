@@ -1,5 +1,9 @@
 use std::{collections::HashSet, fmt::Display};
 
+use crate::util::error::ParseError;
+
+use super::DAY;
+
 #[derive(Debug, PartialEq, Eq, Hash)]
 struct KeyLockSchema {
     raw: String,
@@ -7,16 +11,32 @@ struct KeyLockSchema {
     is_key: bool,
 }
 
-impl From<&str> for KeyLockSchema {
-    fn from(value: &str) -> Self {
+/// the line of an error is relative to the (trimmed) schema - see [parse_schemas]
+impl TryFrom<&str> for KeyLockSchema {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
         let mut lines = value.trim().lines();
-        let reference: char = lines.next().unwrap().trim().chars().next().unwrap();
+        let reference: char = lines
+            .next()
+            .and_then(|line| line.trim().chars().next())
+            .ok_or(ParseError::new(DAY, "empty schema"))?;
         let is_key = reference == '#';
         // 1 bc of first line and also 6 (7-1) to accredit for "line 0"
         let mut pins = if is_key { [1u8; 5] } else { [6u8; 5] };
+        let mut rows = 1;
 
-        for line in lines {
+        for (row, line) in lines.enumerate() {
+            rows += 1;
             for (idx, c) in line.trim().chars().enumerate() {
+                if c != '#' && c != '.' {
+                    return Err(
+                        ParseError::new(DAY, format!("unexpected '{c}'")).at(row + 2, idx + 1)
+                    );
+                }
+                if idx >= pins.len() {
+                    return Err(ParseError::new(DAY, "a schema has 5 columns").at(row + 2, idx + 1));
+                }
                 if c == reference {
                     if is_key {
                         pins[idx] += 1;
@@ -27,14 +47,39 @@ impl From<&str> for KeyLockSchema {
             }
         }
 
-        KeyLockSchema {
+        if rows != 7 {
+            return Err(ParseError::new(
+                DAY,
+                format!("a schema has 7 rows but found {rows}"),
+            ));
+        }
+
+        Ok(KeyLockSchema {
             raw: value.to_string(),
             pins,
             is_key,
-        }
+        })
     }
 }
 
+/// schemas are separated by an empty line - shifts the schema-relative error lines to input lines
+fn parse_schemas(input: &str) -> Result<Vec<KeyLockSchema>, ParseError> {
+    let mut first_line = 1;
+    input
+        .split("\n\n")
+        .map(|block| {
+            let leading = &block[..block.len() - block.trim_start().len()];
+            let offset = first_line + leading.matches('\n').count() - 1;
+            first_line += block.matches('\n').count() + 2;
+
+            KeyLockSchema::try_from(block).map_err(|e| ParseError {
+                line: Some(e.line.unwrap_or(1) + offset),
+                ..e
+            })
+        })
+        .collect()
+}
+
 impl KeyLockSchema {
     fn fits(&self, o: &KeyLockSchema) -> bool {
         if self.is_key == o.is_key {
@@ -54,10 +99,9 @@ impl Display for KeyLockSchema {
     }
 }
 
-pub fn solve_day_25_part_01(input: &str) -> u32 {
-    input
-        .split("\n\n")
-        .map(KeyLockSchema::from)
+pub fn solve_day_25_part_01(input: &str) -> Result<u32, ParseError> {
+    Ok(parse_schemas(input)?
+        .into_iter()
         .fold((0u32, HashSet::new()), |(mut c, mut set), schema| {
             c += set
                 .iter()
@@ -66,7 +110,7 @@ pub fn solve_day_25_part_01(input: &str) -> u32 {
             set.insert(schema);
             (c, set)
         })
-        .0
+        .0)
 }
 
 #[cfg(test)]
@@ -78,7 +122,7 @@ mod tests {
     fn should_solve_day_25_part_01() {
         let input = read_string("./src/day25/input.txt").unwrap();
 
-        let solution = solve_day_25_part_01(&input);
+        let solution = solve_day_25_part_01(&input).unwrap();
 
         assert_eq!(3508, solution);
     }
@@ -127,12 +171,25 @@ mod tests {
 #####"
             .trim();
 
-        assert_eq!(3, solve_day_25_part_01(input));
+        assert_eq!(3, solve_day_25_part_01(input).unwrap());
+    }
+
+    #[test]
+    fn should_report_bad_schema() {
+        let input = "#####\n.####\n.####\n.####\n.#.#.\n.#...\n.....\n\n.....\n#....\n#.x..\n#...#\n#.#.#\n#.###\n#####";
+        let e = solve_day_25_part_01(input).unwrap_err();
+        assert_eq!("day 25 (line 11, column 3): unexpected 'x'", e.to_string());
+
+        let e = solve_day_25_part_01("#####\n.####\n.....").unwrap_err();
+        assert_eq!(
+            "day 25 (line 1): a schema has 7 rows but found 3",
+            e.to_string()
+        );
     }
 
     #[test]
     fn should_pins() {
-        let s = KeyLockSchema::from(
+        let s = KeyLockSchema::try_from(
             "
     #####
     ##.##
@@ -141,14 +198,15 @@ mod tests {
     ...#.
     ...#.
     .....",
-        );
+        )
+        .unwrap();
 
         assert_eq!([2, 3, 1, 6, 4], s.pins);
     }
 
     #[test]
     fn should_fit() {
-        let s1 = KeyLockSchema::from(
+        let s1 = KeyLockSchema::try_from(
             "
 #####
 #####
@@ -158,9 +216,10 @@ mod tests {
 ...#.
 .....
 ",
-        );
+        )
+        .unwrap();
 
-        let s2 = KeyLockSchema::from(
+        let s2 = KeyLockSchema::try_from(
             "
 .....
 .....
@@ -170,14 +229,15 @@ mod tests {
 ###.#
 #####
 ",
-        );
+        )
+        .unwrap();
 
         assert!(s1.fits(&s2));
     }
 
     #[test]
     fn shouldnt_fit() {
-        let s1 = KeyLockSchema::from(
+        let s1 = KeyLockSchema::try_from(
             "
 #####
 #####
@@ -187,9 +247,10 @@ mod tests {
 ...#.
 .....
 ",
-        );
+        )
+        .unwrap();
 
-        let s2 = KeyLockSchema::from(
+        let s2 = KeyLockSchema::try_from(
             "
 .....
 .....
@@ -199,7 +260,8 @@ mod tests {
 ###.#
 #####
 ",
-        );
+        )
+        .unwrap();
 
         assert!(!s1.fits(&s2));
     }
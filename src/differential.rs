@@ -0,0 +1,360 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+
+use itertools::Itertools;
+
+use crate::answer::Answer;
+use crate::util::error::ParseError;
+use crate::util::random::XorShift;
+use crate::*;
+
+/// what one implementation made of an input - panics are caught, so a panicking fast path shows up
+/// as divergence instead of taking the whole run down
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Solved(Answer),
+    Invalid(ParseError),
+    Panicked,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Solved(answer) => write!(f, "{answer}"),
+            Outcome::Invalid(e) => write!(f, "invalid input - {e}"),
+            Outcome::Panicked => write!(f, "panicked"),
+        }
+    }
+}
+
+/// the first input on which two implementations disagree - `case` is the 0-based index of the input
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence<I, O> {
+    pub case: usize,
+    pub input: I,
+    pub left: O,
+    pub right: O,
+}
+
+impl<I: Display, O: Display> Display for Divergence<I, O> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "diverged on case {}: {} != {}",
+            self.case, self.left, self.right
+        )?;
+        write!(f, "--- input ---\n{}", self.input)
+    }
+}
+
+/// feeds every input to both implementations and stops at the first disagreement
+pub fn first_divergence<I, O, L, R>(
+    inputs: impl IntoIterator<Item = I>,
+    left: L,
+    right: R,
+) -> Option<Divergence<I, O>>
+where
+    O: PartialEq,
+    L: Fn(&I) -> O,
+    R: Fn(&I) -> O,
+{
+    inputs.into_iter().enumerate().find_map(|(case, input)| {
+        let (l, r) = (left(&input), right(&input));
+        if l == r {
+            None
+        } else {
+            Some(Divergence {
+                case,
+                input,
+                left: l,
+                right: r,
+            })
+        }
+    })
+}
+
+/// two implementations of the same puzzle plus a generator for inputs that satisfy the puzzle's
+/// promises (e.g. "the guard leaves the map") - both have to agree on every generated input
+pub struct Twin {
+    pub day: u8,
+    pub part: u8,
+    pub name: &'static str,
+    generate: fn(&mut XorShift) -> String,
+    left: fn(&str) -> Result<Answer, ParseError>,
+    right: fn(&str) -> Result<Answer, ParseError>,
+}
+
+fn run(solve: fn(&str) -> Result<Answer, ParseError>, input: &str) -> Outcome {
+    match panic::catch_unwind(AssertUnwindSafe(|| solve(input))) {
+        Ok(Ok(answer)) => Outcome::Solved(answer),
+        Ok(Err(e)) => Outcome::Invalid(e),
+        Err(_) => Outcome::Panicked,
+    }
+}
+
+impl Twin {
+    /// generates `cases` inputs from `seed` - same seed, same inputs
+    pub fn inputs(&self, seed: u64, cases: usize) -> impl Iterator<Item = String> + '_ {
+        let mut rng = XorShift::new(seed);
+        (0..cases).map(move |_| (self.generate)(&mut rng))
+    }
+
+    pub fn first_divergence(&self, seed: u64, cases: usize) -> Option<Divergence<String, Outcome>> {
+        first_divergence(
+            self.inputs(seed, cases),
+            |input| run(self.left, input),
+            |input| run(self.right, input),
+        )
+    }
+}
+
+/// `mul(a,b)`, `do()` and `don't()` - sometimes broken - in between random garbage
+fn generate_corrupted_memory(rng: &mut XorShift) -> String {
+    const GARBAGE: &[char] = &[
+        'm', 'u', 'l', '(', ')', ',', 'd', 'o', 'n', '\'', 't', ' ', '*', '[', ']', '%', '!', '1',
+        '7', '\n',
+    ];
+    const BROKEN: &[&str] = &["mul(", "do(", "don't(", "mul(4*", "mul ( 2,3)", "mul(12,"];
+    let mut memory = String::new();
+    for _ in 0..rng.between(1, 60) {
+        match rng.below(8) {
+            0 => memory.push_str("do()"),
+            1 => memory.push_str("don't()"),
+            2 => memory.push_str(rng.pick::<&str>(BROKEN)),
+            3 | 4 => {
+                let (a, b) = (rng.between(0, 999), rng.between(0, 999));
+                memory.push_str(&format!("mul({a},{b})"));
+            }
+            _ => (0..rng.between(1, 4)).for_each(|_| memory.push(*rng.pick(GARBAGE))),
+        }
+    }
+    memory
+}
+
+/// walks the map like the guard does - `true` if the guard leaves it (instead of looping forever)
+fn guard_leaves(map: &[Vec<char>], mut pos: (isize, isize)) -> bool {
+    const DIRECTIONS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+    let at = |(x, y): (isize, isize)| {
+        map.get(usize::try_from(y).ok()?)?
+            .get(usize::try_from(x).ok()?)
+            .copied()
+    };
+    let mut dir = 0;
+    let mut seen = HashSet::new();
+
+    while seen.insert((pos, dir)) {
+        let turns = (0..4).find(|turn| {
+            let (dx, dy) = DIRECTIONS[(dir + turn) % 4];
+            at((pos.0 + dx, pos.1 + dy)) != Some('#')
+        });
+        let Some(turns) = turns else {
+            return false; // boxed in
+        };
+        dir = (dir + turns) % 4;
+        let (dx, dy) = DIRECTIONS[dir];
+        pos = (pos.0 + dx, pos.1 + dy);
+        if at(pos).is_none() {
+            return true;
+        }
+    }
+    false
+}
+
+/// small lab maps where the guard (without extra obstacle) is guaranteed to leave
+fn generate_lab_map(rng: &mut XorShift) -> String {
+    loop {
+        let (width, height) = (rng.between(3, 14), rng.between(3, 14));
+        let mut map: Vec<Vec<char>> = (0..height)
+            .map(|_| {
+                (0..width)
+                    .map(|_| if rng.one_in(6) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        let start = (rng.below(width), rng.below(height));
+        map[start.1][start.0] = '^';
+
+        if guard_leaves(&map, (start.0 as isize, start.1 as isize)) {
+            return map
+                .iter()
+                .map(|row| row.iter().collect::<String>())
+                .join("\n");
+        }
+    }
+}
+
+fn generate_door_codes(rng: &mut XorShift) -> String {
+    (0..rng.between(1, 5))
+        .map(|_| format!("{:03}A", rng.below(1000)))
+        .join("\n")
+}
+
+/// the puzzle promises that every computer has the same number of connections `d` and that
+/// exactly one LAN party of size `d` exists - that's what the hacky solution relies on
+///
+/// built from a triangle free (bipartite) circulant graph where every node has degree `d`, minus
+/// `d/2` edges whose ends are re-wired to the `d` members of the party instead
+fn generate_lan(rng: &mut XorShift) -> String {
+    let d = *rng.pick(&[4, 6]);
+    let m = rng.between(d + 1, 20);
+
+    let mut names: Vec<String> = ('a'..='z')
+        .flat_map(|a| ('a'..='z').map(move |b| format!("{a}{b}")))
+        .collect();
+    rng.shuffle(&mut names);
+    let (left, rest) = names.split_at(m);
+    let (right, rest) = rest.split_at(m);
+    let party = &rest[..d];
+
+    let mut shifts: Vec<usize> = (0..m).collect();
+    rng.shuffle(&mut shifts);
+    let shifts = &shifts[..d];
+
+    let mut edges: Vec<(&str, &str)> = vec![];
+    for (i, l) in left.iter().enumerate() {
+        for (s, shift) in shifts.iter().enumerate() {
+            let r = &right[(i + shift) % m];
+            if s == 0 && i < d / 2 {
+                // re-wired to the party instead
+                edges.push((l, &party[2 * i]));
+                edges.push((r, &party[2 * i + 1]));
+            } else {
+                edges.push((l, r));
+            }
+        }
+    }
+    for (i, a) in party.iter().enumerate() {
+        for b in &party[i + 1..] {
+            edges.push((a, b));
+        }
+    }
+
+    rng.shuffle(&mut edges);
+    edges
+        .into_iter()
+        .map(|(a, b)| {
+            if rng.one_in(2) {
+                format!("{a}-{b}")
+            } else {
+                format!("{b}-{a}")
+            }
+        })
+        .join("\n")
+}
+
+static TWINS: &[Twin] = &[
+    Twin {
+        day: 3,
+        part: 2,
+        name: "starts_with vs state_machine",
+        generate: generate_corrupted_memory,
+        left: |input| Ok(day03::part2_starts_with::solve_day_03_part_02_starts_with(input).into()),
+        right: |input| {
+            Ok(
+                day03::part2_state_machine::solve_day_03_part_02_state_machine(input.chars())
+                    .into(),
+            )
+        },
+    },
+    Twin {
+        day: 3,
+        part: 2,
+        name: "starts_with vs interpreter",
+        generate: generate_corrupted_memory,
+        left: |input| Ok(day03::part2_starts_with::solve_day_03_part_02_starts_with(input).into()),
+        right: |input| Ok(day03::interpreter::solve_day_03_part_02_interpreter(input).into()),
+    },
+    Twin {
+        day: 6,
+        part: 2,
+        name: "HashMap vs DenseGrid",
+        generate: generate_lab_map,
+        left: |input| day06::solve_day_06_part_02(input.to_string()).map(Answer::from),
+        right: |input| day06::solve_day_06_part_02_fast(input.to_string()).map(Answer::from),
+    },
+    Twin {
+        day: 21,
+        part: 1,
+        name: "compile vs compile_fast",
+        generate: generate_door_codes,
+        left: |input| day21::part1::solve_day_21_slow(input, 2).map(Answer::from),
+        right: |input| day21::part2::solve_day_21_fast(input, 2).map(Answer::from),
+    },
+    Twin {
+        day: 23,
+        part: 2,
+        name: "clique search vs hacky",
+        generate: generate_lan,
+        left: |input| day23::part2::solve_day_23_part_02(input).map(Answer::from),
+        right: |input| {
+            day23::part2::solve_day_23_part_02_with_hacky_shit_solution(input).map(Answer::from)
+        },
+    },
+];
+
+pub fn twins() -> impl Iterator<Item = &'static Twin> {
+    TWINS.iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: u64 = 2024;
+    const CASES: usize = 50;
+
+    #[test]
+    fn should_agree_on_generated_inputs() {
+        for twin in twins() {
+            if let Some(divergence) = twin.first_divergence(SEED, CASES) {
+                panic!(
+                    "day {} part {} ({}) {divergence}",
+                    twin.day, twin.part, twin.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn should_report_first_divergent_input() {
+        let divergence = first_divergence(1..10, |n| n * 2, |n| if *n < 4 { n * 2 } else { n + 4 });
+
+        assert_eq!(
+            Some(Divergence {
+                case: 4,
+                input: 5,
+                left: 10,
+                right: 9
+            }),
+            divergence
+        );
+    }
+
+    #[test]
+    fn should_catch_panics_as_divergence() {
+        let twin = Twin {
+            day: 0,
+            part: 0,
+            name: "always vs never",
+            generate: |rng| rng.below(10).to_string(),
+            left: |input| Ok(Answer::Text(input.to_string())),
+            right: |_| panic!("not implemented"),
+        };
+
+        let divergence = twin.first_divergence(SEED, 5).unwrap();
+
+        assert_eq!(0, divergence.case);
+        assert_eq!(Outcome::Panicked, divergence.right);
+    }
+
+    #[test]
+    fn should_generate_lan_with_single_party() {
+        let mut rng = XorShift::new(SEED);
+        let lan = generate_lan(&mut rng);
+
+        let solution = day23::part2::solve_day_23_part_02(&lan).unwrap();
+
+        assert!(solution.split(',').count() >= 4);
+    }
+}
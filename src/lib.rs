@@ -1,5 +1,6 @@
 extern crate core;
 
+pub mod answer;
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -25,4 +26,9 @@ pub mod day22;
 pub mod day23;
 pub mod day24;
 pub mod day25;
+pub mod differential;
+pub mod solver;
 pub mod util;
+
+pub use answer::{Answer, AnswerKind};
+pub use solver::{playback, recording, solver, solvers, Solver};
@@ -0,0 +1,426 @@
+use crate::answer::Answer;
+use crate::util::animation::Recorder;
+use crate::util::error::ParseError;
+use crate::util::playback::Playback;
+use crate::*;
+
+/// common entry point for every puzzle - takes the raw puzzle input and returns a typed [`Answer`]
+/// (or a [`ParseError`] pointing at the malformed input), so tooling doesn't need to know the
+/// per-day function signatures
+pub trait Solver: Sync {
+    fn day(&self) -> u8;
+    fn part(&self) -> u8;
+    fn solve(&self, input: &str) -> Result<Answer, ParseError>;
+}
+
+/// a registered solution - the `solve` fn adapts the day's own entry point to
+/// `&str -> Result<Answer, ParseError>`
+pub struct Solution {
+    pub day: u8,
+    pub part: u8,
+    pub name: &'static str,
+    solve: fn(&str) -> Result<Answer, ParseError>,
+}
+
+impl Solver for Solution {
+    fn day(&self) -> u8 {
+        self.day
+    }
+
+    fn part(&self) -> u8 {
+        self.part
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, ParseError> {
+        (self.solve)(input)
+    }
+}
+
+fn to_lines(input: &str) -> Vec<String> {
+    input.lines().map(str::to_string).collect()
+}
+
+/// every (day, part) in puzzle order - puzzle specific parameters (map sizes, iterations, ...)
+/// are fixed to the values of the actual puzzle
+static SOLUTIONS: &[Solution] = &[
+    Solution {
+        day: 1,
+        part: 1,
+        name: "solve_day_01_part_01",
+        solve: |input| day01::solve_day_01_part_01(to_lines(input)).map(Answer::from),
+    },
+    Solution {
+        day: 1,
+        part: 2,
+        name: "solve_day_01_part_02",
+        solve: |input| day01::solve_day_01_part_02(to_lines(input)).map(Answer::from),
+    },
+    Solution {
+        day: 2,
+        part: 1,
+        name: "solve_day_02_part_01",
+        solve: |input| day02::solve_day_02_part_01(to_lines(input)).map(Answer::from),
+    },
+    Solution {
+        day: 2,
+        part: 2,
+        name: "solve_day_02_part_02",
+        solve: |input| day02::solve_day_02_part_02(to_lines(input)).map(Answer::from),
+    },
+    Solution {
+        day: 3,
+        part: 1,
+        name: "solve_day_03_part_01_interpreter",
+        solve: |input| Ok(day03::interpreter::solve_day_03_part_01_interpreter(input).into()),
+    },
+    Solution {
+        day: 3,
+        part: 2,
+        name: "solve_day_03_part_02_interpreter",
+        solve: |input| Ok(day03::interpreter::solve_day_03_part_02_interpreter(input).into()),
+    },
+    Solution {
+        day: 4,
+        part: 1,
+        name: "solve_day_04_part_01",
+        solve: |input| Ok(day04::solve_day_04_part_01(input.to_string()).into()),
+    },
+    Solution {
+        day: 4,
+        part: 2,
+        name: "solve_day_04_part_02",
+        solve: |input| Ok(day04::solve_day_04_part_02(input.to_string()).into()),
+    },
+    Solution {
+        day: 5,
+        part: 1,
+        name: "solve_day_05_part_01",
+        solve: |input| day05::solve_day_05_part_01(to_lines(input)).map(Answer::from),
+    },
+    Solution {
+        day: 5,
+        part: 2,
+        name: "solve_day_05_part_02",
+        solve: |input| day05::solve_day_05_part_02(to_lines(input)).map(Answer::from),
+    },
+    Solution {
+        day: 6,
+        part: 1,
+        name: "solve_day_06_part_01",
+        solve: |input| day06::solve_day_06_part_01(input.to_string()).map(Answer::from),
+    },
+    Solution {
+        day: 6,
+        part: 2,
+        name: "solve_day_06_part_02_fast",
+        solve: |input| day06::solve_day_06_part_02_fast(input.to_string()).map(Answer::from),
+    },
+    Solution {
+        day: 7,
+        part: 1,
+        name: "solve_day_07",
+        solve: |input| day07::part1::solve_day_07(input.to_string(), false).map(Answer::from),
+    },
+    Solution {
+        day: 7,
+        part: 2,
+        name: "solve_day_07",
+        solve: |input| day07::part1::solve_day_07(input.to_string(), true).map(Answer::from),
+    },
+    Solution {
+        day: 8,
+        part: 1,
+        name: "solve_day_08_part_01",
+        solve: |input| Ok(day08::part1::solve_day_08_part_01(input.to_string()).into()),
+    },
+    Solution {
+        day: 8,
+        part: 2,
+        name: "solve_day_08_part_02",
+        solve: |input| Ok(day08::part2::solve_day_08_part_02(input.to_string()).into()),
+    },
+    Solution {
+        day: 9,
+        part: 1,
+        name: "solve_day_09_part_01",
+        // the disk map is a single line of digits - a trailing newline is not a digit
+        solve: |input| day09::part1::solve_day_09_part_01(input.trim().to_string()).map(Answer::from),
+    },
+    Solution {
+        day: 9,
+        part: 2,
+        name: "solve_day_09_part_02",
+        solve: |input| day09::part2::solve_day_09_part_02(input.trim().to_string()).map(Answer::from),
+    },
+    Solution {
+        day: 10,
+        part: 1,
+        name: "solve_day_10_part_01",
+        solve: |input| Ok(day10::part1::solve_day_10_part_01(input.to_string()).into()),
+    },
+    Solution {
+        day: 10,
+        part: 2,
+        name: "solve_day_10_part_02",
+        solve: |input| Ok(day10::part2::solve_day_10_part_02(input.to_string()).into()),
+    },
+    Solution {
+        day: 11,
+        part: 1,
+        name: "solve_day_11",
+        solve: |input| day11::solve_day_11(input, 25).map(Answer::from),
+    },
+    Solution {
+        day: 11,
+        part: 2,
+        name: "solve_day_11",
+        solve: |input| day11::solve_day_11(input, 75).map(Answer::from),
+    },
+    Solution {
+        day: 12,
+        part: 1,
+        name: "solve_day_12_part_01",
+        solve: |input| Ok(day12::part1::solve_day_12_part_01(input).into()),
+    },
+    Solution {
+        day: 12,
+        part: 2,
+        name: "solve_day_12_part_02",
+        solve: |input| Ok(day12::part2::solve_day_12_part_02(input).into()),
+    },
+    Solution {
+        day: 13,
+        part: 1,
+        name: "solve_day_13_part_01",
+        solve: |input| day13::part1::solve_day_13_part_01(input).map(Answer::from),
+    },
+    Solution {
+        day: 13,
+        part: 2,
+        name: "solve_day_13_part_02",
+        solve: |input| day13::part2::solve_day_13_part_02(input).map(Answer::from),
+    },
+    Solution {
+        day: 14,
+        part: 1,
+        name: "solve_day_14_part_01",
+        solve: |input| day14::part1::solve_day_14_part_01(input, 101, 103, 100).map(Answer::from),
+    },
+    Solution {
+        day: 14,
+        part: 2,
+        name: "solve_day_14_part_02",
+        solve: |input| day14::part2::solve_day_14_part_02(input).map(Answer::from),
+    },
+    Solution {
+        day: 15,
+        part: 1,
+        name: "solve_day_15_part_01",
+        solve: |input| day15::part1::solve_day_15_part_01(input).map(Answer::from),
+    },
+    Solution {
+        day: 15,
+        part: 2,
+        name: "solve_day_15_part_02",
+        solve: |input| day15::part2::solve_day_15_part_02(input).map(Answer::from),
+    },
+    Solution {
+        day: 16,
+        part: 1,
+        name: "solve_day_16_part_01",
+        solve: |input| day16::part1::solve_day_16_part_01(input).map(Answer::from),
+    },
+    Solution {
+        day: 16,
+        part: 2,
+        name: "solve_day_16_part_02",
+        solve: |input| day16::part2::solve_day_16_part_02(input).map(Answer::from),
+    },
+    Solution {
+        day: 17,
+        part: 1,
+        name: "solve_day_17_part_01",
+        solve: |input| day17::part1::solve_day_17_part_01(input).map(Answer::from),
+    },
+    Solution {
+        day: 17,
+        part: 2,
+        name: "solve_day_17_part_02",
+        solve: |input| day17::part2::parse(input).map(|m| day17::part2::solve_day_17_part_02(&m).into()),
+    },
+    Solution {
+        day: 18,
+        part: 1,
+        name: "solve_day_18_part_01",
+        solve: |input| day18::part1::solve_day_18_part_01(input, 71, 1024).map(Answer::from),
+    },
+    Solution {
+        day: 18,
+        part: 2,
+        name: "solve_day_18_part_02",
+        solve: |input| day18::part2::solve_day_18_part_02(input, 71).map(Answer::from),
+    },
+    Solution {
+        day: 19,
+        part: 1,
+        name: "solve_day_19_part_01",
+        solve: |input| day19::part1::solve_day_19_part_01(input).map(Answer::from),
+    },
+    Solution {
+        day: 19,
+        part: 2,
+        name: "solve_day_19_part_02",
+        solve: |input| day19::part2::solve_day_19_part_02(input).map(Answer::from),
+    },
+    Solution {
+        day: 20,
+        part: 1,
+        name: "solve_day_20_part_01",
+        solve: |input| day20::part1::solve_day_20_part_01(input, 100).map(Answer::from),
+    },
+    Solution {
+        day: 20,
+        part: 2,
+        name: "solve_day_20_part_02",
+        solve: |input| day20::part2::solve_day_20_part_02(input, 100).map(Answer::from),
+    },
+    Solution {
+        day: 21,
+        part: 1,
+        name: "solve_day_21_fast",
+        solve: |input| day21::part2::solve_day_21_fast(input.trim(), 2).map(Answer::from),
+    },
+    Solution {
+        day: 21,
+        part: 2,
+        name: "solve_day_21_fast",
+        solve: |input| day21::part2::solve_day_21_fast(input.trim(), 25).map(Answer::from),
+    },
+    Solution {
+        day: 22,
+        part: 1,
+        name: "solve_day_22_part_01",
+        solve: |input| day22::part1::solve_day_22_part_01(input).map(Answer::from),
+    },
+    Solution {
+        day: 22,
+        part: 2,
+        name: "solve_day_22_part_02",
+        solve: |input| day22::part2::solve_day_22_part_02(input).map(Answer::from),
+    },
+    Solution {
+        day: 23,
+        part: 1,
+        name: "solve_day_23_part_01",
+        solve: |input| day23::part1::solve_day_23_part_01(input).map(Answer::from),
+    },
+    Solution {
+        day: 23,
+        part: 2,
+        name: "solve_day_23_part_02",
+        solve: |input| day23::part2::solve_day_23_part_02(input).map(Answer::from),
+    },
+    Solution {
+        day: 24,
+        part: 1,
+        name: "solve_day_24_part_01",
+        solve: |input| day24::part1::solve_day_24_part_01(input).map(Answer::from),
+    },
+    Solution {
+        day: 24,
+        part: 2,
+        name: "solve_day_24_part_02",
+        solve: |input| day24::part2::solve_day_24_part_02(input).map(Answer::from),
+    },
+    Solution {
+        day: 25,
+        part: 1,
+        name: "solve_day_25_part_01",
+        solve: |input| day25::part1::solve_day_25_part_01(input).map(Answer::from),
+    },
+];
+
+/// all registered solutions ordered by day and part
+pub fn solvers() -> impl Iterator<Item = &'static dyn Solver> {
+    SOLUTIONS.iter().map(|s| s as &dyn Solver)
+}
+
+pub fn solver(day: u8, part: u8) -> Option<&'static dyn Solver> {
+    solvers().find(|s| s.day() == day && s.part() == part)
+}
+
+/// days whose simulation can be watched in a terminal
+pub const PLAYBACK_DAYS: [u8; 3] = [6, 14, 15];
+
+/// the simulation of `day` as [Playback] - `None` if there is nothing to watch for that day
+pub fn playback(day: u8, input: &str) -> Option<Result<Playback, ParseError>> {
+    match day {
+        6 => Some(day06::playback_day_06(input.to_string())),
+        14 => Some(day14::part2::playback_day_14(input)),
+        15 => Some(day15::part1::playback_day_15(input)),
+        _ => None,
+    }
+}
+
+/// days whose simulation can be exported as images
+pub const RECORDING_DAYS: [u8; 2] = [14, 15];
+
+/// the frames of `day` worth looking at - `None` if there is nothing to export for that day
+pub fn recording(day: u8, input: &str) -> Option<Result<Recorder, ParseError>> {
+    match day {
+        14 => Some(day14::part2::record_day_14(input)),
+        15 => Some(day15::part1::record_day_15(input)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn should_register_every_day() {
+        let keys: HashSet<(u8, u8)> = solvers().map(|s| (s.day(), s.part())).collect();
+
+        assert_eq!(49, solvers().count());
+        assert_eq!(49, keys.len());
+        for day in 1..=25 {
+            assert!(keys.contains(&(day, 1)), "day {day} part 1 missing");
+        }
+    }
+
+    #[test]
+    fn should_solve_via_registry() {
+        let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+        assert_eq!(Ok(Answer::Integer(11)), solver(1, 1).unwrap().solve(input));
+        assert_eq!(Ok(Answer::Integer(31)), solver(1, 2).unwrap().solve(input));
+        assert!(solver(25, 2).is_none());
+    }
+
+    #[test]
+    fn should_play_back_registered_days() {
+        for day in PLAYBACK_DAYS {
+            assert!(playback(day, "").is_some());
+        }
+        assert!(playback(1, "").is_none());
+    }
+
+    #[test]
+    fn should_record_registered_days() {
+        for day in RECORDING_DAYS {
+            assert!(recording(day, "").is_some());
+        }
+        assert!(recording(1, "").is_none());
+    }
+
+    #[test]
+    fn should_report_malformed_input_via_registry() {
+        let e = solver(1, 1).unwrap().solve("3   4\n4   x\n").unwrap_err();
+
+        assert_eq!(1, e.day);
+        assert_eq!(Some(2), e.line);
+    }
+}
@@ -0,0 +1,398 @@
+use std::fmt::{Display, Formatter};
+
+use crate::util::algebra::rational::Rational;
+use crate::util::number::{crt, lcm, solve_linear_congruence};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinearError {
+    /// some intermediate result didn't fit into an `i128`
+    Overflow,
+    /// integer solutions are only searched for with at most one free variable
+    TooManyFreeVariables(usize),
+}
+
+impl Display for LinearError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LinearError::Overflow => write!(f, "overflow while solving the linear system"),
+            LinearError::TooManyFreeVariables(n) => {
+                write!(f, "{n} free variables - can't search integer solutions")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LinearError {}
+
+/// `a * x = b` with `a` having one row per equation and one column per unknown - any shape, any
+/// rank, solved exactly
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearSystem {
+    a: Vec<Vec<Rational>>,
+    b: Vec<Rational>,
+    unknowns: usize,
+}
+
+/// what Gaussian elimination found out about a [LinearSystem]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solutions {
+    rank: usize,
+    particular: Option<Vec<Rational>>,
+    nullspace: Vec<Vec<Rational>>,
+    free: Vec<usize>,
+}
+
+impl Solutions {
+    /// number of independent equations
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        self.particular.is_some()
+    }
+
+    /// one solution (all free variables 0) - `None` if the equations contradict each other
+    pub fn particular(&self) -> Option<&[Rational]> {
+        self.particular.as_deref()
+    }
+
+    /// basis of all `x` with `a * x = 0` - any combination of them can be added to
+    /// [Solutions::particular] (one vector per free variable, empty if the solution is unique)
+    pub fn nullspace(&self) -> &[Vec<Rational>] {
+        &self.nullspace
+    }
+
+    /// indices of the unknowns that can be chosen freely
+    pub fn free_variables(&self) -> &[usize] {
+        &self.free
+    }
+
+    pub fn unique(&self) -> Option<&[Rational]> {
+        self.particular().filter(|_| self.nullspace.is_empty())
+    }
+
+    /// the unique solution - if there is one and it's made of integers only
+    pub fn unique_integer(&self) -> Option<Vec<i128>> {
+        self.unique()?.iter().map(Rational::to_integer).collect()
+    }
+}
+
+impl LinearSystem {
+    /// panics if the rows differ in length or `b` doesn't have one value per row
+    pub fn new(a: Vec<Vec<Rational>>, b: Vec<Rational>) -> Self {
+        assert_eq!(a.len(), b.len(), "need one right hand side per equation");
+        let unknowns = a.first().map_or(0, Vec::len);
+        assert!(
+            a.iter().all(|row| row.len() == unknowns),
+            "all equations need the same number of unknowns"
+        );
+        LinearSystem { a, b, unknowns }
+    }
+
+    pub fn from_integers(a: &[Vec<i128>], b: &[i128]) -> Self {
+        LinearSystem::new(
+            a.iter()
+                .map(|row| row.iter().copied().map(Rational::from).collect())
+                .collect(),
+            b.iter().copied().map(Rational::from).collect(),
+        )
+    }
+
+    /// Gauss-Jordan elimination down to the reduced row echelon form
+    pub fn solve(&self) -> Result<Solutions, LinearError> {
+        let mut rows: Vec<Vec<Rational>> = self
+            .a
+            .iter()
+            .zip(&self.b)
+            .map(|(row, b)| row.iter().chain([b]).copied().collect())
+            .collect();
+        let mut pivots: Vec<usize> = vec![];
+
+        for col in 0..self.unknowns {
+            let row = pivots.len();
+            let Some(pivot_row) = (row..rows.len()).find(|&r| !rows[r][col].is_zero()) else {
+                continue;
+            };
+            rows.swap(row, pivot_row);
+
+            let pivot = rows[row][col];
+            for value in rows[row].iter_mut() {
+                *value = value.checked_div(pivot).ok_or(LinearError::Overflow)?;
+            }
+            let pivot_row = rows[row].clone();
+            for (other, values) in rows.iter_mut().enumerate() {
+                let factor = values[col];
+                if other == row || factor.is_zero() {
+                    continue;
+                }
+                for (value, pivot_value) in values.iter_mut().zip(&pivot_row).skip(col) {
+                    *value = factor
+                        .checked_mul(*pivot_value)
+                        .and_then(|step| value.checked_sub(step))
+                        .ok_or(LinearError::Overflow)?;
+                }
+            }
+            pivots.push(col);
+        }
+
+        let rank = pivots.len();
+        let consistent = rows[rank..].iter().all(|row| row[self.unknowns].is_zero());
+        let particular = consistent.then(|| {
+            let mut x = vec![Rational::ZERO; self.unknowns];
+            for (row, &col) in pivots.iter().enumerate() {
+                x[col] = rows[row][self.unknowns];
+            }
+            x
+        });
+
+        let free: Vec<usize> = (0..self.unknowns).filter(|c| !pivots.contains(c)).collect();
+        let nullspace = free
+            .iter()
+            .map(|&f| {
+                let mut v = vec![Rational::ZERO; self.unknowns];
+                v[f] = Rational::ONE;
+                for (row, &col) in pivots.iter().enumerate() {
+                    v[col] = rows[row][f].checked_neg().ok_or(LinearError::Overflow)?;
+                }
+                Ok(v)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Solutions {
+            rank,
+            particular,
+            nullspace,
+            free,
+        })
+    }
+
+    /// integer solution with every unknown >= 0 and the lowest `sum(costs[i] * x[i])` - like the
+    /// cheapest way to press buttons that add up to a target
+    ///
+    /// `None` if there is no such solution (or no cheapest one, as negative costs can make it
+    /// cheaper forever) - systems with more than one free variable aren't supported
+    pub fn cheapest_non_negative_integer(
+        &self,
+        costs: &[i128],
+    ) -> Result<Option<Vec<i128>>, LinearError> {
+        assert_eq!(self.unknowns, costs.len(), "need one cost per unknown");
+        let solutions = self.solve()?;
+        let Some(p) = solutions.particular() else {
+            return Ok(None);
+        };
+
+        let (n, f) = match (solutions.nullspace(), solutions.free_variables()) {
+            ([], _) => {
+                let x = solutions.unique_integer();
+                return Ok(x.filter(|x| x.iter().all(|&v| v >= 0)));
+            }
+            ([n], [f]) => (n, *f),
+            (nullspace, _) => return Err(LinearError::TooManyFreeVariables(nullspace.len())),
+        };
+
+        // x = p + t * n with t = x[f] - t has to be an integer >= 0 and keep everything else an
+        // integer >= 0 too, which narrows it down to an interval and a residue class
+        let overflow = |_| LinearError::Overflow;
+        let mut t = Candidates {
+            low: 0,
+            high: None,
+            residue: 0,
+            modulus: 1,
+        };
+        for i in (0..self.unknowns).filter(|&i| i != f) {
+            if !t.restrict(p[i], n[i]).map_err(overflow)? {
+                return Ok(None);
+            }
+        }
+
+        let dot = |v: &[Rational]| {
+            v.iter()
+                .zip(costs)
+                .try_fold(Rational::ZERO, |sum, (x, &c)| {
+                    sum.checked_add(x.checked_mul(c.into())?)
+                })
+                .ok_or(LinearError::Overflow)
+        };
+        let chosen = if dot(n)?.signum() >= 0 {
+            t.lowest()
+        } else {
+            t.highest()
+        };
+        let Some(t) = chosen.map_err(overflow)? else {
+            return Ok(None);
+        };
+
+        let x = p
+            .iter()
+            .zip(n)
+            .map(|(p, n)| {
+                n.checked_mul(t.into())
+                    .and_then(|tn| p.checked_add(tn))
+                    .and_then(|x| x.to_integer())
+                    .ok_or(LinearError::Overflow)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Some(x))
+    }
+}
+
+/// the integers `low..=high` with `t % modulus == residue`
+struct Candidates {
+    low: i128,
+    high: Option<i128>,
+    residue: i128,
+    modulus: i128,
+}
+
+impl Candidates {
+    /// keeps the `t` for which `p + t * n` is an integer >= 0 - `false` if none are left
+    fn restrict(&mut self, p: Rational, n: Rational) -> Result<bool, ()> {
+        if n.is_zero() {
+            return Ok(p.is_integer() && p.signum() >= 0);
+        }
+
+        // p + t * n >= 0
+        let bound = p.checked_neg().and_then(|p| p.checked_div(n)).ok_or(())?;
+        if n.signum() > 0 {
+            self.low = self.low.max(bound.ceil());
+        } else {
+            self.high = Some(self.high.map_or(bound.floor(), |h| h.min(bound.floor())));
+        }
+
+        // p + t * n integer <=> (p * l) + t * (n * l) = 0 (mod l) with l = lcm of the denominators
+        let l = lcm(p.denominator(), n.denominator()).ok_or(())?;
+        let scaled = |r: Rational| {
+            r.checked_mul(l.into())
+                .and_then(|r| r.to_integer())
+                .ok_or(())
+        };
+        let (a, c) = (scaled(p)?, scaled(n)?);
+        let Some((residue, modulus)) = solve_linear_congruence(c, -a, l) else {
+            return Ok(false);
+        };
+        // crt panics if the combined modulus doesn't fit
+        lcm(self.modulus, modulus).ok_or(())?;
+        let Some((residue, modulus)) = crt([(self.residue, self.modulus), (residue, modulus)])
+        else {
+            return Ok(false);
+        };
+        (self.residue, self.modulus) = (residue, modulus);
+        Ok(self.high.is_none_or(|h| self.low <= h))
+    }
+
+    fn lowest(&self) -> Result<Option<i128>, ()> {
+        let offset = (self.residue - self.low).rem_euclid(self.modulus);
+        let t = self.low.checked_add(offset).ok_or(())?;
+        Ok(Some(t).filter(|&t| self.high.is_none_or(|h| t <= h)))
+    }
+
+    fn highest(&self) -> Result<Option<i128>, ()> {
+        let Some(high) = self.high else {
+            return Ok(None);
+        };
+        let t = high - (high - self.residue).rem_euclid(self.modulus);
+        Ok(Some(t).filter(|&t| t >= self.low))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(numerator: i128, denominator: i128) -> Rational {
+        Rational::new(numerator, denominator).unwrap()
+    }
+
+    #[test]
+    fn should_solve_unique_system() {
+        // x + y + z = 6, 2y + 5z = -4, 2x + 5y - z = 27
+        let system = LinearSystem::from_integers(
+            &[vec![1, 1, 1], vec![0, 2, 5], vec![2, 5, -1]],
+            &[6, -4, 27],
+        );
+
+        let solutions = system.solve().unwrap();
+
+        assert_eq!(3, solutions.rank());
+        assert_eq!(Some(vec![5, 3, -2]), solutions.unique_integer());
+    }
+
+    #[test]
+    fn should_keep_fractions_exact() {
+        let system = LinearSystem::from_integers(&[vec![3, 0], vec![0, 7]], &[1, 2]);
+
+        let solutions = system.solve().unwrap();
+
+        assert_eq!(Some(&[r(1, 3), r(2, 7)][..]), solutions.unique());
+        assert_eq!(None, solutions.unique_integer());
+    }
+
+    #[test]
+    fn should_report_rank_and_nullspace() {
+        // the second equation is the first one doubled - 2 unknowns, 1 free
+        let system = LinearSystem::from_integers(&[vec![1, 2], vec![2, 4], vec![0, 0]], &[3, 6, 0]);
+
+        let solutions = system.solve().unwrap();
+
+        assert_eq!(1, solutions.rank());
+        assert_eq!(Some(&[r(3, 1), Rational::ZERO][..]), solutions.particular());
+        assert_eq!(&[vec![r(-2, 1), Rational::ONE]], solutions.nullspace());
+        assert_eq!(&[1], solutions.free_variables());
+        assert_eq!(None, solutions.unique());
+    }
+
+    #[test]
+    fn should_detect_contradiction() {
+        let system = LinearSystem::from_integers(&[vec![1, 1], vec![1, 1]], &[1, 2]);
+
+        let solutions = system.solve().unwrap();
+
+        assert!(!solutions.is_consistent());
+        assert_eq!(None, system.cheapest_non_negative_integer(&[1, 1]).unwrap());
+    }
+
+    #[test]
+    fn should_find_cheapest_integer_solution_on_a_line() {
+        // 2a + b = 5 (twice) - a costs 3, b costs 1, so b only
+        let cheap_b = LinearSystem::from_integers(&[vec![2, 1], vec![2, 1]], &[5, 5]);
+        // 6a + b = 13 - now a is the better deal per step
+        let cheap_a = LinearSystem::from_integers(&[vec![6, 1]], &[13]);
+        // 4a + 6b = 9 - never an integer
+        let odd = LinearSystem::from_integers(&[vec![4, 6]], &[9]);
+
+        assert_eq!(
+            Some(vec![0, 5]),
+            cheap_b.cheapest_non_negative_integer(&[3, 1]).unwrap()
+        );
+        assert_eq!(
+            Some(vec![2, 1]),
+            cheap_a.cheapest_non_negative_integer(&[3, 1]).unwrap()
+        );
+        assert_eq!(None, odd.cheapest_non_negative_integer(&[3, 1]).unwrap());
+    }
+
+    #[test]
+    fn should_find_cheapest_with_three_buttons() {
+        // x: 3a + 1b + 2c = 17, y: 1a + 1b + 1c = 7
+        let system = LinearSystem::from_integers(&[vec![3, 1, 2], vec![1, 1, 1]], &[17, 7]);
+
+        let cheapest = system.cheapest_non_negative_integer(&[3, 1, 1]).unwrap();
+
+        // all solutions: (a, b, c) = (a, a - 3, 10 - 2a) with a in 3..=5 - the fewest a is cheapest
+        assert_eq!(Some(vec![3, 0, 4]), cheapest);
+        assert_eq!(
+            Err(LinearError::TooManyFreeVariables(2)),
+            LinearSystem::from_integers(&[vec![1, 1, 1]], &[3])
+                .cheapest_non_negative_integer(&[1, 1, 1])
+        );
+    }
+
+    #[test]
+    fn should_report_overflow() {
+        let system =
+            LinearSystem::from_integers(&[vec![1, i128::MAX], vec![i128::MAX, 1]], &[1, 1]);
+
+        assert_eq!(Err(LinearError::Overflow), system.solve());
+    }
+}
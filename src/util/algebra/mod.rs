@@ -1,3 +1,6 @@
+pub mod linear;
+pub mod rational;
+
 use std::ops::{Add, Sub};
 
 pub fn cartesian_product_flat_map<I, J, T>(iter1: I, iter2: J) -> impl Iterator<Item=(T, T)>
@@ -92,4 +95,4 @@ mod tests {
         assert_eq!((2, 4), mirror(p1, p3));
         assert_eq!((-1, 1), mirror(p2, p3));
     }
-}
\ No newline at end of file
+}
\ No newline at end of file
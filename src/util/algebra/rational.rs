@@ -0,0 +1,274 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// exact fraction on `i128` - always reduced with a positive denominator, so equal values are
+/// equal structs
+///
+/// the `checked_` methods return `None` instead of overflowing, the operators panic (like plain
+/// integers in debug builds)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational::integer(0);
+    pub const ONE: Rational = Rational::integer(1);
+
+    /// `None` for a zero denominator (or if the sign can't be moved to the numerator)
+    pub fn new(numerator: i128, denominator: i128) -> Option<Rational> {
+        if denominator == 0 {
+            return None;
+        }
+        let g = gcd(numerator, denominator);
+        let (mut numerator, mut denominator) = (numerator / g, denominator / g);
+        if denominator < 0 {
+            numerator = numerator.checked_neg()?;
+            denominator = denominator.checked_neg()?;
+        }
+        Some(Rational {
+            numerator,
+            denominator,
+        })
+    }
+
+    pub const fn integer(n: i128) -> Rational {
+        Rational {
+            numerator: n,
+            denominator: 1,
+        }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    /// always positive
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    pub fn to_integer(&self) -> Option<i128> {
+        self.is_integer().then_some(self.numerator)
+    }
+
+    pub fn signum(&self) -> i128 {
+        self.numerator.signum()
+    }
+
+    /// rounded towards negative infinity
+    pub fn floor(&self) -> i128 {
+        self.numerator.div_euclid(self.denominator)
+    }
+
+    /// rounded towards positive infinity
+    pub fn ceil(&self) -> i128 {
+        -(-self.numerator).div_euclid(self.denominator)
+    }
+
+    pub fn checked_neg(self) -> Option<Rational> {
+        Some(Rational {
+            numerator: self.numerator.checked_neg()?,
+            ..self
+        })
+    }
+
+    /// `None` for zero
+    pub fn checked_recip(self) -> Option<Rational> {
+        Rational::new(self.denominator, self.numerator)
+    }
+
+    pub fn checked_add(self, rhs: Rational) -> Option<Rational> {
+        // a/b + c/d = (a * d/g + c * b/g) / (b/g * d) with g = gcd(b, d) keeps the numbers small
+        let g = gcd(self.denominator, rhs.denominator);
+        let numerator = self
+            .numerator
+            .checked_mul(rhs.denominator / g)?
+            .checked_add(rhs.numerator.checked_mul(self.denominator / g)?)?;
+        Rational::new(
+            numerator,
+            (self.denominator / g).checked_mul(rhs.denominator)?,
+        )
+    }
+
+    pub fn checked_sub(self, rhs: Rational) -> Option<Rational> {
+        self.checked_add(rhs.checked_neg()?)
+    }
+
+    pub fn checked_mul(self, rhs: Rational) -> Option<Rational> {
+        // cross reduce first - a/b * c/d = (a/g1 * c/g2) / (b/g2 * d/g1)
+        let g1 = gcd(self.numerator, rhs.denominator);
+        let g2 = gcd(rhs.numerator, self.denominator);
+        Rational::new(
+            (self.numerator / g1).checked_mul(rhs.numerator / g2)?,
+            (self.denominator / g2).checked_mul(rhs.denominator / g1)?,
+        )
+    }
+
+    /// `None` for a division by zero too
+    pub fn checked_div(self, rhs: Rational) -> Option<Rational> {
+        self.checked_mul(rhs.checked_recip()?)
+    }
+}
+
+/// always positive (1 for `gcd(0, 0)`, so it can be divided by)
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.max(1) as i128
+}
+
+impl Default for Rational {
+    fn default() -> Self {
+        Rational::ZERO
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(n: i128) -> Self {
+        Rational::integer(n)
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.denominator {
+            1 => write!(f, "{}", self.numerator),
+            d => write!(f, "{}/{d}", self.numerator),
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// compares the integer parts first and only then the remaining fractions, so it never overflows
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = (self.floor(), other.floor());
+        if a != b {
+            return a.cmp(&b);
+        }
+        // both fractions are in [0, 1) - a/b < c/d <=> d/c < b/a
+        let rest = |r: &Rational| r.numerator.rem_euclid(r.denominator);
+        match (rest(self), rest(other)) {
+            (0, 0) => Ordering::Equal,
+            (0, _) => Ordering::Less,
+            (_, 0) => Ordering::Greater,
+            (x, y) => {
+                let inverse = |num: i128, den: i128| Rational::new(den, num).expect("not zero");
+                inverse(y, other.denominator).cmp(&inverse(x, self.denominator))
+            }
+        }
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Self::Output {
+        self.checked_neg().expect("attempt to negate with overflow")
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs).expect("attempt to add with overflow")
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("attempt to subtract with overflow")
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs)
+            .expect("attempt to multiply with overflow")
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs)
+            .expect("attempt to divide by zero or with overflow")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(numerator: i128, denominator: i128) -> Rational {
+        Rational::new(numerator, denominator).unwrap()
+    }
+
+    #[test]
+    fn should_normalize() {
+        assert_eq!(r(1, 2), r(-3, -6));
+        assert_eq!((-2, 3), (r(4, -6).numerator(), r(4, -6).denominator()));
+        assert_eq!(Rational::ZERO, r(0, -5));
+        assert_eq!(None, Rational::new(1, 0));
+        assert_eq!("-2/3", r(4, -6).to_string());
+        assert_eq!(Some(7), r(14, 2).to_integer());
+    }
+
+    #[test]
+    fn should_calculate_exactly() {
+        assert_eq!(r(5, 6), r(1, 2) + r(1, 3));
+        assert_eq!(r(1, 6), r(1, 2) - r(1, 3));
+        assert_eq!(r(1, 6), r(1, 2) * r(1, 3));
+        assert_eq!(r(3, 2), r(1, 2) / r(1, 3));
+        assert_eq!(None, r(1, 2).checked_div(Rational::ZERO));
+        assert_eq!((-2, -1), (r(-3, 2).floor(), r(-3, 2).ceil()));
+        assert_eq!((1, 2), (r(3, 2).floor(), r(3, 2).ceil()));
+    }
+
+    #[test]
+    fn should_detect_overflow() {
+        let big = Rational::integer(i128::MAX);
+
+        assert_eq!(None, big.checked_add(Rational::ONE));
+        assert_eq!(None, big.checked_mul(Rational::integer(2)));
+        assert_eq!(None, Rational::integer(i128::MIN).checked_neg());
+        // cross reducing keeps this one in range
+        assert_eq!(Some(Rational::ONE), big.checked_mul(r(1, i128::MAX)));
+    }
+
+    #[test]
+    fn should_compare_without_overflow() {
+        let almost_one = r(i128::MAX - 1, i128::MAX);
+
+        assert!(r(1, 3) < r(1, 2));
+        assert!(r(-1, 2) < r(-1, 3));
+        assert!(almost_one < Rational::ONE);
+        assert!(r(i128::MAX - 2, i128::MAX - 1) < almost_one);
+        assert_eq!(Some(&r(7, 2)), [r(1, 2), r(7, 2), r(-8, 1)].iter().max());
+    }
+}
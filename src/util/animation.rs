@@ -0,0 +1,419 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::util::image::{Image, Rgb};
+
+/// collects the frames of a simulation - keeps every `every`th step (starting with the first) until
+/// `max_frames` are recorded, so long simulations don't eat all memory
+///
+/// frames are only rendered if they're kept, so recording is cheap for skipped steps
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recorder {
+    every: usize,
+    max_frames: Option<usize>,
+    steps: usize,
+    frames: Vec<Image>,
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Recorder::new()
+    }
+}
+
+impl Recorder {
+    /// every step, no limit
+    pub fn new() -> Self {
+        Recorder {
+            every: 1,
+            max_frames: None,
+            steps: 0,
+            frames: vec![],
+        }
+    }
+
+    /// only every `n`th step becomes a frame - `n` must be > 0
+    pub fn every(self, n: usize) -> Self {
+        assert!(n > 0, "can't record every 0th step");
+        Recorder { every: n, ..self }
+    }
+
+    pub fn max_frames(self, max: usize) -> Self {
+        Recorder {
+            max_frames: Some(max),
+            ..self
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.max_frames.is_some_and(|max| self.frames.len() >= max)
+    }
+
+    /// one step of the simulation - `false` once the recorder is full (the simulation can stop)
+    pub fn record(&mut self, render: impl FnOnce() -> Image) -> bool {
+        if self.is_full() {
+            return false;
+        }
+        if self.steps.is_multiple_of(self.every) {
+            self.frames.push(render());
+        }
+        self.steps += 1;
+        !self.is_full()
+    }
+
+    /// records states until they run out or the recorder is full - the rest isn't even simulated
+    pub fn record_all<S>(
+        &mut self,
+        states: impl IntoIterator<Item = S>,
+        render: impl Fn(&S) -> Image,
+    ) {
+        for state in states {
+            if !self.record(|| render(&state)) {
+                break;
+            }
+        }
+    }
+
+    pub fn frames(&self) -> &[Image] {
+        &self.frames
+    }
+
+    /// looping animated gif with `delay` hundredths of a second per frame - the size of the first
+    /// frame is used for all frames
+    ///
+    /// gifs have at most 256 colours: the first 256 distinct colours make the palette, every other
+    /// colour is drawn as the closest of them (plenty for puzzle maps)
+    pub fn to_gif(&self, delay: u16) -> Vec<u8> {
+        let (width, height) = self
+            .frames
+            .first()
+            .map_or((0, 0), |f| (f.width(), f.height()));
+        let palette = Palette::new(&self.frames);
+
+        let mut bytes = b"GIF89a".to_vec();
+        bytes.extend((width as u16).to_le_bytes());
+        bytes.extend((height as u16).to_le_bytes());
+        // global colour table with 2^bits entries, 8 bit colour resolution
+        bytes.push(0x80 | 0x70 | (palette.bits - 1));
+        bytes.extend([0, 0]); // background colour index, pixel aspect ratio
+        for i in 0..1 << palette.bits {
+            let Rgb(r, g, b) = palette.colours.get(i).copied().unwrap_or_default();
+            bytes.extend([r, g, b]);
+        }
+        // application extension - loop forever
+        bytes.extend([0x21, 0xFF, 0x0B]);
+        bytes.extend(b"NETSCAPE2.0");
+        bytes.extend([0x03, 0x01, 0x00, 0x00, 0x00]);
+
+        let min_code_size = palette.bits.max(2);
+        for frame in &self.frames {
+            // graphic control extension - no transparency, just the delay
+            bytes.extend([0x21, 0xF9, 0x04, 0x00]);
+            bytes.extend(delay.to_le_bytes());
+            bytes.extend([0x00, 0x00]);
+
+            // image descriptor - whole screen, no local colour table
+            bytes.push(0x2C);
+            bytes.extend([0, 0, 0, 0]);
+            bytes.extend((width as u16).to_le_bytes());
+            bytes.extend((height as u16).to_le_bytes());
+            bytes.push(0);
+
+            let indices: Vec<u8> = (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(|(x, y)| palette.index(frame.get(x, y).unwrap_or_default()))
+                .collect();
+            bytes.push(min_code_size);
+            for block in lzw_encode(&indices, min_code_size).chunks(255) {
+                bytes.push(block.len() as u8);
+                bytes.extend(block);
+            }
+            bytes.push(0);
+        }
+        bytes.push(0x3B);
+        bytes
+    }
+
+    /// see [Recorder::to_gif] - missing directories are created
+    pub fn save_gif(&self, path: impl AsRef<Path>, delay: u16) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_gif(delay))
+    }
+
+    /// one `frame_0000.<extension>` per frame in `dir` (see [Image::save] for the extensions) -
+    /// returns the written paths in order
+    pub fn save_sequence(
+        &self,
+        dir: impl AsRef<Path>,
+        extension: &str,
+    ) -> io::Result<Vec<PathBuf>> {
+        self.frames
+            .iter()
+            .enumerate()
+            .map(|(i, frame)| {
+                let path = dir.as_ref().join(format!("frame_{i:04}.{extension}"));
+                frame.save(&path).map(|_| path)
+            })
+            .collect()
+    }
+}
+
+struct Palette {
+    colours: Vec<Rgb>,
+    lookup: HashMap<Rgb, u8>,
+    /// the colour table has 2^bits entries (1..=8)
+    bits: u8,
+}
+
+impl Palette {
+    fn new(frames: &[Image]) -> Self {
+        let mut colours = vec![];
+        let mut lookup = HashMap::new();
+        let pixels = frames.iter().flat_map(|f| {
+            (0..f.height()).flat_map(move |y| (0..f.width()).filter_map(move |x| f.get(x, y)))
+        });
+        for colour in pixels {
+            if colours.len() == 256 {
+                break;
+            }
+            lookup.entry(colour).or_insert_with(|| {
+                colours.push(colour);
+                (colours.len() - 1) as u8
+            });
+        }
+        let bits = (1..=8).find(|b| colours.len() <= 1 << b).unwrap_or(8);
+        Palette {
+            colours,
+            lookup,
+            bits,
+        }
+    }
+
+    fn index(&self, colour: Rgb) -> u8 {
+        if let Some(&i) = self.lookup.get(&colour) {
+            return i;
+        }
+        let distance = |Rgb(r, g, b): &Rgb| {
+            let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+            d(*r, colour.0) + d(*g, colour.1) + d(*b, colour.2)
+        };
+        self.colours
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, c)| distance(c))
+            .map_or(0, |(i, _)| i as u8)
+    }
+}
+
+/// writes codes of varying width, least significant bit first (like gif wants it)
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u8) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += width;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/// variable width gif lzw - starts with a clear code and starts over whenever the 12 bit code
+/// table is full
+fn lzw_encode(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    const MAX_CODE: u16 = 4096;
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    let mut out = BitWriter {
+        bytes: vec![],
+        buffer: 0,
+        bits: 0,
+    };
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next_code = end + 1;
+    let mut width = min_code_size + 1;
+
+    // the width grows as soon as the next code wouldn't fit anymore - the decoder does the same
+    // one code later, as it learns every code one step after the encoder
+    let emit = |out: &mut BitWriter, code: u16, next_code: u16, width: &mut u8| {
+        out.write(code, *width);
+        if next_code >= 1 << *width && *width < 12 {
+            *width += 1;
+        }
+    };
+
+    out.write(clear, width);
+    let Some((&first, rest)) = indices.split_first() else {
+        out.write(end, width);
+        return out.finish();
+    };
+
+    let mut current = first as u16;
+    for &index in rest {
+        if let Some(&code) = table.get(&(current, index)) {
+            current = code;
+            continue;
+        }
+        emit(&mut out, current, next_code, &mut width);
+        if next_code < MAX_CODE {
+            table.insert((current, index), next_code);
+            next_code += 1;
+        } else {
+            out.write(clear, width);
+            table.clear();
+            next_code = end + 1;
+            width = min_code_size + 1;
+        }
+        current = index as u16;
+    }
+    emit(&mut out, current, next_code, &mut width);
+    out.write(end, width);
+    out.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::random::XorShift;
+
+    /// textbook gif lzw decoder - only here to check the encoder against
+    fn lzw_decode(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1u16 << min_code_size;
+        let end = clear + 1;
+        let (mut bit, mut width) = (0usize, min_code_size + 1);
+        let mut table: Vec<Vec<u8>> = vec![];
+        let mut previous: Option<u16> = None;
+        let mut out = vec![];
+
+        loop {
+            let code = (0..width as usize).fold(0u16, |code, i| {
+                let b = (bytes[(bit + i) / 8] >> ((bit + i) % 8)) & 1;
+                code | ((b as u16) << i)
+            });
+            bit += width as usize;
+
+            if code == clear {
+                table = (0..clear).map(|i| vec![i as u8]).collect();
+                table.extend([vec![], vec![]]);
+                width = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return out;
+            }
+            let entry = match (table.get(code as usize), previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(p)) => {
+                    let mut entry = table[p as usize].clone();
+                    entry.push(entry[0]);
+                    entry
+                }
+                (None, None) => panic!("unknown code {code}"),
+            };
+            if let Some(p) = previous {
+                if table.len() < 4096 {
+                    let mut new = table[p as usize].clone();
+                    new.push(entry[0]);
+                    table.push(new);
+                }
+            }
+            if table.len() == 1 << width && width < 12 {
+                width += 1;
+            }
+            out.extend(&entry);
+            previous = Some(code);
+        }
+    }
+
+    fn frame(colour: Rgb) -> Image {
+        let mut image = Image::new(3, 2, Rgb::BLACK);
+        image.set(1, 1, colour);
+        image
+    }
+
+    #[test]
+    fn should_round_trip_lzw() {
+        let mut rng = XorShift::new(14);
+        let cases: Vec<(Vec<u8>, u8)> = vec![
+            (vec![], 2),
+            (vec![1], 2),
+            (vec![0, 0, 0, 0, 1, 1, 1, 1, 0, 0, 0, 0], 2),
+            ((0..20_000).map(|_| rng.below(4) as u8).collect(), 2),
+            ((0..50_000).map(|i| (i / 7 % 200) as u8).collect(), 8),
+            ((0..100_000).map(|_| rng.below(256) as u8).collect(), 8),
+        ];
+
+        for (indices, min_code_size) in cases {
+            let encoded = lzw_encode(&indices, min_code_size);
+            assert_eq!(indices, lzw_decode(&encoded, min_code_size));
+        }
+    }
+
+    #[test]
+    fn should_skip_and_cap_frames() {
+        let mut recorder = Recorder::new().every(3).max_frames(2);
+        let mut simulated = 0;
+
+        recorder.record_all((0..100).inspect(|_| simulated += 1), |&i| {
+            frame(Rgb::distinct(i))
+        });
+
+        assert_eq!(
+            vec![frame(Rgb::distinct(0)), frame(Rgb::distinct(3))],
+            recorder.frames()
+        );
+        assert_eq!(4, simulated);
+        assert!(recorder.is_full());
+        assert!(!recorder.record(|| frame(Rgb::WHITE)));
+    }
+
+    #[test]
+    fn should_write_gif() {
+        let mut recorder = Recorder::new();
+        recorder.record_all([Rgb::RED, Rgb::GREEN, Rgb::BLUE], |&c| frame(c));
+
+        let gif = recorder.to_gif(10);
+
+        assert_eq!(b"GIF89a", &gif[..6]);
+        assert_eq!([3, 0, 2, 0], gif[6..10]);
+        // black, red, green, blue - 4 colours in a 2 bit table
+        assert_eq!(0xF1, gif[10]);
+        assert_eq!([0, 0, 0, 255, 0, 0], gif[13..19]);
+        assert_eq!(3, gif.windows(2).filter(|w| w == &[0x21, 0xF9]).count());
+        assert_eq!(Some(&0x3B), gif.last());
+    }
+
+    #[test]
+    fn should_map_colours_beyond_palette_to_closest() {
+        let mut big = Image::new(16, 17, Rgb::BLACK);
+        for i in 0..16 * 17 {
+            big.set(i % 16, i / 16, Rgb(i as u8, 0, 0));
+        }
+        let palette = Palette::new(&[big]);
+
+        assert_eq!(256, palette.colours.len());
+        assert_eq!(8, palette.bits);
+        assert_eq!(255, palette.index(Rgb(255, 0, 0)));
+        assert_eq!(255, palette.index(Rgb(255, 10, 0)));
+    }
+}
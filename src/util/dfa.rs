@@ -0,0 +1,683 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use std::str::FromStr;
+
+/// why a set of token patterns can't be compiled - `token` is the index of the pattern and
+/// `position` a 0-based char offset in it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternError {
+    Syntax {
+        token: usize,
+        position: usize,
+        reason: &'static str,
+    },
+    /// two ways through the same pattern put the same char into different groups - the automaton
+    /// couldn't tell which one is right
+    AmbiguousCapture { token: usize },
+    /// would match in between any two chars
+    MatchesEmpty { token: usize },
+}
+
+impl Display for PatternError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatternError::Syntax {
+                token,
+                position,
+                reason,
+            } => write!(f, "pattern {token}, column {}: {reason}", position + 1),
+            PatternError::AmbiguousCapture { token } => {
+                write!(f, "pattern {token} has ambiguous capture groups")
+            }
+            PatternError::MatchesEmpty { token } => {
+                write!(f, "pattern {token} matches the empty string")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Char(char),
+    Digit,
+    Concat(Vec<Node>),
+    Alt(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+    },
+    /// 1-based like in any regex
+    Group(usize, Box<Node>),
+}
+
+/// recursive descent over a tiny regex dialect:
+///
+/// ```text
+/// ALTERNATION := CONCAT ( "|" CONCAT )*
+/// CONCAT := REPEAT*
+/// REPEAT := ATOM ( "?" | "*" | "+" | "{" N "}" | "{" N "," "}" | "{" N "," M "}" )?
+/// ATOM := "(" ALTERNATION ")" | "\d" | "\" CHAR | CHAR
+/// ```
+struct Parser {
+    token: usize,
+    chars: Vec<char>,
+    position: usize,
+    groups: usize,
+}
+
+impl Parser {
+    fn parse(token: usize, pattern: &str) -> Result<(Node, usize), PatternError> {
+        let mut parser = Parser {
+            token,
+            chars: pattern.chars().collect(),
+            position: 0,
+            groups: 0,
+        };
+        let node = parser.alternation()?;
+        match parser.peek() {
+            None => Ok((node, parser.groups)),
+            Some(_) => Err(parser.error("unmatched ')'")),
+        }
+    }
+
+    fn error(&self, reason: &'static str) -> PatternError {
+        PatternError::Syntax {
+            token: self.token,
+            position: self.position,
+            reason,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.position += 1;
+        c
+    }
+
+    fn alternation(&mut self) -> Result<Node, PatternError> {
+        let mut alternatives = vec![self.concat()?];
+        while self.peek() == Some('|') {
+            self.position += 1;
+            alternatives.push(self.concat()?);
+        }
+        Ok(match alternatives.len() {
+            1 => alternatives.pop().unwrap(),
+            _ => Node::Alt(alternatives),
+        })
+    }
+
+    fn concat(&mut self) -> Result<Node, PatternError> {
+        let mut nodes = vec![];
+        while !matches!(self.peek(), None | Some('|' | ')')) {
+            nodes.push(self.repeat()?);
+        }
+        Ok(Node::Concat(nodes))
+    }
+
+    fn repeat(&mut self) -> Result<Node, PatternError> {
+        let start = self.position;
+        let node = self.atom()?;
+        let (min, max) = match self.peek() {
+            Some('{') => self.bounds()?,
+            Some(quantifier @ ('?' | '*' | '+')) => {
+                self.position += 1;
+                match quantifier {
+                    '?' => (0, Some(1)),
+                    '*' => (0, None),
+                    _ => (1, None),
+                }
+            }
+            _ => return Ok(node),
+        };
+        if let Node::Group(..) = node {
+            self.position = start;
+            return Err(self.error("groups can't be repeated"));
+        }
+        Ok(Node::Repeat {
+            node: Box::new(node),
+            min,
+            max,
+        })
+    }
+
+    fn bounds(&mut self) -> Result<(usize, Option<usize>), PatternError> {
+        self.position += 1;
+        let min = self.number()?;
+        let max = match self.peek() {
+            Some(',') => {
+                self.position += 1;
+                match self.peek() {
+                    Some('}') => None,
+                    _ => Some(self.number()?),
+                }
+            }
+            _ => Some(min),
+        };
+        if self.peek() != Some('}') {
+            return Err(self.error("expected '}'"));
+        }
+        if max.is_some_and(|max| max < min) {
+            return Err(self.error("the upper bound is below the lower one"));
+        }
+        self.position += 1;
+        Ok((min, max))
+    }
+
+    fn number(&mut self) -> Result<usize, PatternError> {
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.position += 1;
+        }
+        self.chars[start..self.position]
+            .iter()
+            .collect::<String>()
+            .parse()
+            .map_err(|_| self.error("expected a number"))
+    }
+
+    fn atom(&mut self) -> Result<Node, PatternError> {
+        match self.next() {
+            Some('(') => {
+                let open = self.position - 1;
+                self.groups += 1;
+                let index = self.groups;
+                let inner = self.alternation()?;
+                match self.next() {
+                    Some(')') => Ok(Node::Group(index, Box::new(inner))),
+                    _ => {
+                        self.position = open;
+                        Err(self.error("unclosed group"))
+                    }
+                }
+            }
+            Some('\\') => match self.next() {
+                Some('d') => Ok(Node::Digit),
+                Some(c) => Ok(Node::Char(c)),
+                None => Err(self.error("nothing to escape")),
+            },
+            Some('?' | '*' | '+' | '{' | '}') => {
+                self.position -= 1;
+                Err(self.error("nothing to repeat"))
+            }
+            Some(c) => Ok(Node::Char(c)),
+            None => Err(self.error("unexpected end")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Matcher {
+    Char(char),
+    Digit,
+}
+
+impl Matcher {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Matcher::Char(expected) => *expected == c,
+            Matcher::Digit => c.is_ascii_digit(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Edge {
+    matcher: Matcher,
+    to: usize,
+    /// capture slots the char ends up in
+    slots: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct NfaState {
+    token: usize,
+    epsilon: Vec<usize>,
+    edges: Vec<Edge>,
+    accepts: bool,
+}
+
+/// Thompson construction - one start state per token
+#[derive(Debug, Default)]
+struct Nfa {
+    states: Vec<NfaState>,
+}
+
+impl Nfa {
+    fn add(&mut self, token: usize) -> usize {
+        self.states.push(NfaState {
+            token,
+            ..NfaState::default()
+        });
+        self.states.len() - 1
+    }
+
+    fn epsilon(&mut self, from: usize, to: usize) {
+        self.states[from].epsilon.push(to);
+    }
+
+    /// the state after `node` - `slots` are the capture slots of the enclosing groups
+    fn build(
+        &mut self,
+        node: &Node,
+        from: usize,
+        first_slot: usize,
+        slots: &mut Vec<usize>,
+    ) -> usize {
+        let token = self.states[from].token;
+        match node {
+            Node::Char(_) | Node::Digit => {
+                let to = self.add(token);
+                let matcher = match node {
+                    Node::Char(c) => Matcher::Char(*c),
+                    _ => Matcher::Digit,
+                };
+                self.states[from].edges.push(Edge {
+                    matcher,
+                    to,
+                    slots: slots.clone(),
+                });
+                to
+            }
+            Node::Concat(nodes) => nodes
+                .iter()
+                .fold(from, |at, node| self.build(node, at, first_slot, slots)),
+            Node::Alt(alternatives) => {
+                let end = self.add(token);
+                for alternative in alternatives {
+                    let start = self.add(token);
+                    self.epsilon(from, start);
+                    let last = self.build(alternative, start, first_slot, slots);
+                    self.epsilon(last, end);
+                }
+                end
+            }
+            Node::Repeat { node, min, max } => {
+                let mut at = (0..*min).fold(from, |at, _| self.build(node, at, first_slot, slots));
+                match max {
+                    None => {
+                        let repeat = self.add(token);
+                        self.epsilon(at, repeat);
+                        let last = self.build(node, repeat, first_slot, slots);
+                        self.epsilon(last, repeat);
+                        repeat
+                    }
+                    Some(max) => {
+                        let end = self.add(token);
+                        for _ in *min..*max {
+                            self.epsilon(at, end);
+                            at = self.build(node, at, first_slot, slots);
+                        }
+                        self.epsilon(at, end);
+                        end
+                    }
+                }
+            }
+            Node::Group(index, inner) => {
+                slots.push(first_slot + index - 1);
+                let last = self.build(inner, from, first_slot, slots);
+                slots.pop();
+                last
+            }
+        }
+    }
+
+    fn closure(&self, states: impl IntoIterator<Item = usize>) -> BTreeSet<usize> {
+        let mut closure = BTreeSet::new();
+        let mut todo: Vec<usize> = states.into_iter().collect();
+        while let Some(state) = todo.pop() {
+            if closure.insert(state) {
+                todo.extend(&self.states[state].epsilon);
+            }
+        }
+        closure
+    }
+}
+
+fn literals(node: &Node, chars: &mut BTreeSet<char>) {
+    match node {
+        Node::Char(c) => {
+            chars.insert(*c);
+        }
+        Node::Digit => {}
+        Node::Concat(nodes) | Node::Alt(nodes) => nodes.iter().for_each(|n| literals(n, chars)),
+        Node::Repeat { node, .. } | Node::Group(_, node) => literals(node, chars),
+    }
+}
+
+const DEAD: usize = 0;
+const START: usize = 1;
+
+/// table driven automaton for a set of token patterns - compiled once, then it needs a single
+/// table lookup per char
+///
+/// patterns are a small regex dialect: literal chars (`\` escapes `(`, `)`, `|`, ...), `\d` for
+/// a digit, `?`, `*`, `+`, `{n}`, `{n,}` and `{n,m}` after a char or `\d`, alternation with `|`
+/// and capture groups in parentheses (which can't be repeated)
+#[derive(Debug, Clone)]
+pub struct Dfa {
+    /// char class of every ascii char - class 0 are chars no pattern mentions
+    ascii: [usize; 128],
+    others: HashMap<char, usize>,
+    classes: usize,
+    /// `state * classes + class` -> next state
+    transitions: Vec<usize>,
+    /// capture slots of every transition
+    tags: Vec<Vec<usize>>,
+    /// the pattern a state accepts - the first one if there are several
+    accepts: Vec<Option<usize>>,
+    /// first capture slot of every pattern - plus one past the last
+    slots: Vec<usize>,
+}
+
+/// one token found by [Dfa::matches] - `span` counts chars from the start of the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    /// index of the pattern
+    pub token: usize,
+    pub span: Range<usize>,
+    /// `captures[0]` is group 1 - `None` for groups in an alternative that didn't match
+    pub captures: Vec<Option<String>>,
+}
+
+impl Match {
+    /// 1-based like the groups in the pattern
+    pub fn capture(&self, group: usize) -> Option<&str> {
+        self.captures.get(group.checked_sub(1)?)?.as_deref()
+    }
+
+    pub fn number<T: FromStr>(&self, group: usize) -> Option<T> {
+        self.capture(group)?.parse().ok()
+    }
+}
+
+impl Dfa {
+    /// subset construction over the patterns' NFAs
+    pub fn compile(tokens: &[&str]) -> Result<Dfa, PatternError> {
+        let mut nfa = Nfa::default();
+        let mut starts = vec![];
+        let mut chars: BTreeSet<char> = ('0'..='9').collect();
+        let mut slots = vec![0];
+        for (token, pattern) in tokens.iter().enumerate() {
+            let (node, groups) = Parser::parse(token, pattern)?;
+            literals(&node, &mut chars);
+            let start = nfa.add(token);
+            let end = nfa.build(&node, start, slots[token], &mut vec![]);
+            nfa.states[end].accepts = true;
+            if nfa.closure([start]).contains(&end) {
+                return Err(PatternError::MatchesEmpty { token });
+            }
+            starts.push(start);
+            slots.push(slots[token] + groups);
+        }
+
+        let mut ascii = [0; 128];
+        let mut others = HashMap::new();
+        // class 0 has no representative - nothing matches it
+        let representatives: Vec<Option<char>> = [None]
+            .into_iter()
+            .chain(chars.iter().copied().map(Some))
+            .collect();
+        for (class, &c) in chars.iter().enumerate() {
+            match ascii.get_mut(c as usize) {
+                Some(entry) => *entry = class + 1,
+                None => {
+                    others.insert(c, class + 1);
+                }
+            }
+        }
+        let classes = representatives.len();
+
+        let mut sets = vec![BTreeSet::new(), nfa.closure(starts)];
+        let mut index: HashMap<BTreeSet<usize>, usize> = sets
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(i, set)| (set, i))
+            .collect();
+        let mut transitions = vec![DEAD; classes];
+        let mut tags = vec![vec![]; classes];
+        let mut accepts = vec![None];
+        let mut todo = VecDeque::from([START]);
+        while let Some(state) = todo.pop_front() {
+            accepts.push(
+                sets[state]
+                    .iter()
+                    .filter(|&&s| nfa.states[s].accepts)
+                    .map(|&s| nfa.states[s].token)
+                    .min(),
+            );
+            for representative in &representatives {
+                let mut targets = vec![];
+                // slots per token - the same token has to tag the same slots on every path
+                let mut tagged: BTreeMap<usize, &Vec<usize>> = BTreeMap::new();
+                for &s in &sets[state] {
+                    for edge in &nfa.states[s].edges {
+                        if !representative.is_some_and(|c| edge.matcher.matches(c)) {
+                            continue;
+                        }
+                        let token = nfa.states[s].token;
+                        if *tagged.entry(token).or_insert(&edge.slots) != &edge.slots {
+                            return Err(PatternError::AmbiguousCapture { token });
+                        }
+                        targets.push(edge.to);
+                    }
+                }
+
+                let target = nfa.closure(targets);
+                let next = match index.get(&target) {
+                    _ if target.is_empty() => DEAD,
+                    Some(&next) => next,
+                    None => {
+                        sets.push(target.clone());
+                        index.insert(target, sets.len() - 1);
+                        todo.push_back(sets.len() - 1);
+                        sets.len() - 1
+                    }
+                };
+                transitions.push(next);
+                tags.push(tagged.into_values().flatten().copied().collect());
+            }
+        }
+
+        Ok(Dfa {
+            ascii,
+            others,
+            classes,
+            transitions,
+            tags,
+            accepts,
+            slots,
+        })
+    }
+
+    /// including the dead state
+    pub fn states(&self) -> usize {
+        self.accepts.len()
+    }
+
+    fn class(&self, c: char) -> usize {
+        match self.ascii.get(c as usize) {
+            Some(&class) => class,
+            None => self.others.get(&c).copied().unwrap_or(0),
+        }
+    }
+
+    /// all non-overlapping tokens from left to right - the longest one wins if several start at
+    /// the same char, the first pattern if they're equally long
+    ///
+    /// chars that aren't part of any token are skipped - only the chars of the token that's
+    /// currently being matched are kept, so `input` can be arbitrarily long
+    pub fn matches<I: Iterator<Item = char>>(&self, input: I) -> Matches<'_, I> {
+        Matches {
+            dfa: self,
+            input,
+            buffer: VecDeque::new(),
+            offset: 0,
+            spans: vec![None; *self.slots.last().unwrap()],
+        }
+    }
+}
+
+/// where every capture slot is in the buffer
+type Spans = Vec<Option<Range<usize>>>;
+
+pub struct Matches<'a, I: Iterator<Item = char>> {
+    dfa: &'a Dfa,
+    input: I,
+    /// chars read but not consumed yet - `buffer[0]` is at `offset`
+    buffer: VecDeque<char>,
+    offset: usize,
+    spans: Spans,
+}
+
+impl<I: Iterator<Item = char>> Matches<'_, I> {
+    fn char_at(&mut self, i: usize) -> Option<char> {
+        while self.buffer.len() <= i {
+            self.buffer.push_back(self.input.next()?);
+        }
+        Some(self.buffer[i])
+    }
+
+    fn consume(&mut self, len: usize) {
+        self.buffer.drain(..len);
+        self.offset += len;
+    }
+
+    /// the longest token starting at `buffer[0]` - `(token, length, capture spans)`
+    fn longest(&mut self) -> Option<(usize, usize, Spans)> {
+        let dfa = self.dfa;
+        self.spans.fill(None);
+        let (mut state, mut i, mut longest) = (START, 0, None);
+        while let Some(c) = self.char_at(i) {
+            let transition = state * dfa.classes + dfa.class(c);
+            state = dfa.transitions[transition];
+            if state == DEAD {
+                break;
+            }
+            for &slot in &dfa.tags[transition] {
+                self.spans[slot].get_or_insert(i..i).end = i + 1;
+            }
+            i += 1;
+            if let Some(token) = dfa.accepts[state] {
+                let slots = dfa.slots[token]..dfa.slots[token + 1];
+                longest = Some((token, i, self.spans[slots].to_vec()));
+            }
+        }
+        longest
+    }
+}
+
+impl<I: Iterator<Item = char>> Iterator for Matches<'_, I> {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((token, len, spans)) = self.longest() {
+                let captures = spans
+                    .into_iter()
+                    .map(|span| Some(self.buffer.range(span?).collect()))
+                    .collect();
+                let span = self.offset..self.offset + len;
+                self.consume(len);
+                return Some(Match {
+                    token,
+                    span,
+                    captures,
+                });
+            }
+            self.char_at(0)?;
+            self.consume(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(dfa: &Dfa, input: &str) -> Vec<(usize, String)> {
+        dfa.matches(input.chars())
+            .map(|m| (m.token, input[m.span].to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn should_match_with_captures() {
+        let dfa = Dfa::compile(&[r"mul\((\d{1,3}),(\d{1,3})\)", r"do\(\)", r"don't\(\)"]).unwrap();
+
+        let matches: Vec<Match> = dfa
+            .matches("xmumul(2,40)don't(do()mul(1,2".chars())
+            .collect();
+
+        assert_eq!(2, matches.len());
+        assert_eq!((0, 3..12), (matches[0].token, matches[0].span.clone()));
+        assert_eq!(
+            (Some(2), Some(40)),
+            (matches[0].number::<u32>(1), matches[0].number(2))
+        );
+        assert_eq!(None, matches[0].capture(3));
+        assert_eq!((1, 18..22), (matches[1].token, matches[1].span.clone()));
+    }
+
+    #[test]
+    fn should_respect_length_bounds() {
+        let dfa = Dfa::compile(&[r"<\d{2,3}>", r"#\d*", r"a{2,}b?"]).unwrap();
+
+        assert_eq!(
+            vec![
+                (0, "<12>".to_string()),
+                (0, "<123>".to_string()),
+                (1, "#".to_string()),
+                (1, "#0042".to_string()),
+                (2, "aaab".to_string()),
+            ],
+            tokens(&dfa, "<1><12><123><1234>#a#0042aaab")
+        );
+    }
+
+    #[test]
+    fn should_prefer_longest_then_first() {
+        let dfa = Dfa::compile(&["ab", "a(b|c)d", "ab", r"(x)|(y)z"]).unwrap();
+
+        assert_eq!(
+            vec![
+                (1, "abd".to_string()),
+                (0, "ab".to_string()),
+                (1, "acd".to_string())
+            ],
+            tokens(&dfa, "abdabcacd")
+        );
+        let yz = dfa.matches("yz".chars()).next().unwrap();
+        assert_eq!(vec![None, Some("y".to_string())], yz.captures);
+    }
+
+    #[test]
+    fn should_reject_bad_patterns() {
+        let error = |pattern: &str| Dfa::compile(&["ok", pattern]).unwrap_err().to_string();
+
+        assert_eq!("pattern 1, column 1: unclosed group", error("(ab"));
+        assert_eq!("pattern 1, column 3: unmatched ')'", error("ab)"));
+        assert_eq!("pattern 1, column 1: nothing to repeat", error("*a"));
+        assert_eq!(
+            "pattern 1, column 1: groups can't be repeated",
+            error("(a)+")
+        );
+        assert_eq!(
+            "pattern 1, column 7: the upper bound is below the lower one",
+            error(r"\d{3,1}")
+        );
+        assert_eq!("pattern 1 matches the empty string", error("a?|b"));
+        assert_eq!(
+            "pattern 1 has ambiguous capture groups",
+            error(r"(\d)x|\dy")
+        );
+    }
+}
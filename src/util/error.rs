@@ -0,0 +1,110 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// malformed puzzle input - line and column are 1-based (like in any editor) and only known if the
+/// parser could tell where exactly things went wrong
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub reason: String,
+}
+
+impl ParseError {
+    pub fn new(day: u8, reason: impl Into<String>) -> Self {
+        ParseError {
+            day,
+            line: None,
+            column: None,
+            reason: reason.into(),
+        }
+    }
+
+    pub fn at(self, line: usize, column: usize) -> Self {
+        self.at_line(line).at_column(column)
+    }
+
+    /// parsers of single lines don't know where they are - the caller adds the line afterward
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn at_column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// points the column at `token` - which has to be a sub-slice of `line` (otherwise the
+    /// column stays unknown)
+    pub fn at_token(self, line: &str, token: &str) -> Self {
+        let offset = (token.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+        match line.get(..offset) {
+            Some(before) => self.at_column(before.chars().count() + 1),
+            None => self,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day {:02}", self.day)?;
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, " (line {line}, column {column})")?,
+            (Some(line), None) => write!(f, " (line {line})")?,
+            (None, Some(column)) => write!(f, " (column {column})")?,
+            (None, None) => {}
+        }
+        write!(f, ": {}", self.reason)
+    }
+}
+
+impl Error for ParseError {}
+
+/// parses a (trimmed) number token - the error names the token but has no position yet
+pub fn parse_number<T: FromStr>(day: u8, token: &str) -> Result<T, ParseError> {
+    token
+        .trim()
+        .parse::<T>()
+        .map_err(|_| ParseError::new(day, format!("'{token}' is not a valid number")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_display_position() {
+        let e = ParseError::new(16, "unexpected 'x'");
+        assert_eq!("day 16: unexpected 'x'", e.to_string());
+        assert_eq!("day 16 (line 3): unexpected 'x'", e.clone().at_line(3).to_string());
+        assert_eq!(
+            "day 16 (line 3, column 5): unexpected 'x'",
+            e.at(3, 5).to_string()
+        );
+    }
+
+    #[test]
+    fn should_point_at_token() {
+        let line = "p=0,4 v=3,x";
+        let token = &line[10..];
+
+        let e = ParseError::new(14, "NaN").at_token(line, token);
+        assert_eq!(Some(11), e.column);
+
+        let elsewhere = String::from("x");
+        let e = ParseError::new(14, "NaN").at_token(line, &elsewhere);
+        assert_eq!(None, e.column);
+    }
+
+    #[test]
+    fn should_parse_number() {
+        assert_eq!(Ok(42u8), parse_number(1, " 42"));
+        assert_eq!(
+            "day 01: '4x2' is not a valid number",
+            parse_number::<u8>(1, "4x2").unwrap_err().to_string()
+        );
+    }
+}
@@ -0,0 +1,379 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// undirected graph without self loops or parallel edges - node ids are interned to indices, so
+/// everything below works on plain `usize`s and sorted neighbor lists instead of references
+///
+/// indices are handed out in insertion order and stay valid (nodes are never removed)
+#[derive(Debug, Clone)]
+pub struct Graph<K> {
+    ids: Vec<K>,
+    index: HashMap<K, usize>,
+    adjacency: Vec<Vec<usize>>,
+}
+
+/// how connected the nodes of a [Graph] are
+#[derive(Debug, Clone, PartialEq)]
+pub struct DegreeStats {
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+    /// degree -> number of nodes with that degree
+    pub histogram: BTreeMap<usize, usize>,
+}
+
+impl DegreeStats {
+    /// every node has the same degree (e.g. the LAN of day 23)
+    pub fn is_regular(&self) -> bool {
+        self.min == self.max
+    }
+}
+
+impl<K: Hash + Eq + Clone> Default for Graph<K> {
+    fn default() -> Self {
+        Graph::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone> FromIterator<(K, K)> for Graph<K> {
+    fn from_iter<I: IntoIterator<Item = (K, K)>>(edges: I) -> Self {
+        let mut graph = Graph::new();
+        for (a, b) in edges {
+            graph.add_edge(a, b);
+        }
+        graph
+    }
+}
+
+impl<K: Hash + Eq + Clone> Graph<K> {
+    pub fn new() -> Self {
+        Graph {
+            ids: vec![],
+            index: HashMap::new(),
+            adjacency: vec![],
+        }
+    }
+
+    /// index of the node - added if it isn't known yet
+    pub fn add_node(&mut self, id: K) -> usize {
+        if let Some(&i) = self.index.get(&id) {
+            return i;
+        }
+        let i = self.ids.len();
+        self.index.insert(id.clone(), i);
+        self.ids.push(id);
+        self.adjacency.push(vec![]);
+        i
+    }
+
+    /// adds both nodes if necessary - edges that already exist and self loops are ignored
+    pub fn add_edge(&mut self, a: K, b: K) {
+        let (a, b) = (self.add_node(a), self.add_node(b));
+        if a == b {
+            return;
+        }
+        if let Err(pos) = self.adjacency[a].binary_search(&b) {
+            self.adjacency[a].insert(pos, b);
+            let pos = self.adjacency[b].binary_search(&a).unwrap_err();
+            self.adjacency[b].insert(pos, a);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.adjacency.iter().map(Vec::len).sum::<usize>() / 2
+    }
+
+    pub fn index_of(&self, id: &K) -> Option<usize> {
+        self.index.get(id).copied()
+    }
+
+    /// panics if there is no node with this index
+    pub fn id(&self, i: usize) -> &K {
+        &self.ids[i]
+    }
+
+    /// ids of the given nodes - handy to turn a clique back into names
+    pub fn ids_of<'a>(&'a self, nodes: &'a [usize]) -> impl Iterator<Item = &'a K> {
+        nodes.iter().map(|&i| self.id(i))
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = usize> {
+        0..self.len()
+    }
+
+    /// sorted ascending
+    pub fn neighbors(&self, i: usize) -> &[usize] {
+        &self.adjacency[i]
+    }
+
+    pub fn degree(&self, i: usize) -> usize {
+        self.adjacency[i].len()
+    }
+
+    pub fn is_connected(&self, a: usize, b: usize) -> bool {
+        self.adjacency[a].binary_search(&b).is_ok()
+    }
+
+    /// `None` for an empty graph
+    pub fn degree_stats(&self) -> Option<DegreeStats> {
+        let degrees: Vec<usize> = self.adjacency.iter().map(Vec::len).collect();
+        let mut histogram = BTreeMap::new();
+        for &d in &degrees {
+            *histogram.entry(d).or_insert(0) += 1;
+        }
+        Some(DegreeStats {
+            min: *degrees.iter().min()?,
+            max: *degrees.iter().max()?,
+            mean: degrees.iter().sum::<usize>() as f64 / degrees.len() as f64,
+            histogram,
+        })
+    }
+
+    /// each component sorted ascending - components ordered by their smallest node
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.len()];
+        let mut components = vec![];
+
+        for start in self.nodes() {
+            if seen[start] {
+                continue;
+            }
+            seen[start] = true;
+            let mut component = vec![start];
+            let mut todo = vec![start];
+            while let Some(node) = todo.pop() {
+                for &n in self.neighbors(node) {
+                    if !seen[n] {
+                        seen[n] = true;
+                        component.push(n);
+                        todo.push(n);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+
+    /// every complete sub graph with exactly `k` nodes - each one once, sorted ascending
+    pub fn k_cliques(&self, k: usize) -> Vec<Vec<usize>> {
+        let mut cliques = vec![];
+        if k == 0 {
+            return cliques;
+        }
+        for v in self.nodes() {
+            let later: Vec<usize> = self
+                .neighbors(v)
+                .iter()
+                .copied()
+                .filter(|&n| n > v)
+                .collect();
+            self.extend_k_clique(&mut vec![v], &later, k, &mut cliques);
+        }
+        cliques
+    }
+
+    fn extend_k_clique(
+        &self,
+        clique: &mut Vec<usize>,
+        candidates: &[usize],
+        k: usize,
+        cliques: &mut Vec<Vec<usize>>,
+    ) {
+        if clique.len() == k {
+            cliques.push(clique.clone());
+            return;
+        }
+        if clique.len() + candidates.len() < k {
+            return;
+        }
+        for (i, &v) in candidates.iter().enumerate() {
+            let next = intersect(&candidates[i + 1..], self.neighbors(v));
+            clique.push(v);
+            self.extend_k_clique(clique, &next, k, cliques);
+            clique.pop();
+        }
+    }
+
+    /// Bron–Kerbosch with pivoting - every clique that can't be extended by another node, each
+    /// sorted ascending
+    pub fn maximal_cliques(&self) -> Vec<Vec<usize>> {
+        let mut cliques = vec![];
+        self.bron_kerbosch(&mut vec![], self.nodes().collect(), vec![], &mut |clique| {
+            if !clique.is_empty() {
+                let mut clique = clique.to_vec();
+                clique.sort_unstable();
+                cliques.push(clique);
+            }
+        });
+        cliques
+    }
+
+    /// the biggest clique (the first one found if there are several) - sorted ascending
+    pub fn maximum_clique(&self) -> Vec<usize> {
+        let mut best: Vec<usize> = vec![];
+        self.bron_kerbosch(&mut vec![], self.nodes().collect(), vec![], &mut |clique| {
+            if clique.len() > best.len() {
+                best = clique.to_vec();
+            }
+        });
+        best.sort_unstable();
+        best
+    }
+
+    /// `clique` is the clique so far, `candidates` could still join it and `excluded` were already
+    /// tried - both sorted ascending
+    fn bron_kerbosch(
+        &self,
+        clique: &mut Vec<usize>,
+        mut candidates: Vec<usize>,
+        mut excluded: Vec<usize>,
+        report: &mut impl FnMut(&[usize]),
+    ) {
+        let Some(pivot) = candidates
+            .iter()
+            .chain(excluded.iter())
+            .copied()
+            .max_by_key(|&u| intersect(&candidates, self.neighbors(u)).len())
+        else {
+            report(clique);
+            return;
+        };
+
+        let tries: Vec<usize> = candidates
+            .iter()
+            .copied()
+            .filter(|&v| !self.is_connected(pivot, v))
+            .collect();
+        for v in tries {
+            clique.push(v);
+            self.bron_kerbosch(
+                clique,
+                intersect(&candidates, self.neighbors(v)),
+                intersect(&excluded, self.neighbors(v)),
+                report,
+            );
+            clique.pop();
+
+            candidates.retain(|&c| c != v);
+            let pos = excluded.binary_search(&v).unwrap_err();
+            excluded.insert(pos, v);
+        }
+    }
+}
+
+/// both sorted ascending
+fn intersect(a: &[usize], b: &[usize]) -> Vec<usize> {
+    let (mut i, mut j) = (0, 0);
+    let mut both = vec![];
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                both.push(a[i]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    both
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// two triangles sharing the edge b-c, one of them part of the 4-clique a-b-c-d, plus e-f
+    fn sample() -> Graph<&'static str> {
+        [
+            ("a", "b"),
+            ("a", "c"),
+            ("a", "d"),
+            ("b", "c"),
+            ("b", "d"),
+            ("c", "d"),
+            ("c", "x"),
+            ("b", "x"),
+            ("e", "f"),
+            ("b", "a"),
+            ("a", "a"),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    fn named(graph: &Graph<&str>, cliques: Vec<Vec<usize>>) -> Vec<String> {
+        let mut named: Vec<String> = cliques
+            .iter()
+            .map(|c| {
+                let mut ids: Vec<&str> = graph.ids_of(c).copied().collect();
+                ids.sort();
+                ids.concat()
+            })
+            .collect();
+        named.sort();
+        named
+    }
+
+    #[test]
+    fn should_ignore_duplicate_edges_and_self_loops() {
+        let graph = sample();
+
+        assert_eq!(7, graph.len());
+        assert_eq!(9, graph.edge_count());
+        assert_eq!(Some(0), graph.index_of(&"a"));
+        assert_eq!(&[1, 2, 3], graph.neighbors(0));
+    }
+
+    #[test]
+    fn should_find_k_cliques() {
+        let graph = sample();
+
+        assert_eq!(
+            vec!["abc", "abd", "acd", "bcd", "bcx"],
+            named(&graph, graph.k_cliques(3))
+        );
+        assert_eq!(vec!["abcd"], named(&graph, graph.k_cliques(4)));
+        assert!(graph.k_cliques(5).is_empty());
+    }
+
+    #[test]
+    fn should_find_maximal_and_maximum_cliques() {
+        let graph = sample();
+
+        assert_eq!(
+            vec!["abcd", "bcx", "ef"],
+            named(&graph, graph.maximal_cliques())
+        );
+        assert_eq!(vec!["abcd"], named(&graph, vec![graph.maximum_clique()]));
+        assert!(Graph::<u8>::new().maximal_cliques().is_empty());
+    }
+
+    #[test]
+    fn should_find_components() {
+        let graph = sample();
+
+        assert_eq!(vec![vec![0, 1, 2, 3, 4], vec![5, 6]], graph.components());
+    }
+
+    #[test]
+    fn should_give_degree_stats() {
+        let stats = sample().degree_stats().unwrap();
+
+        assert_eq!((1, 4), (stats.min, stats.max));
+        assert_eq!(18.0 / 7.0, stats.mean);
+        assert_eq!(Some(&2), stats.histogram.get(&1));
+        assert!(!stats.is_regular());
+        assert_eq!(None, Graph::<u8>::new().degree_stats());
+    }
+}
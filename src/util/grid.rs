@@ -1,9 +1,12 @@
 use std::collections::hash_map::IntoIter;
 use std::collections::HashMap;
+use std::iter::Enumerate;
+use std::vec;
 
-use crate::util::point::Point;
+use crate::util::point::{Point, Vec2};
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+/// compass directions - clockwise from north, with north being up (y decreases)
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum Direction {
     N,
     NE,
@@ -15,9 +18,159 @@ pub enum Direction {
     NW,
 }
 
+impl Direction {
+    /// clockwise, starting with north
+    pub const ALL: [Direction; 8] = [
+        Direction::N,
+        Direction::NE,
+        Direction::E,
+        Direction::SE,
+        Direction::S,
+        Direction::SW,
+        Direction::W,
+        Direction::NW,
+    ];
+
+    /// N, E, S, W
+    pub const CARDINAL: [Direction; 4] = [Direction::N, Direction::E, Direction::S, Direction::W];
+
+    /// NE, SE, SW, NW
+    pub const ORDINAL: [Direction; 4] = [Direction::NE, Direction::SE, Direction::SW, Direction::NW];
+
+    pub fn all() -> impl Iterator<Item = Direction> {
+        Direction::ALL.into_iter()
+    }
+
+    pub fn cardinal() -> impl Iterator<Item = Direction> {
+        Direction::CARDINAL.into_iter()
+    }
+
+    pub fn ordinal() -> impl Iterator<Item = Direction> {
+        Direction::ORDINAL.into_iter()
+    }
+
+    /// clockwise by `eighths` * 45° (counter-clockwise if negative)
+    pub fn rotate(&self, eighths: i32) -> Direction {
+        Direction::ALL[(*self as i32 + eighths).rem_euclid(8) as usize]
+    }
+
+    /// 90° clockwise
+    pub fn turn_right(&self) -> Direction {
+        self.rotate(2)
+    }
+
+    /// 90° counter-clockwise
+    pub fn turn_left(&self) -> Direction {
+        self.rotate(-2)
+    }
+
+    pub fn opposite(&self) -> Direction {
+        self.rotate(4)
+    }
+
+    /// N, E, S or W
+    pub fn is_cardinal(&self) -> bool {
+        matches!(
+            self,
+            Direction::N | Direction::E | Direction::S | Direction::W
+        )
+    }
+
+    /// one of the diagonals
+    pub fn is_ordinal(&self) -> bool {
+        !self.is_cardinal()
+    }
+
+    /// (dx, dy) of a single step
+    pub fn delta(&self) -> (i32, i32) {
+        match self {
+            Direction::N => (0, -1),
+            Direction::NE => (1, -1),
+            Direction::E => (1, 0),
+            Direction::SE => (1, 1),
+            Direction::S => (0, 1),
+            Direction::SW => (-1, 1),
+            Direction::W => (-1, 0),
+            Direction::NW => (-1, -1),
+        }
+    }
+
+    /// inverse of [Direction::delta] - `None` for anything that isn't a single step
+    pub fn from_delta(delta: (i32, i32)) -> Option<Direction> {
+        Direction::all().find(|d| d.delta() == delta)
+    }
+
+    /// the direction of the step from `from` to `to` - `None` if they aren't neighbors (8-way)
+    pub fn between(from: &Point, to: &Point) -> Option<Direction> {
+        Direction::from_delta((to.x - from.x, to.y - from.y))
+    }
+}
+
+/// sparse grid - only the cells that exist are stored, so it's fine for unbounded maps or maps
+/// with negative coordinates (see [DenseGrid] for the fast, rectangular one)
 #[derive(Debug, PartialEq)]
 pub struct Grid<T> {
     map: HashMap<Point, T>,
+    /// kept up to date by every change, so asking for the dimensions doesn't scan all cells
+    bounds: Option<(Point, Point)>,
+}
+
+/// cell access both grid storages have in common - algorithms written against this trait can
+/// switch between [Grid] and [DenseGrid] without changes
+pub trait GridAccess<T> {
+    fn get(&self, p: &Point) -> Option<&T>;
+
+    fn width(&self) -> usize;
+
+    fn height(&self) -> usize;
+
+    /// top-left and bottom-right corner - `None` if there is nothing to draw
+    fn bounds(&self) -> Option<(Point, Point)>;
+
+    /// within the bounds of the grid - the cell itself might still be empty
+    fn contains(&self, p: &Point) -> bool;
+
+    fn set(&mut self, p: Point, value: T);
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (Point, &'a T)>
+    where
+        T: 'a;
+
+    fn neighbors<'a>(&'a self, p: &Point) -> impl Iterator<Item = (Point, &'a T)> + use<'a, Self, T>
+    where
+        T: 'a,
+    {
+        [p.left(), p.right(), p.up(), p.down()]
+            .into_iter()
+            .filter_map(|n| self.get(&n).map(|c| (n, c)))
+    }
+
+    /// like [GridAccess::neighbors] but including diagonals
+    fn neighbors8<'a>(
+        &'a self,
+        p: &Point,
+    ) -> impl Iterator<Item = (Point, &'a T)> + use<'a, Self, T>
+    where
+        T: 'a,
+    {
+        self.neighbors_towards(p, Direction::ALL)
+            .map(|(_, n, c)| (n, c))
+    }
+
+    /// the neighbors in `directions`, tagged with the direction they are in
+    fn neighbors_towards<'a, D: IntoIterator<Item = Direction>>(
+        &'a self,
+        p: &Point,
+        directions: D,
+    ) -> impl Iterator<Item = (Direction, Point, &'a T)> + use<'a, Self, T, D>
+    where
+        T: 'a,
+    {
+        let p = *p;
+        directions
+            .into_iter()
+            .filter_map(move |d| self.get(&(p + d)).map(|c| (d, p + d, c)))
+    }
 }
 
 impl From<&str> for Grid<char> {
@@ -33,7 +186,7 @@ impl From<&str> for Grid<char> {
                 })
             })
             .collect();
-        Grid { map }
+        Grid::new(map)
     }
 }
 
@@ -48,11 +201,25 @@ impl<T> IntoIterator for Grid<T> {
 
 impl<T> Grid<T> {
     pub fn new(map: HashMap<Point, T>) -> Self {
-        Grid { map }
+        let bounds = map
+            .keys()
+            .fold(None, |bounds, p| Some(Self::extend(bounds, p)));
+        Grid { map, bounds }
+    }
+
+    /// the smallest rectangle around `bounds` and `p`
+    fn extend(bounds: Option<(Point, Point)>, p: &Point) -> (Point, Point) {
+        match bounds {
+            Some((min, max)) => (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            ),
+            None => (*p, *p),
+        }
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = (&Point, &T)> {
-        self.map.iter()
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.map.iter().map(|(p, t)| (*p, t))
     }
 
     pub fn get(&self, p: &Point) -> Option<&T> {
@@ -60,23 +227,17 @@ impl<T> Grid<T> {
     }
 
     pub fn set(&mut self, p: Point, value: T) {
+        self.bounds = Some(Self::extend(self.bounds, &p));
         self.map.insert(p, value);
     }
 
-    pub fn neighbors(&self, p: &Point) -> impl Iterator<Item = (Point, &T)> {
-        [p.left(), p.right(), p.up(), p.down()]
-            .into_iter()
-            .map(|n| (n, self.get(&n)))
-            .filter_map(|(n, c)| c.map(|inner_c| (n, inner_c)))
-    }
-
     pub fn map<F, U>(self, mut map_fn: F) -> Grid<U>
     where
         F: FnMut(T) -> U,
     {
         let next: HashMap<Point, U> = self.into_iter().map(|(p, t)| (p, map_fn(t))).collect();
 
-        Grid { map: next }
+        Grid::new(next)
     }
 
     pub fn filter_map<F, U>(self, mut map_fn: F) -> Grid<U>
@@ -88,7 +249,462 @@ impl<T> Grid<T> {
             .filter_map(|(p, t)| map_fn(t).map(|u| (p, u)))
             .collect();
 
-        Grid { map: next }
+        Grid::new(next)
+    }
+
+    /// top-left and bottom-right corner of the smallest rectangle around all cells - `None` if empty
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds()
+            .map_or(0, |(min, max)| (max.x - min.x + 1) as usize)
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds()
+            .map_or(0, |(min, max)| (max.y - min.y + 1) as usize)
+    }
+
+    /// within [Grid::bounds] - the cell itself might still be missing
+    pub fn contains(&self, p: &Point) -> bool {
+        self.bounds().is_some_and(|(min, max)| {
+            (min.x..=max.x).contains(&p.x) && (min.y..=max.y).contains(&p.y)
+        })
+    }
+
+    /// top to bottom - missing cells within the bounds are `None`
+    pub fn rows(&self) -> impl Iterator<Item = Vec<Option<&T>>> {
+        let (min, max) = self.bounds().unwrap_or_default();
+        let rows = if self.map.is_empty() {
+            0..0
+        } else {
+            min.y..max.y + 1
+        };
+        rows.map(move |y| {
+            (min.x..=max.x)
+                .map(|x| self.get(&Point::new(x, y)))
+                .collect()
+        })
+    }
+
+    /// left to right - missing cells within the bounds are `None`
+    pub fn columns(&self) -> impl Iterator<Item = Vec<Option<&T>>> {
+        let (min, max) = self.bounds().unwrap_or_default();
+        let columns = if self.map.is_empty() {
+            0..0
+        } else {
+            min.x..max.x + 1
+        };
+        columns.map(move |x| {
+            (min.y..=max.y)
+                .map(|y| self.get(&Point::new(x, y)))
+                .collect()
+        })
+    }
+
+    /// moves every cell - `remap` gets the position relative to the top-left corner plus the
+    /// width and height and returns the new relative position (the top-left corner stays put)
+    fn remap(self, remap: impl Fn(i32, i32, i32, i32) -> (i32, i32)) -> Self {
+        let Some((min, max)) = self.bounds() else {
+            return self;
+        };
+        let (width, height) = (max.x - min.x + 1, max.y - min.y + 1);
+        let map = self
+            .into_iter()
+            .map(|(p, t)| {
+                let (x, y) = remap(p.x - min.x, p.y - min.y, width, height);
+                (Point::new(min.x + x, min.y + y), t)
+            })
+            .collect();
+        Grid::new(map)
+    }
+
+    /// mirrors along the diagonal from the top-left corner - rows become columns
+    pub fn transpose(self) -> Self {
+        self.remap(|x, y, _, _| (y, x))
+    }
+
+    /// clockwise by 90 degrees
+    pub fn rotate_cw(self) -> Self {
+        self.remap(|x, y, _, height| (height - 1 - y, x))
+    }
+
+    /// counterclockwise by 90 degrees
+    pub fn rotate_ccw(self) -> Self {
+        self.remap(|x, y, width, _| (y, width - 1 - x))
+    }
+
+    /// left becomes right
+    pub fn flip_horizontal(self) -> Self {
+        self.remap(|x, y, width, _| (width - 1 - x, y))
+    }
+
+    /// top becomes bottom
+    pub fn flip_vertical(self) -> Self {
+        self.remap(|x, y, _, height| (x, height - 1 - y))
+    }
+
+    /// copy of the `width` x `height` rectangle starting at `top_left` - moved to (0, 0)
+    pub fn sub_grid(&self, top_left: Point, width: usize, height: usize) -> Self
+    where
+        T: Clone,
+    {
+        let map = self
+            .iter()
+            .filter(|(p, _)| {
+                (top_left.x..top_left.x + width as i32).contains(&p.x)
+                    && (top_left.y..top_left.y + height as i32).contains(&p.y)
+            })
+            .map(|(p, t)| (Point::from(p - top_left), t.clone()))
+            .collect();
+        Grid::new(map)
+    }
+}
+
+impl<T> GridAccess<T> for Grid<T> {
+    fn get(&self, p: &Point) -> Option<&T> {
+        Grid::get(self, p)
+    }
+
+    fn width(&self) -> usize {
+        Grid::width(self)
+    }
+
+    fn height(&self) -> usize {
+        Grid::height(self)
+    }
+
+    fn bounds(&self) -> Option<(Point, Point)> {
+        Grid::bounds(self)
+    }
+
+    fn contains(&self, p: &Point) -> bool {
+        Grid::contains(self, p)
+    }
+
+    fn set(&mut self, p: Point, value: T) {
+        Grid::set(self, p, value)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (Point, &'a T)>
+    where
+        T: 'a,
+    {
+        Grid::iter(self)
+    }
+}
+
+/// dense, rectangular grid from (0, 0) to (width - 1, height - 1) backed by a flat `Vec` - same api
+/// as [Grid] but a lot faster in hot loops
+///
+/// cells can still be empty (e.g. after [DenseGrid::filter_map]) - `get` treats them like cells
+/// outside of the grid
+#[derive(Debug, PartialEq, Clone)]
+pub struct DenseGrid<T> {
+    cells: Vec<Option<T>>,
+    width: usize,
+    height: usize,
+}
+
+impl From<&str> for DenseGrid<char> {
+    /// lines shorter than the longest one are padded with empty cells
+    fn from(value: &str) -> Self {
+        let width = value
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let height = value.lines().count();
+        let mut grid = DenseGrid::new(width, height);
+        for (y, line) in value.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                grid.cells[y * width + x] = Some(c);
+            }
+        }
+        grid
+    }
+}
+
+impl<T> IntoIterator for DenseGrid<T> {
+    type Item = (Point, T);
+    type IntoIter = DenseIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        DenseIntoIter {
+            cells: self.cells.into_iter().enumerate(),
+            width: self.width,
+        }
+    }
+}
+
+/// owning iterator over the non-empty cells of a [DenseGrid] (row by row)
+pub struct DenseIntoIter<T> {
+    cells: Enumerate<vec::IntoIter<Option<T>>>,
+    width: usize,
+}
+
+impl<T> Iterator for DenseIntoIter<T> {
+    type Item = (Point, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let width = self.width;
+        self.cells
+            .find_map(|(i, c)| c.map(|c| (DenseGrid::<T>::point_at(width, i), c)))
+    }
+}
+
+impl<T> DenseGrid<T> {
+    /// all cells empty
+    pub fn new(width: usize, height: usize) -> Self {
+        DenseGrid {
+            cells: (0..width * height).map(|_| None).collect(),
+            width,
+            height,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        DenseGrid {
+            cells: vec![Some(value); width * height],
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// (0, 0) to (width - 1, height - 1) - `None` for a grid without cells
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        if self.cells.is_empty() {
+            return None;
+        }
+        Some((
+            Point::new(0, 0),
+            Point::new(self.width as i32 - 1, self.height as i32 - 1),
+        ))
+    }
+
+    fn index(&self, p: &Point) -> Option<usize> {
+        let x = usize::try_from(p.x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(p.y).ok().filter(|&y| y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    fn point_at(width: usize, index: usize) -> Point {
+        Point::new((index % width) as i32, (index / width) as i32)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| c.as_ref().map(|c| (Self::point_at(self.width, i), c)))
+    }
+
+    pub fn get(&self, p: &Point) -> Option<&T> {
+        self.index(p).and_then(|i| self.cells[i].as_ref())
+    }
+
+    pub fn get_mut(&mut self, p: &Point) -> Option<&mut T> {
+        self.index(p).and_then(|i| self.cells[i].as_mut())
+    }
+
+    /// panics if `p` is outside the grid - a dense grid doesn't grow
+    pub fn set(&mut self, p: Point, value: T) {
+        let i = self
+            .index(&p)
+            .unwrap_or_else(|| panic!("{p} is outside of the {}x{} grid", self.width, self.height));
+        self.cells[i] = Some(value);
+    }
+
+    pub fn map<F, U>(self, mut map_fn: F) -> DenseGrid<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        DenseGrid {
+            cells: self.cells.into_iter().map(|c| c.map(&mut map_fn)).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// keeps the dimensions - filtered cells just become empty
+    pub fn filter_map<F, U>(self, mut map_fn: F) -> DenseGrid<U>
+    where
+        F: FnMut(T) -> Option<U>,
+    {
+        DenseGrid {
+            cells: self
+                .cells
+                .into_iter()
+                .map(|c| c.and_then(&mut map_fn))
+                .collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    pub fn contains(&self, p: &Point) -> bool {
+        self.index(p).is_some()
+    }
+
+    /// top to bottom - empty cells are `None`
+    pub fn rows(&self) -> impl Iterator<Item = Vec<Option<&T>>> {
+        // chunks panics on 0 - an empty grid has no rows anyway
+        self.cells
+            .chunks(self.width.max(1))
+            .map(|row| row.iter().map(Option::as_ref).collect())
+    }
+
+    /// left to right - empty cells are `None`
+    pub fn columns(&self) -> impl Iterator<Item = Vec<Option<&T>>> {
+        (0..self.width).map(move |x| {
+            (0..self.height)
+                .map(|y| self.cells[y * self.width + x].as_ref())
+                .collect()
+        })
+    }
+
+    /// moves every cell into a `width` x `height` grid - `remap` takes and returns (x, y)
+    fn remap(
+        self,
+        width: usize,
+        height: usize,
+        remap: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self {
+        let mut cells: Vec<Option<T>> = (0..width * height).map(|_| None).collect();
+        for (i, c) in self.cells.into_iter().enumerate() {
+            let (x, y) = remap(i % self.width, i / self.width);
+            cells[y * width + x] = c;
+        }
+        DenseGrid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// mirrors along the diagonal from the top-left corner - rows become columns
+    pub fn transpose(self) -> Self {
+        let (width, height) = (self.height, self.width);
+        self.remap(width, height, |x, y| (y, x))
+    }
+
+    /// clockwise by 90 degrees
+    pub fn rotate_cw(self) -> Self {
+        let (width, height) = (self.height, self.width);
+        self.remap(width, height, |x, y| (width - 1 - y, x))
+    }
+
+    /// counterclockwise by 90 degrees
+    pub fn rotate_ccw(self) -> Self {
+        let (width, height) = (self.height, self.width);
+        self.remap(width, height, |x, y| (y, height - 1 - x))
+    }
+
+    /// left becomes right
+    pub fn flip_horizontal(self) -> Self {
+        let (width, height) = (self.width, self.height);
+        self.remap(width, height, |x, y| (width - 1 - x, y))
+    }
+
+    /// top becomes bottom
+    pub fn flip_vertical(self) -> Self {
+        let (width, height) = (self.width, self.height);
+        self.remap(width, height, |x, y| (x, height - 1 - y))
+    }
+
+    /// copy of the `width` x `height` rectangle starting at `top_left` - parts outside of this grid
+    /// end up as empty cells
+    pub fn sub_grid(&self, top_left: Point, width: usize, height: usize) -> Self
+    where
+        T: Clone,
+    {
+        let mut grid = DenseGrid::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let p = top_left + Vec2::new(x as i32, y as i32);
+                grid.cells[y * width + x] = self.get(&p).cloned();
+            }
+        }
+        grid
+    }
+}
+
+impl<T> GridAccess<T> for DenseGrid<T> {
+    fn get(&self, p: &Point) -> Option<&T> {
+        DenseGrid::get(self, p)
+    }
+
+    fn width(&self) -> usize {
+        DenseGrid::width(self)
+    }
+
+    fn height(&self) -> usize {
+        DenseGrid::height(self)
+    }
+
+    fn bounds(&self) -> Option<(Point, Point)> {
+        DenseGrid::bounds(self)
+    }
+
+    fn contains(&self, p: &Point) -> bool {
+        DenseGrid::contains(self, p)
+    }
+
+    fn set(&mut self, p: Point, value: T) {
+        DenseGrid::set(self, p, value)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (Point, &'a T)>
+    where
+        T: 'a,
+    {
+        DenseGrid::iter(self)
+    }
+}
+
+impl<T> From<DenseGrid<T>> for Grid<T> {
+    fn from(value: DenseGrid<T>) -> Self {
+        Grid::new(value.into_iter().collect())
+    }
+}
+
+/// fails with the first point that can't be stored densely (negative coordinates)
+impl<T> TryFrom<Grid<T>> for DenseGrid<T> {
+    type Error = Point;
+
+    fn try_from(value: Grid<T>) -> Result<Self, Self::Error> {
+        if let Some((p, _)) = value.iter().find(|(p, _)| p.x < 0 || p.y < 0) {
+            return Err(p);
+        }
+        let width = value
+            .iter()
+            .map(|(p, _)| p.x as usize + 1)
+            .max()
+            .unwrap_or(0);
+        let height = value
+            .iter()
+            .map(|(p, _)| p.y as usize + 1)
+            .max()
+            .unwrap_or(0);
+
+        let mut grid = DenseGrid::new(width, height);
+        for (p, t) in value {
+            grid.set(p, t);
+        }
+        Ok(grid)
     }
 }
 
@@ -107,4 +723,204 @@ mod tests {
         let numeric: Grid<u8> = original.map(|v| v.to_string().parse::<u8>().unwrap());
         assert_eq!(36u8, numeric.map.iter().map(|(_, v)| v).sum::<u8>());
     }
+
+    #[test]
+    fn should_behave_like_sparse_grid() {
+        let chars = "
+#.#
+.A.
+#.."
+        .trim();
+        let sparse: Grid<char> = Grid::from(chars);
+        let dense: DenseGrid<char> = DenseGrid::from(chars);
+
+        assert_eq!((3, 3), (dense.width(), dense.height()));
+        for p in [
+            Point::new(1, 1),
+            Point::new(2, 2),
+            Point::new(-1, 0),
+            Point::new(3, 0),
+        ] {
+            assert_eq!(sparse.get(&p), dense.get(&p));
+            assert_eq!(
+                sparse.neighbors(&p).collect::<HashMap<_, _>>(),
+                dense.neighbors(&p).collect::<HashMap<_, _>>()
+            );
+        }
+
+        let sparse = sparse.filter_map(|c| if c == '#' { Some(1u8) } else { None });
+        let dense = dense.filter_map(|c| if c == '#' { Some(1u8) } else { None });
+        assert_eq!(3, dense.iter().count());
+        assert_eq!(sparse, Grid::from(dense));
+    }
+
+    #[test]
+    fn should_index_row_major() {
+        let mut dense = DenseGrid::filled(4, 2, 0);
+        dense.set(Point::new(3, 1), 7);
+        *dense.get_mut(&Point::new(1, 0)).unwrap() = 5;
+
+        assert_eq!(
+            vec![(Point::new(1, 0), 5), (Point::new(3, 1), 7)],
+            dense
+                .into_iter()
+                .filter(|(_, v)| *v != 0)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "(4, 0) is outside of the 4x2 grid")]
+    fn should_not_grow_dense_grid() {
+        DenseGrid::filled(4, 2, 0).set(Point::new(4, 0), 1);
+    }
+
+    const ABC: &str = "abc\ndef";
+
+    fn text<'a>(lines: impl Iterator<Item = Vec<Option<&'a char>>>) -> Vec<String> {
+        lines
+            .map(|line| line.into_iter().map(|c| *c.unwrap_or(&' ')).collect())
+            .collect()
+    }
+
+    #[test]
+    fn should_know_dimensions() {
+        let sparse = Grid::from(ABC);
+        let dense = DenseGrid::from(ABC);
+
+        assert_eq!((3, 2), (sparse.width(), sparse.height()));
+        assert_eq!((3, 2), (dense.width(), dense.height()));
+        assert!(sparse.contains(&Point::new(2, 1)) && dense.contains(&Point::new(2, 1)));
+        assert!(!sparse.contains(&Point::new(3, 1)) && !dense.contains(&Point::new(3, 1)));
+        assert!(!sparse.contains(&Point::new(0, -1)) && !dense.contains(&Point::new(0, -1)));
+
+        let empty: Grid<char> = Grid::new(HashMap::new());
+        assert_eq!((0, 0), (empty.width(), empty.height()));
+        assert_eq!(0, empty.rows().count());
+
+        let mut growing = empty;
+        growing.set(Point::new(-1, 2), 'a');
+        growing.set(Point::new(1, 0), 'b');
+        assert_eq!(Some((Point::new(-1, 0), Point::new(1, 2))), growing.bounds());
+        assert_eq!((3, 3), (growing.width(), growing.height()));
+    }
+
+    #[test]
+    fn should_iterate_rows_and_columns() {
+        let sparse = Grid::from(ABC);
+        let dense = DenseGrid::from(ABC);
+
+        assert_eq!(vec!["abc", "def"], text(sparse.rows()));
+        assert_eq!(vec!["abc", "def"], text(dense.rows()));
+        assert_eq!(vec!["ad", "be", "cf"], text(sparse.columns()));
+        assert_eq!(vec!["ad", "be", "cf"], text(dense.columns()));
+    }
+
+    #[test]
+    fn should_transform() {
+        fn check(
+            dense: fn(DenseGrid<char>) -> DenseGrid<char>,
+            sparse: fn(Grid<char>) -> Grid<char>,
+            expected: &str,
+        ) {
+            assert_eq!(DenseGrid::from(expected), dense(DenseGrid::from(ABC)));
+            assert_eq!(Grid::from(expected), sparse(Grid::from(ABC)));
+        }
+
+        check(DenseGrid::transpose, Grid::transpose, "ad\nbe\ncf");
+        check(DenseGrid::rotate_cw, Grid::rotate_cw, "da\neb\nfc");
+        check(DenseGrid::rotate_ccw, Grid::rotate_ccw, "cf\nbe\nad");
+        check(
+            DenseGrid::flip_horizontal,
+            Grid::flip_horizontal,
+            "cba\nfed",
+        );
+        check(DenseGrid::flip_vertical, Grid::flip_vertical, "def\nabc");
+
+        let dense = DenseGrid::from(ABC);
+        assert_eq!(
+            dense.clone(),
+            dense.rotate_cw().rotate_cw().rotate_ccw().rotate_ccw()
+        );
+    }
+
+    #[test]
+    fn should_slice_sub_grid() {
+        let sparse = Grid::from(ABC);
+        let dense = DenseGrid::from(ABC);
+
+        assert_eq!(Grid::from("ef"), sparse.sub_grid(Point::new(1, 1), 2, 1));
+        assert_eq!(
+            DenseGrid::from("ef"),
+            dense.sub_grid(Point::new(1, 1), 2, 1)
+        );
+        assert_eq!(
+            vec!["f ", "  "],
+            text(dense.sub_grid(Point::new(2, 1), 2, 2).rows())
+        );
+    }
+
+    #[test]
+    fn should_convert_between_storages() {
+        let mut sparse = Grid::new(HashMap::new());
+        sparse.set(Point::new(2, 1), 'x');
+
+        let dense = DenseGrid::try_from(sparse).unwrap();
+        assert_eq!((3, 2), (dense.width(), dense.height()));
+        assert_eq!(Some(&'x'), dense.get(&Point::new(2, 1)));
+        assert_eq!(None, dense.get(&Point::new(0, 0)));
+
+        let mut sparse = Grid::new(HashMap::new());
+        sparse.set(Point::new(-1, 0), 'x');
+        assert_eq!(Err(Point::new(-1, 0)), DenseGrid::try_from(sparse));
+    }
+
+    #[test]
+    fn should_rotate_directions() {
+        assert_eq!(Direction::E, Direction::N.turn_right());
+        assert_eq!(Direction::W, Direction::N.turn_left());
+        assert_eq!(Direction::SW, Direction::NE.opposite());
+        assert_eq!(Direction::N, Direction::NW.rotate(1));
+        assert_eq!(Direction::NW, Direction::N.rotate(-9));
+        assert!(Direction::all().all(|d| d.turn_right().turn_left() == d));
+        assert!(Direction::cardinal().all(|d| d.is_cardinal()));
+        assert!(Direction::ordinal().all(|d| d.is_ordinal()));
+    }
+
+    #[test]
+    fn should_convert_between_direction_and_delta() {
+        let origin = Point::new(3, 3);
+        for d in Direction::all() {
+            assert_eq!(Some(d), Direction::from_delta(d.delta()));
+            assert_eq!(Some(d), Direction::between(&origin, &(origin + d)));
+        }
+        assert_eq!(None, Direction::between(&origin, &Point::new(5, 3)));
+        assert_eq!(None, Direction::between(&origin, &origin));
+    }
+
+    #[test]
+    fn should_give_neighbors8() {
+        let chars = "
+abc
+def
+ghi"
+        .trim();
+        let sparse: Grid<char> = Grid::from(chars);
+        let dense: DenseGrid<char> = DenseGrid::from(chars);
+        let center = Point::new(1, 1);
+        let corner = Point::new(0, 0);
+
+        let all: String = sparse.neighbors8(&center).map(|(_, c)| c).collect();
+        assert_eq!("bcfihgda", all);
+        assert_eq!(3, dense.neighbors8(&corner).count());
+
+        let towards: Vec<(Direction, Point, &char)> = dense
+            .neighbors_towards(&corner, Direction::ORDINAL)
+            .collect();
+        assert_eq!(vec![(Direction::SE, center, &'e')], towards);
+        assert_eq!(
+            sparse.neighbors8(&corner).collect::<Vec<_>>(),
+            GridAccess::neighbors8(&dense, &corner).collect::<Vec<_>>()
+        );
+    }
 }
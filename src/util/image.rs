@@ -0,0 +1,385 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::util::grid::GridAccess;
+use crate::util::point::Point;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const RED: Rgb = Rgb(255, 0, 0);
+    pub const GREEN: Rgb = Rgb(0, 255, 0);
+    pub const BLUE: Rgb = Rgb(0, 0, 255);
+
+    /// a different, well saturated colour for every `i` (golden angle around the hue circle) -
+    /// for things like regions or ids that just need to be told apart
+    pub fn distinct(i: usize) -> Rgb {
+        let hue = (i as f64 * 137.508) % 360.0;
+        let x = 1.0 - ((hue / 60.0) % 2.0 - 1.0).abs();
+        let (r, g, b) = match (hue / 60.0) as u8 {
+            0 => (1.0, x, 0.0),
+            1 => (x, 1.0, 0.0),
+            2 => (0.0, 1.0, x),
+            3 => (0.0, x, 1.0),
+            4 => (x, 0.0, 1.0),
+            _ => (1.0, 0.0, x),
+        };
+        let channel = |c: f64| (55.0 + c * 200.0) as u8;
+        Rgb(channel(r), channel(g), channel(b))
+    }
+}
+
+/// rgb raster, row by row from the top-left - what all the writers below take
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// one pixel per cell within the bounds of the grid (sparse grids start at the top-left
+    /// corner of their bounds) - cells that are missing get the `empty` colour
+    pub fn from_grid<T>(
+        grid: &impl GridAccess<T>,
+        empty: Rgb,
+        colour: impl Fn(Point, &T) -> Rgb,
+    ) -> Self {
+        let Some((min, max)) = grid.bounds() else {
+            return Image::new(0, 0, empty);
+        };
+        let mut image = Image::new(
+            (max.x - min.x + 1) as usize,
+            (max.y - min.y + 1) as usize,
+            empty,
+        );
+        for (p, cell) in grid.iter() {
+            image.set(
+                (p.x - min.x) as usize,
+                (p.y - min.y) as usize,
+                colour(p, cell),
+            );
+        }
+        image
+    }
+
+    /// `foreground` for every point - points outside of `width` x `height` are left out
+    pub fn from_points<'a>(
+        points: impl IntoIterator<Item = &'a Point>,
+        width: usize,
+        height: usize,
+        background: Rgb,
+        foreground: Rgb,
+    ) -> Self {
+        let mut image = Image::new(width, height, background);
+        for p in points {
+            if let (Ok(x), Ok(y)) = (usize::try_from(p.x), usize::try_from(p.y)) {
+                if x < width && y < height {
+                    image.set(x, y, foreground);
+                }
+            }
+        }
+        image
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    /// panics if (x, y) is outside the image
+    pub fn set(&mut self, x: usize, y: usize, colour: Rgb) {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside of the {}x{} image",
+            self.width,
+            self.height
+        );
+        self.pixels[y * self.width + x] = colour;
+    }
+
+    /// every pixel becomes a `factor` x `factor` block - puzzle maps are tiny otherwise
+    pub fn scale(&self, factor: usize) -> Image {
+        let mut scaled = Image::new(self.width * factor, self.height * factor, Rgb::BLACK);
+        for y in 0..scaled.height {
+            for x in 0..scaled.width {
+                scaled.pixels[y * scaled.width + x] =
+                    self.pixels[(y / factor) * self.width + x / factor];
+            }
+        }
+        scaled
+    }
+
+    fn rows(&self) -> impl DoubleEndedIterator<Item = &[Rgb]> {
+        // `chunks` panics on 0 - an image without width has no rows anyway
+        self.pixels.chunks(self.width.max(1))
+    }
+
+    /// binary portable pixmap (P6)
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        for Rgb(r, g, b) in &self.pixels {
+            bytes.extend([*r, *g, *b]);
+        }
+        bytes
+    }
+
+    /// 24 bit uncompressed bitmap
+    pub fn to_bmp(&self) -> Vec<u8> {
+        const HEADER_SIZE: usize = 14 + 40; // file header + BITMAPINFOHEADER
+
+        // rows are padded to a multiple of 4 bytes
+        let padding = (4 - (self.width * 3) % 4) % 4;
+        let pixel_array_size = (self.width * 3 + padding) * self.height;
+
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + pixel_array_size);
+        bytes.extend(b"BM");
+        bytes.extend(((HEADER_SIZE + pixel_array_size) as u32).to_le_bytes());
+        bytes.extend(0u32.to_le_bytes()); // reserved
+        bytes.extend((HEADER_SIZE as u32).to_le_bytes()); // offset of the pixel data
+
+        bytes.extend(40u32.to_le_bytes()); // size of this header
+        bytes.extend((self.width as i32).to_le_bytes());
+        bytes.extend((self.height as i32).to_le_bytes()); // positive - rows are stored bottom-up
+        bytes.extend(1u16.to_le_bytes()); // planes
+        bytes.extend(24u16.to_le_bytes()); // bits per pixel
+        bytes.extend(0u32.to_le_bytes()); // no compression
+        bytes.extend((pixel_array_size as u32).to_le_bytes());
+        bytes.extend([0u8; 16]); // resolution (x, y) and palette (used, important) - all defaults
+
+        if self.width > 0 {
+            for row in self.rows().rev() {
+                for Rgb(r, g, b) in row {
+                    bytes.extend([*b, *g, *r]);
+                }
+                bytes.extend(vec![0; padding]);
+            }
+        }
+        bytes
+    }
+
+    /// truecolour png - the image data is zlib "compressed" with stored (uncompressed) deflate
+    /// blocks, so it's as big as a bitmap but needs no compression code or crate
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+        if self.width > 0 {
+            for row in self.rows() {
+                raw.push(0); // filter type: none
+                for Rgb(r, g, b) in row {
+                    raw.extend([*r, *g, *b]);
+                }
+            }
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // bit depth 8, colour type 2 (rgb), default compression, filter and no interlace
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut bytes, b"IHDR", &header);
+        png_chunk(&mut bytes, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut bytes, b"IEND", &[]);
+        bytes
+    }
+
+    /// the format is picked by the extension (`ppm`, `bmp` or `png`) - missing directories are created
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let bytes = match path.extension().and_then(|e| e.to_str()) {
+            Some("ppm") => self.to_ppm(),
+            Some("bmp") => self.to_bmp(),
+            Some("png") => self.to_png(),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{} - expected .ppm, .bmp or .png", path.display()),
+                ))
+            }
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, bytes)
+    }
+}
+
+fn png_chunk(bytes: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    bytes.extend((data.len() as u32).to_be_bytes());
+    let start = bytes.len();
+    bytes.extend(kind);
+    bytes.extend(data);
+    let crc = crc32(&bytes[start..]);
+    bytes.extend(crc.to_be_bytes());
+}
+
+/// zlib stream of stored deflate blocks (max 65535 bytes each)
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 65535;
+    let mut bytes = vec![0x78, 0x01]; // deflate, 32k window, no dictionary, fastest
+    let blocks: Vec<&[u8]> = if data.is_empty() {
+        vec![&[]]
+    } else {
+        data.chunks(MAX_BLOCK).collect()
+    };
+    for (i, block) in blocks.iter().enumerate() {
+        let last = i + 1 == blocks.len();
+        bytes.push(last as u8); // BFINAL - BTYPE 00 (stored)
+        bytes.extend((block.len() as u16).to_le_bytes());
+        bytes.extend((!(block.len() as u16)).to_le_bytes());
+        bytes.extend(*block);
+    }
+    bytes.extend(adler32(data).to_be_bytes());
+    bytes
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &byte| {
+        (0..8).fold(crc ^ byte as u32, |crc, _| {
+            if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % MOD;
+        (a, (b + a) % MOD)
+    });
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::grid::DenseGrid;
+
+    fn checkerboard() -> Image {
+        let grid = DenseGrid::from("#.\n.#");
+        Image::from_grid(&grid, Rgb::BLACK, |_, &c| {
+            if c == '#' {
+                Rgb::WHITE
+            } else {
+                Rgb::RED
+            }
+        })
+    }
+
+    #[test]
+    fn should_checksum() {
+        assert_eq!(0xCBF4_3926, crc32(b"123456789"));
+        assert_eq!(0x11E6_0398, adler32(b"Wikipedia"));
+    }
+
+    #[test]
+    fn should_map_grid_and_points() {
+        let image = checkerboard();
+        assert_eq!(Some(Rgb::WHITE), image.get(0, 0));
+        assert_eq!(Some(Rgb::RED), image.get(1, 0));
+        assert_eq!(None, image.get(2, 0));
+
+        let points = [Point::new(1, 0), Point::new(5, 5), Point::new(-1, 0)];
+        let image = Image::from_points(&points, 2, 1, Rgb::BLACK, Rgb::GREEN);
+        assert_eq!(vec![Rgb::BLACK, Rgb::GREEN], image.pixels);
+    }
+
+    #[test]
+    fn should_scale() {
+        let scaled = checkerboard().scale(3);
+
+        assert_eq!((6, 6), (scaled.width(), scaled.height()));
+        assert_eq!(Some(Rgb::WHITE), scaled.get(2, 2));
+        assert_eq!(Some(Rgb::RED), scaled.get(3, 2));
+        assert_eq!(Some(Rgb::WHITE), scaled.get(5, 5));
+    }
+
+    #[test]
+    fn should_write_ppm() {
+        let ppm = checkerboard().to_ppm();
+
+        assert_eq!(b"P6\n2 2\n255\n", &ppm[..11]);
+        assert_eq!(&[255, 255, 255, 255, 0, 0], &ppm[11..17]);
+        assert_eq!(11 + 4 * 3, ppm.len());
+    }
+
+    #[test]
+    fn should_write_bmp_bottom_up_with_padding() {
+        let bmp = checkerboard().to_bmp();
+
+        // 2 pixels * 3 bytes + 2 bytes padding per row
+        assert_eq!(54 + 2 * 8, bmp.len());
+        assert_eq!(
+            bmp.len() as u32,
+            u32::from_le_bytes(bmp[2..6].try_into().unwrap())
+        );
+        // bottom row first - red then white, in BGR
+        assert_eq!(&[0, 0, 255, 255, 255, 255, 0, 0], &bmp[54..62]);
+    }
+
+    #[test]
+    fn should_write_png() {
+        let png = checkerboard().to_png();
+
+        assert_eq!(b"\x89PNG\r\n\x1a\n", &png[..8]);
+        assert_eq!(b"IHDR", &png[12..16]);
+        // IEND is empty and always has the same crc
+        assert_eq!(
+            &[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82],
+            &png[png.len() - 12..]
+        );
+
+        let raw = [0, 255, 255, 255, 255, 0, 0, 0, 255, 0, 0, 255, 255, 255];
+        let idat = zlib_stored(&raw);
+        assert!(png.windows(idat.len()).any(|w| w == idat.as_slice()));
+    }
+
+    #[test]
+    fn should_split_big_images_into_several_blocks() {
+        let data = vec![7u8; 70_000];
+        let zlib = zlib_stored(&data);
+
+        // header + 2 block headers + data + adler
+        assert_eq!(2 + 2 * 5 + 70_000 + 4, zlib.len());
+        assert_eq!(0, zlib[2]);
+        assert_eq!(1, zlib[2 + 5 + 65535]);
+    }
+
+    #[test]
+    fn should_refuse_unknown_format() {
+        let e = checkerboard().save("./tmp/image.gif").unwrap_err();
+
+        assert_eq!(io::ErrorKind::InvalidInput, e.kind());
+    }
+
+    #[test]
+    fn should_tell_indices_apart() {
+        let colours: std::collections::HashSet<Rgb> = (0..50).map(Rgb::distinct).collect();
+
+        assert_eq!(50, colours.len());
+    }
+}
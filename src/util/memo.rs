@@ -0,0 +1,325 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+/// how often a memo table could answer right away - to see whether caching pays off at all
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    /// entries thrown out because the table was full
+    pub evictions: u64,
+}
+
+impl Stats {
+    /// `0.0` before the first lookup
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+impl std::ops::Add for Stats {
+    type Output = Stats;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Stats {
+            hits: self.hits + rhs.hits,
+            misses: self.misses + rhs.misses,
+            evictions: self.evictions + rhs.evictions,
+        }
+    }
+}
+
+/// owned memo table for recursive functions - create one per solve and pass it down instead of
+/// hiding it in a global static
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    values: HashMap<K, (V, u64)>,
+    /// last use -> key, only kept up to date with a capacity
+    usage: BTreeMap<u64, K>,
+    capacity: Option<usize>,
+    clock: u64,
+    stats: Stats,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            values: HashMap::new(),
+            usage: BTreeMap::new(),
+            capacity: None,
+            clock: 0,
+            stats: Stats::default(),
+        }
+    }
+
+    /// keeps at most `capacity` entries - the least recently used one has to go first
+    pub fn bounded(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "a memo without any space can't remember anything"
+        );
+        Memo {
+            capacity: Some(capacity),
+            ..Memo::new()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// counts as a hit or a miss
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let tick = self.tick();
+        match self.values.get_mut(key) {
+            Some((value, last_used)) => {
+                self.stats.hits += 1;
+                if self.capacity.is_some() {
+                    let key = self
+                        .usage
+                        .remove(last_used)
+                        .expect("every entry has a usage");
+                    self.usage.insert(tick, key);
+                    *last_used = tick;
+                }
+                Some(value.clone())
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        let tick = self.tick();
+        let Some(capacity) = self.capacity else {
+            self.values.insert(key, (value, tick));
+            return;
+        };
+
+        if let Some((_, last_used)) = self.values.insert(key.clone(), (value, tick)) {
+            self.usage.remove(&last_used);
+        }
+        self.usage.insert(tick, key);
+        while self.values.len() > capacity {
+            let (_, oldest) = self.usage.pop_first().expect("more entries than capacity");
+            self.values.remove(&oldest);
+            self.stats.evictions += 1;
+        }
+    }
+
+    /// the remembered value or `f`'s result (which gets remembered) - `f` gets the memo back, so
+    /// it can recurse
+    pub fn compute(&mut self, key: K, f: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+        let value = f(self);
+        self.insert(key, value.clone());
+        value
+    }
+
+    /// forgets all values but keeps the statistics
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.usage.clear();
+    }
+
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+}
+
+/// [Memo] for several threads - keys are spread over independently locked shards, so threads
+/// rarely wait on each other
+///
+/// values are computed without holding a lock, so two threads might compute the same key at the
+/// same time (which is fine for pure functions)
+#[derive(Debug)]
+pub struct ShardedMemo<K, V> {
+    shards: Vec<Mutex<Memo<K, V>>>,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Default for ShardedMemo<K, V> {
+    fn default() -> Self {
+        ShardedMemo::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> ShardedMemo<K, V> {
+    /// one shard per thread is too few - keys cluster, so use a bunch more
+    const SHARDS_PER_THREAD: usize = 4;
+
+    pub fn new() -> Self {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        ShardedMemo::with_shards(threads * Self::SHARDS_PER_THREAD, Memo::new)
+    }
+
+    /// every shard keeps at most `capacity / shards` entries (but at least one)
+    pub fn bounded(capacity: usize, shards: usize) -> Self {
+        assert!(shards > 0, "need at least one shard");
+        let per_shard = (capacity / shards).max(1);
+        ShardedMemo::with_shards(shards, || Memo::bounded(per_shard))
+    }
+
+    fn with_shards(shards: usize, memo: impl Fn() -> Memo<K, V>) -> Self {
+        assert!(shards > 0, "need at least one shard");
+        ShardedMemo {
+            shards: (0..shards).map(|_| Mutex::new(memo())).collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| lock(shard).len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// summed up over all shards
+    pub fn stats(&self) -> Stats {
+        self.shards
+            .iter()
+            .map(|shard| lock(shard).stats())
+            .fold(Stats::default(), |a, b| a + b)
+    }
+
+    pub fn get(&self, key: &K) -> Option<V> {
+        lock(self.shard(key)).get(key)
+    }
+
+    pub fn insert(&self, key: K, value: V) {
+        lock(self.shard(&key)).insert(key, value)
+    }
+
+    /// like [Memo::compute] - but with a shared reference, so it works from rayon closures
+    pub fn compute(&self, key: K, f: impl FnOnce(&Self) -> V) -> V {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+        let value = f(self);
+        self.insert(key, value.clone());
+        value
+    }
+
+    fn shard(&self, key: &K) -> &Mutex<Memo<K, V>> {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+}
+
+/// a panic while computing never happens with the lock held, so a poisoned shard is still fine
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rayon::prelude::*;
+
+    fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        memo.compute(n, |memo| match n {
+            0 | 1 => n,
+            n => fib(memo, n - 1) + fib(memo, n - 2),
+        })
+    }
+
+    #[test]
+    fn should_memoize_recursion() {
+        let mut memo = Memo::new();
+
+        assert_eq!(12586269025, fib(&mut memo, 50));
+        assert_eq!(51, memo.len());
+        // every n > 1 asks for n - 2 once it's already known
+        assert_eq!(
+            Stats {
+                hits: 48,
+                misses: 51,
+                evictions: 0
+            },
+            memo.stats()
+        );
+    }
+
+    #[test]
+    fn should_evict_least_recently_used() {
+        let mut memo = Memo::bounded(2);
+        memo.insert('a', 1);
+        memo.insert('b', 2);
+
+        assert_eq!(Some(1), memo.get(&'a'));
+        memo.insert('c', 3);
+
+        assert_eq!(None, memo.get(&'b'));
+        assert_eq!(Some(1), memo.get(&'a'));
+        assert_eq!(Some(3), memo.get(&'c'));
+        assert_eq!(2, memo.len());
+        assert_eq!(1, memo.stats().evictions);
+        // still correct with far too little space - just slower
+        assert_eq!(12586269025, fib(&mut Memo::bounded(3), 50));
+    }
+
+    #[test]
+    fn should_share_between_threads() {
+        fn collatz(memo: &ShardedMemo<u64, u32>, n: u64) -> u32 {
+            memo.compute(n, |memo| match n {
+                1 => 0,
+                n if n % 2 == 0 => 1 + collatz(memo, n / 2),
+                n => 1 + collatz(memo, 3 * n + 1),
+            })
+        }
+        let memo = ShardedMemo::new();
+
+        let longest = (1..10_000u64)
+            .into_par_iter()
+            .map(|n| collatz(&memo, n))
+            .max();
+
+        assert_eq!(Some(261), longest);
+        assert!(memo.stats().hits > 0);
+        // keys computed by two threads at once count as two misses but make only one entry
+        assert!(memo.len() as u64 <= memo.stats().misses);
+    }
+
+    #[test]
+    fn should_bound_shards() {
+        let memo = ShardedMemo::bounded(8, 4);
+
+        (0..100).for_each(|i| memo.insert(i, i));
+
+        assert_eq!(8, memo.len());
+        assert_eq!(92, memo.stats().evictions);
+    }
+
+    #[test]
+    #[should_panic(expected = "need at least one shard")]
+    fn should_not_shard_into_nothing() {
+        ShardedMemo::<u8, u8>::bounded(10, 0);
+    }
+}
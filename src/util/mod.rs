@@ -1,6 +1,19 @@
 pub mod algebra;
+pub mod animation;
+pub mod dfa;
+pub mod error;
 pub mod file;
+pub mod graph;
 pub mod grid;
+pub mod image;
+pub mod memo;
+pub mod number;
+pub mod parse;
+pub mod playback;
 pub mod point;
+pub mod random;
+pub mod render;
+pub mod search;
 pub mod stringify;
 pub mod timer;
+pub mod word_search;
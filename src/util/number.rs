@@ -0,0 +1,256 @@
+/// always >= 0 (and 0 only for `gcd(0, 0)`)
+pub fn gcd(a: i128, b: i128) -> i128 {
+    ext_gcd(a, b).0
+}
+
+/// `(g, x, y)` with `a * x + b * y = g = gcd(a, b)`
+pub fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r.div_euclid(r);
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `None` if it doesn't fit into an `i128` - `lcm(0, n)` is 0
+pub fn lcm(a: i128, b: i128) -> Option<i128> {
+    match gcd(a, b) {
+        0 => Some(0),
+        g => (a / g).checked_mul(b).map(i128::abs),
+    }
+}
+
+/// lcm of all numbers, 1 for none at all - e.g. when all cycles line up again
+pub fn lcm_of(numbers: impl IntoIterator<Item = i128>) -> Option<i128> {
+    numbers.into_iter().try_fold(1, lcm)
+}
+
+/// `x` in `0..m` with `a * x = 1 (mod m)` - `None` if `a` and `m` share a factor
+pub fn mod_inv(a: i128, m: i128) -> Option<i128> {
+    assert!(m > 0, "modulus has to be positive");
+    let (g, x, _) = ext_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// all `x` with `a * x = b (mod m)` as `(residue, modulus)` - `None` if there are none
+pub fn solve_linear_congruence(a: i128, b: i128, m: i128) -> Option<(i128, i128)> {
+    assert!(m > 0, "modulus has to be positive");
+    let g = gcd(a.rem_euclid(m), m);
+    if b.rem_euclid(g) != 0 {
+        return None;
+    }
+    let m = m / g;
+    let inverse = mod_inv(a / g, m).expect("coprime after dividing by the gcd");
+    Some((mul_mod((b / g).rem_euclid(m), inverse, m), m))
+}
+
+/// `a * b mod m` for `a, b` in `0..m` - falls back to double and add if the product doesn't fit
+fn mul_mod(a: i128, b: i128, m: i128) -> i128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+    // both below 2^127, so the sums fit into an u128
+    let (mut a, mut b, m) = (a as u128, b as u128, m as u128);
+    let mut result = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = (result + a) % m;
+        }
+        a = (a + a) % m;
+        b >>= 1;
+    }
+    result as i128
+}
+
+/// `base^exp mod m` in `0..m` by squaring
+pub fn mod_pow(base: i128, mut exp: u128, m: i128) -> i128 {
+    assert!(m > 0, "modulus has to be positive");
+    let mut base = base.rem_euclid(m);
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Chinese Remainder Theorem - all `x = r (mod m)` at once, returned as the smallest `(r, m)`
+/// (with `m` being the lcm of all moduli)
+///
+/// the moduli don't have to be coprime - `None` if the congruences contradict each other,
+/// panics if the lcm of the moduli doesn't fit into an `i128`
+pub fn crt(congruences: impl IntoIterator<Item = (i128, i128)>) -> Option<(i128, i128)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(r1, m1), (r2, m2)| {
+            assert!(m2 > 0, "modulus has to be positive");
+            let r2 = r2.rem_euclid(m2);
+            let (g, p, _) = ext_gcd(m1, m2);
+            if (r2 - r1).rem_euclid(g) != 0 {
+                return None;
+            }
+            let m = lcm(m1, m2).expect("attempt to combine moduli with overflow");
+            // x = r1 + m1 * k with m1 * k = r2 - r1 (mod m2) - everything below stays below m
+            let n = m2 / g;
+            let k = mul_mod(((r2 - r1) / g).rem_euclid(n), p.rem_euclid(n), n);
+            Some(((r1 + m1 * k).rem_euclid(m), m))
+        })
+}
+
+/// `x, f(x), f(f(x)), ...` runs into a loop after `start` steps and then repeats every `length`
+/// steps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// the first step that looks exactly like step `n` - jump there instead of simulating `n` steps
+    pub fn earliest_equivalent(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// tortoise and hare - only ever keeps two values around
+pub fn floyd<T: Clone + PartialEq>(x0: T, f: impl Fn(T) -> T) -> Cycle {
+    let mut tortoise = f(x0.clone());
+    let mut hare = f(tortoise.clone());
+    while tortoise != hare {
+        tortoise = f(tortoise);
+        hare = f(f(hare));
+    }
+
+    let mut start = 0;
+    tortoise = x0;
+    while tortoise != hare {
+        tortoise = f(tortoise);
+        hare = f(hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = f(tortoise.clone());
+    while tortoise != hare {
+        hare = f(hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// like [floyd] but with fewer calls to `f` - the hare teleports instead of running twice as fast
+pub fn brent<T: Clone + PartialEq>(x0: T, f: impl Fn(T) -> T) -> Cycle {
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise = x0.clone();
+    let mut hare = f(x0.clone());
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(hare);
+        length += 1;
+    }
+
+    let mut tortoise = x0.clone();
+    let mut hare = x0;
+    for _ in 0..length {
+        hare = f(hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(tortoise);
+        hare = f(hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// `f` applied `n` times - finds the cycle first, so `n` can be way bigger than the cycle
+pub fn nth_iterate<T: Clone + PartialEq>(x0: T, f: impl Fn(T) -> T, n: usize) -> T {
+    let cycle = brent(x0.clone(), &f);
+    (0..cycle.earliest_equivalent(n)).fold(x0, |x, _| f(x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_calculate_gcd_and_lcm() {
+        assert_eq!((6, -1, 1), ext_gcd(12, 18));
+        assert_eq!(1, gcd(-3, -2));
+        assert_eq!(0, gcd(0, 0));
+        assert_eq!(Some(36), lcm(12, -18));
+        assert_eq!(Some(2520), lcm_of(1..=10));
+        assert_eq!(None, lcm_of([i128::MAX, i128::MAX - 1]));
+    }
+
+    #[test]
+    fn should_invert_and_power() {
+        assert_eq!(Some(4), mod_inv(3, 11));
+        assert_eq!(Some(7), mod_inv(-3, 11));
+        assert_eq!(None, mod_inv(6, 9));
+        assert_eq!(Some((2, 3)), solve_linear_congruence(6, 3, 9));
+        assert_eq!(None, solve_linear_congruence(6, 4, 9));
+        assert_eq!(445, mod_pow(4, 13, 497));
+        assert_eq!(1, mod_pow(3, 1_000_000_006, 1_000_000_007));
+        assert_eq!(0, mod_pow(5, 0, 1));
+        // the squares don't fit into an i128 anymore
+        assert_eq!(i128::MAX - 1, mod_pow(-1, 3, i128::MAX));
+        assert_eq!(1, mod_pow(2, 127, i128::MAX));
+    }
+
+    #[test]
+    fn should_solve_crt() {
+        assert_eq!(Some((23, 105)), crt([(2, 3), (3, 5), (2, 7)]));
+        // not coprime, but compatible
+        assert_eq!(Some((10, 12)), crt([(4, 6), (2, 4)]));
+        assert_eq!(None, crt([(1, 6), (2, 4)]));
+        assert_eq!(Some((0, 1)), crt([]));
+    }
+
+    #[test]
+    fn should_find_cycles() {
+        // 0 1 2 3 4 5 6 7 8 -> 3 -> ... (tail of 3, loop of 6)
+        let f = |x: u32| if x == 8 { 3 } else { x + 1 };
+        let expected = Cycle {
+            start: 3,
+            length: 6,
+        };
+
+        assert_eq!(expected, floyd(0, f));
+        assert_eq!(expected, brent(0, f));
+        assert_eq!(
+            Cycle {
+                start: 0,
+                length: 1
+            },
+            brent(7, |x: u32| x)
+        );
+        assert_eq!(
+            3 + (1_000_000 - 3) % 6,
+            expected.earliest_equivalent(1_000_000)
+        );
+        assert_eq!(4, nth_iterate(0, f, 1_000_000_000_000));
+    }
+}
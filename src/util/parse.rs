@@ -0,0 +1,335 @@
+use std::str::FromStr;
+
+use crate::util::error::{parse_number, ParseError};
+
+/// one line of the input (without the line break) that knows where it came from - so every error
+/// it creates already has the right line and column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub day: u8,
+    /// 1-based
+    pub number: usize,
+    pub text: &'a str,
+}
+
+/// consecutive lines of the input, e.g. the map before the blank line and the moves after it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    pub day: u8,
+    /// 1-based number of the section's first line
+    pub first_line: usize,
+    pub text: &'a str,
+}
+
+/// `label: value` lines in any order, like `Register A: 729` - other lines are ignored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fields<'a> {
+    day: u8,
+    separator: &'a str,
+    fields: Vec<(&'a str, Line<'a>, &'a str)>,
+}
+
+/// all lines of the input, numbered
+pub fn lines(day: u8, input: &str) -> impl Iterator<Item = Line<'_>> {
+    Section {
+        day,
+        first_line: 1,
+        text: input,
+    }
+    .lines()
+}
+
+/// the blocks of non-blank lines - any number of blank lines separate them and leading or trailing
+/// ones are dropped
+pub fn sections(day: u8, input: &str) -> Vec<Section<'_>> {
+    let mut sections = Vec::new();
+    let mut current: Option<(usize, usize, usize)> = None;
+    for (number, start, line, _) in numbered_lines(input) {
+        match (&mut current, line.trim().is_empty()) {
+            (None, true) => {}
+            (Some(_), true) => sections.extend(current.take()),
+            (None, false) => current = Some((number, start, start + line.len())),
+            (Some((_, _, end)), false) => *end = start + line.len(),
+        }
+    }
+    sections.extend(current);
+
+    sections
+        .into_iter()
+        .map(|(first_line, start, end)| Section {
+            day,
+            first_line,
+            text: &input[start..end],
+        })
+        .collect()
+}
+
+/// everything before the first blank line and everything after it (which might be empty) - `None`
+/// without a blank line
+pub fn split_at_blank_line(day: u8, input: &str) -> Option<(Section<'_>, Section<'_>)> {
+    let (number, start, _, next) =
+        numbered_lines(input).find(|(_, _, line, _)| line.trim().is_empty())?;
+    let before = Section {
+        day,
+        first_line: 1,
+        text: input[..start].trim_end_matches(['\n', '\r']),
+    };
+    let after = Section {
+        day,
+        first_line: number + 1,
+        text: &input[next..],
+    };
+    Some((before, after))
+}
+
+/// `(number, start, line, start of the next line)` - with the line break cut off like
+/// [str::lines] does
+fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, usize, &str, usize)> {
+    let mut next = 0;
+    input
+        .split_inclusive('\n')
+        .enumerate()
+        .map(move |(i, raw)| {
+            let start = next;
+            next += raw.len();
+            let line = raw.strip_suffix('\n').unwrap_or(raw);
+            (i + 1, start, line.strip_suffix('\r').unwrap_or(line), next)
+        })
+}
+
+impl<'a> Section<'a> {
+    pub fn lines(&self) -> impl Iterator<Item = Line<'a>> {
+        let (day, first_line) = (self.day, self.first_line);
+        self.text.lines().enumerate().map(move |(i, text)| Line {
+            day,
+            number: first_line + i,
+            text,
+        })
+    }
+
+    /// positioned at the first line of the section
+    pub fn error(&self, reason: impl Into<String>) -> ParseError {
+        ParseError::new(self.day, reason).at_line(self.first_line)
+    }
+}
+
+impl<'a> Line<'a> {
+    pub fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    pub fn error(&self, reason: impl Into<String>) -> ParseError {
+        ParseError::new(self.day, reason).at_line(self.number)
+    }
+
+    /// `token` has to be a sub-slice of the line - see [ParseError::at_token]
+    pub fn error_at(&self, token: &str, reason: impl Into<String>) -> ParseError {
+        self.error(reason).at_token(self.text, token)
+    }
+
+    /// `token` (a sub-slice of the line) as a number - also signed ones like `-42`
+    pub fn number<T: FromStr>(&self, token: &str) -> Result<T, ParseError> {
+        parse_number(self.day, token).map_err(|e| e.at_token(self.text, token).at_line(self.number))
+    }
+
+    /// all numbers in `token` split by any of the `separators` - empty pieces are skipped, so
+    /// `&[' ']` handles runs of spaces too
+    pub fn numbers<T: FromStr>(
+        &self,
+        token: &'a str,
+        separators: &[char],
+    ) -> Result<Vec<T>, ParseError> {
+        token
+            .split(separators)
+            .filter(|piece| !piece.trim().is_empty())
+            .map(|piece| self.number(piece))
+            .collect()
+    }
+
+    /// what follows `label` (leading whitespace of the line ignored) - `None` for other lines
+    pub fn label(&self, label: &str) -> Option<&'a str> {
+        self.text.trim_start().strip_prefix(label).map(str::trim)
+    }
+
+    /// `key` and `value` around the first `separator`, both trimmed
+    pub fn key_value(&self, separator: &str) -> Result<(&'a str, &'a str), ParseError> {
+        self.text
+            .split_once(separator)
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .ok_or_else(|| self.error(format!("'{}' should contain '{separator}'", self.text)))
+    }
+
+    /// scanf for lines - `"Button A: X+{}, Y+{}"` returns the two (trimmed) tokens in place of the
+    /// `{}`, everything else has to match exactly (apart from whitespace around the line)
+    ///
+    /// a `{}` reaches up to the first occurrence of the text after it - panics if `pattern`
+    /// doesn't contain `N` placeholders or two placeholders aren't separated by anything
+    pub fn scan<const N: usize>(&self, pattern: &str) -> Result<[&'a str; N], ParseError> {
+        let literals: Vec<&str> = pattern.split("{}").collect();
+        assert_eq!(N + 1, literals.len(), "'{pattern}' needs {N} placeholders");
+
+        let mut tokens = [""; N];
+        let mut rest = self.text.trim();
+        let missing =
+            |rest: &str, literal: &str| self.error_at(rest, format!("expected '{literal}'"));
+        rest = rest
+            .strip_prefix(literals[0])
+            .ok_or_else(|| missing(rest, literals[0]))?;
+
+        for (i, literal) in literals[1..].iter().enumerate() {
+            let end = match *literal {
+                "" if i + 1 == N => rest.len(),
+                "" => panic!("'{pattern}' has placeholders right next to each other"),
+                literal => rest
+                    .find(literal)
+                    .ok_or_else(|| missing(&rest[rest.len()..], literal))?,
+            };
+            tokens[i] = rest[..end].trim();
+            rest = &rest[end + literal.len()..];
+        }
+
+        match rest {
+            "" => Ok(tokens),
+            rest => Err(self.error_at(rest, format!("unexpected '{rest}'"))),
+        }
+    }
+}
+
+impl<'a> Fields<'a> {
+    /// every line with a `separator` is a field - later lines win if a label shows up twice
+    pub fn new(day: u8, input: &'a str, separator: &'a str) -> Self {
+        let fields = lines(day, input)
+            .filter_map(|line| {
+                let (label, value) = line.key_value(separator).ok()?;
+                Some((label, line, value))
+            })
+            .collect();
+        Fields {
+            day,
+            separator,
+            fields,
+        }
+    }
+
+    /// the value and the line it's on
+    pub fn get(&self, label: &str) -> Result<(Line<'a>, &'a str), ParseError> {
+        self.fields
+            .iter()
+            .rev()
+            .find(|(l, _, _)| *l == label)
+            .map(|&(_, line, value)| (line, value))
+            .ok_or_else(|| {
+                let separator = self.separator.trim();
+                ParseError::new(self.day, format!("missing '{label}{separator}'"))
+            })
+    }
+
+    pub fn number<T: FromStr>(&self, label: &str) -> Result<T, ParseError> {
+        let (line, value) = self.get(label)?;
+        line.number(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_split_sections() {
+        let input = "\n  \na\nb\n\n\r\nc\r\n\n";
+
+        let sections = sections(1, input);
+
+        assert_eq!(
+            vec![(3, "a\nb"), (7, "c")],
+            sections
+                .iter()
+                .map(|s| (s.first_line, s.text))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(3, "a"), (4, "b")],
+            sections[0]
+                .lines()
+                .map(|l| (l.number, l.text))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn should_split_at_first_blank_line() {
+        let (before, after) = split_at_blank_line(1, "a\nb\n\nc\n\nd").unwrap();
+
+        assert_eq!((1, "a\nb"), (before.first_line, before.text));
+        assert_eq!((4, "c\n\nd"), (after.first_line, after.text));
+        assert_eq!("", split_at_blank_line(1, "a\n\n").unwrap().1.text);
+        assert_eq!(None, split_at_blank_line(1, "a\nb"));
+    }
+
+    #[test]
+    fn should_scan_line() {
+        let line = lines(13, "x\n  Prize: X=-8400, Y=5400  ").nth(1).unwrap();
+
+        let [x, y] = line.scan("Prize: X={}, Y={}").unwrap();
+
+        assert_eq!(
+            (-8400, 5400),
+            (
+                line.number::<i32>(x).unwrap(),
+                line.number::<i32>(y).unwrap()
+            )
+        );
+        assert_eq!(
+            "day 13 (line 2, column 3): expected 'Button A: X+'",
+            line.scan::<2>("Button A: X+{}, Y+{}")
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "day 13 (line 2, column 25): expected ', Z='",
+            line.scan::<2>("Prize: X={}, Z={}").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "day 13 (line 2, column 23): unexpected '00'",
+            line.scan::<1>("Prize: X={}, Y=54").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn should_parse_numbers_and_labels() {
+        let line = lines(5, "Program: 0, 1,5  4").next().unwrap();
+
+        let program = line.label("Program:").unwrap();
+
+        assert_eq!(
+            vec![0u8, 1, 5, 4],
+            line.numbers(program, &[',', ' ']).unwrap()
+        );
+        assert_eq!(None, line.label("Register A:"));
+
+        let line = lines(5, "Program: 0,x").next().unwrap();
+        let e = line
+            .numbers::<u8>(line.label("Program:").unwrap(), &[','])
+            .unwrap_err();
+        assert_eq!(
+            "day 05 (line 1, column 12): 'x' is not a valid number",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn should_look_up_fields() {
+        let fields = Fields::new(17, "Register A: 729\n\nRegister B: x\nProgram: 0,1", ":");
+
+        assert_eq!(Ok(729u32), fields.number("Register A"));
+        assert_eq!("0,1", fields.get("Program").unwrap().1);
+        assert_eq!(
+            "day 17 (line 3, column 13): 'x' is not a valid number",
+            fields.number::<u32>("Register B").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "day 17: missing 'Register C:'",
+            fields.number::<u32>("Register C").unwrap_err().to_string()
+        );
+    }
+}
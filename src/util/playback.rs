@@ -0,0 +1,461 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const CURSOR_HOME: &str = "\x1b[H";
+const CLEAR_LINE_END: &str = "\x1b[K";
+const CLEAR_SCREEN_END: &str = "\x1b[J";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+const ANSI_RESET: &str = "\x1b[0m";
+
+const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+const MIN_DELAY: Duration = Duration::from_millis(10);
+const MAX_DELAY: Duration = Duration::from_millis(2000);
+
+/// the basic ansi colours (plus grey) - anything fancier isn't worth it for a map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Grey,
+}
+
+impl Colour {
+    fn code(&self) -> &'static str {
+        match self {
+            Colour::Black => "\x1b[30m",
+            Colour::Red => "\x1b[1;31m",
+            Colour::Green => "\x1b[32m",
+            Colour::Yellow => "\x1b[33m",
+            Colour::Blue => "\x1b[34m",
+            Colour::Magenta => "\x1b[35m",
+            Colour::Cyan => "\x1b[36m",
+            Colour::White => "\x1b[37m",
+            Colour::Grey => "\x1b[90m",
+        }
+    }
+}
+
+/// colour per cell kind - frames are plain text (e.g. from a [Renderer](crate::util::render::Renderer)),
+/// every char with a colour gets painted, everything else stays as it is
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CellColours(HashMap<char, Colour>);
+
+impl CellColours {
+    pub fn new() -> Self {
+        CellColours::default()
+    }
+
+    pub fn with(mut self, cell: char, colour: Colour) -> Self {
+        self.0.insert(cell, colour);
+        self
+    }
+
+    pub fn paint(&self, text: &str) -> String {
+        let mut painted = String::with_capacity(text.len());
+        for c in text.chars() {
+            match self.0.get(&c) {
+                Some(colour) => {
+                    painted.push_str(colour.code());
+                    painted.push(c);
+                    painted.push_str(ANSI_RESET);
+                }
+                None => painted.push(c),
+            }
+        }
+        painted
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    /// space
+    TogglePlay,
+    /// `l` or arrow right - pauses
+    Forward,
+    /// `h` or arrow left - pauses
+    Back,
+    /// `+` or arrow up
+    Faster,
+    /// `-` or arrow down
+    Slower,
+    /// `g`
+    First,
+    /// `G`
+    Last,
+    /// `q`, escape or ctrl-c
+    Quit,
+}
+
+/// keys as they come from a terminal in raw mode - unknown bytes are skipped
+pub fn parse_keys(bytes: &[u8]) -> Vec<Key> {
+    let mut keys = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let key = match &bytes[i..] {
+            [0x1b, b'[', arrow, ..] => {
+                i += 2;
+                match arrow {
+                    b'A' => Some(Key::Faster),
+                    b'B' => Some(Key::Slower),
+                    b'C' => Some(Key::Forward),
+                    b'D' => Some(Key::Back),
+                    _ => None,
+                }
+            }
+            [b' ', ..] => Some(Key::TogglePlay),
+            [b'l', ..] => Some(Key::Forward),
+            [b'h', ..] => Some(Key::Back),
+            [b'+' | b'=', ..] => Some(Key::Faster),
+            [b'-', ..] => Some(Key::Slower),
+            [b'g', ..] => Some(Key::First),
+            [b'G', ..] => Some(Key::Last),
+            [b'q' | 0x1b | 0x03, ..] => Some(Key::Quit),
+            _ => None,
+        };
+        keys.extend(key);
+        i += 1;
+    }
+    keys
+}
+
+/// what happened while waiting for a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    Key(Key),
+    /// the frame delay passed without a key
+    Tick,
+    /// there won't be any more keys
+    Closed,
+}
+
+pub trait Keys {
+    /// waits at most `timeout` for a key - `None` waits until there is one
+    fn next(&mut self, timeout: Option<Duration>) -> Input;
+}
+
+/// canned input for running a [Playback] headless - never actually waits and is [Input::Closed]
+/// once the script is through
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scripted(VecDeque<Input>);
+
+impl Scripted {
+    pub fn new(inputs: impl IntoIterator<Item = Input>) -> Self {
+        Scripted(inputs.into_iter().collect())
+    }
+
+    /// keys as typed (see [parse_keys]) - without any ticks in between
+    pub fn keys(typed: &str) -> Self {
+        Scripted::new(parse_keys(typed.as_bytes()).into_iter().map(Input::Key))
+    }
+}
+
+impl Keys for Scripted {
+    fn next(&mut self, _: Option<Duration>) -> Input {
+        self.0.pop_front().unwrap_or(Input::Closed)
+    }
+}
+
+/// the keyboard - puts the terminal into raw mode (via `stty`, so unix only) until dropped
+pub struct TerminalKeys {
+    keys: mpsc::Receiver<Key>,
+    saved_mode: String,
+}
+
+impl TerminalKeys {
+    pub fn open() -> io::Result<Self> {
+        let saved_mode = stty(&["-g"])?;
+        stty(&["raw", "-echo"])?;
+
+        // not stdin - that might be the puzzle input
+        let mut tty = File::open("/dev/tty")?;
+        let (sender, keys) = mpsc::channel();
+        thread::spawn(move || {
+            let mut buffer = [0; 16];
+            while let Ok(n @ 1..) = tty.read(&mut buffer) {
+                for key in parse_keys(&buffer[..n]) {
+                    if sender.send(key).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        Ok(TerminalKeys { keys, saved_mode })
+    }
+}
+
+impl Drop for TerminalKeys {
+    fn drop(&mut self) {
+        let _ = stty(&[self.saved_mode.trim()]);
+    }
+}
+
+impl Keys for TerminalKeys {
+    fn next(&mut self, timeout: Option<Duration>) -> Input {
+        let key = match timeout {
+            None => self.keys.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            Some(timeout) => self.keys.recv_timeout(timeout),
+        };
+        match key {
+            Ok(key) => Input::Key(key),
+            Err(RecvTimeoutError::Timeout) => Input::Tick,
+            Err(RecvTimeoutError::Disconnected) => Input::Closed,
+        }
+    }
+}
+
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(File::open("/dev/tty")?)
+        .stderr(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!("stty {} failed", args.join(" "))));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// steps through a recorded simulation in a terminal - frames are rendered on demand, so the
+/// states can be something small (an iteration, an index into a path) instead of whole grids
+///
+/// space plays/pauses, arrows (or `h`/`l`) step, up/down (or `+`/`-`) change the speed, `g`/`G`
+/// jump to the first/last frame and `q` quits
+pub struct Playback {
+    frames: usize,
+    render: Box<dyn Fn(usize) -> String>,
+    colours: CellColours,
+    position: usize,
+    playing: bool,
+    delay: Duration,
+}
+
+impl Playback {
+    /// `render` gets the index of the frame (`0..frames`)
+    pub fn new(frames: usize, render: impl Fn(usize) -> String + 'static) -> Self {
+        Playback {
+            frames,
+            render: Box::new(render),
+            colours: CellColours::new(),
+            position: 0,
+            playing: false,
+            delay: DEFAULT_DELAY,
+        }
+    }
+
+    pub fn from_states<S: 'static>(
+        states: Vec<S>,
+        render: impl Fn(&S) -> String + 'static,
+    ) -> Self {
+        Playback::new(states.len(), move |i| render(&states[i]))
+    }
+
+    pub fn colours(self, colours: CellColours) -> Self {
+        Playback { colours, ..self }
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames == 0
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// the current frame without colours
+    pub fn frame(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        (self.render)(self.position)
+    }
+
+    /// until quit or the keys run out - playing stops at the last frame
+    pub fn run(&mut self, keys: &mut impl Keys, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "{CLEAR_SCREEN}{HIDE_CURSOR}")?;
+        loop {
+            self.draw(out)?;
+            match keys.next(self.playing.then_some(self.delay)) {
+                Input::Key(Key::Quit) | Input::Closed => break,
+                Input::Key(key) => self.apply(key),
+                Input::Tick => self.tick(),
+            }
+        }
+        write!(out, "{SHOW_CURSOR}")?;
+        out.flush()
+    }
+
+    /// [Playback::run] on the actual terminal
+    pub fn watch(&mut self) -> io::Result<()> {
+        let mut keys = TerminalKeys::open()?;
+        self.run(&mut keys, &mut io::stdout().lock())
+    }
+
+    fn last(&self) -> usize {
+        self.frames.saturating_sub(1)
+    }
+
+    fn apply(&mut self, key: Key) {
+        match key {
+            Key::TogglePlay => self.playing = !self.playing && self.position < self.last(),
+            Key::Forward => {
+                self.playing = false;
+                self.position = (self.position + 1).min(self.last());
+            }
+            Key::Back => {
+                self.playing = false;
+                self.position = self.position.saturating_sub(1);
+            }
+            Key::Faster => self.delay = (self.delay / 2).max(MIN_DELAY),
+            Key::Slower => self.delay = (self.delay * 2).min(MAX_DELAY),
+            Key::First => self.position = 0,
+            Key::Last => self.position = self.last(),
+            Key::Quit => {}
+        }
+    }
+
+    fn tick(&mut self) {
+        if self.playing {
+            self.position = (self.position + 1).min(self.last());
+            self.playing = self.position < self.last();
+        }
+    }
+
+    /// redraws from the top left corner instead of scrolling - `\r\n` as the terminal is in raw
+    /// mode
+    fn draw(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "{CURSOR_HOME}")?;
+        for line in self.colours.paint(&self.frame()).lines() {
+            write!(out, "{line}{CLEAR_LINE_END}\r\n")?;
+        }
+        let state = if self.playing { "playing" } else { "paused" };
+        write!(
+            out,
+            "frame {}/{} - {state} - {}ms per frame{CLEAR_LINE_END}\r\n",
+            (self.position + 1).min(self.frames),
+            self.frames,
+            self.delay.as_millis()
+        )?;
+        write!(
+            out,
+            "[space] play/pause [h/l] step [+/-] speed [g/G] first/last [q] quit{CLEAR_SCREEN_END}"
+        )?;
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counter(frames: usize) -> Playback {
+        Playback::from_states((0..frames).collect(), |i: &usize| format!("#{i}"))
+    }
+
+    fn run(playback: &mut Playback, script: Scripted) -> String {
+        let mut out = vec![];
+        playback.run(&mut script.clone(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn should_parse_keys() {
+        assert_eq!(
+            vec![
+                Key::TogglePlay,
+                Key::Forward,
+                Key::Back,
+                Key::Faster,
+                Key::Slower,
+                Key::Last,
+                Key::Quit
+            ],
+            parse_keys(b" \x1b[C\x1b[Dx+\x1b[BGq")
+        );
+        assert_eq!(vec![Key::Quit], parse_keys(b"\x1b"));
+    }
+
+    #[test]
+    fn should_play_until_last_frame() {
+        let mut playback = counter(3);
+
+        run(
+            &mut playback,
+            Scripted::new([
+                Input::Key(Key::TogglePlay),
+                Input::Tick,
+                Input::Tick,
+                Input::Tick,
+            ]),
+        );
+
+        assert_eq!(2, playback.position());
+        assert!(!playback.is_playing());
+        assert_eq!("#2", playback.frame());
+    }
+
+    #[test]
+    fn should_step_back_and_forth() {
+        let mut playback = counter(5);
+
+        run(&mut playback, Scripted::keys("lll h l"));
+        assert_eq!(3, playback.position());
+        assert!(!playback.is_playing());
+
+        run(&mut playback, Scripted::keys("hhhhhh"));
+        assert_eq!(0, playback.position());
+
+        run(&mut playback, Scripted::keys("G g "));
+        assert_eq!(0, playback.position());
+        assert!(playback.is_playing());
+    }
+
+    #[test]
+    fn should_change_speed() {
+        let mut playback = counter(1);
+
+        run(&mut playback, Scripted::keys("++++++++"));
+        assert_eq!(MIN_DELAY, playback.delay());
+
+        run(&mut playback, Scripted::keys("--"));
+        assert_eq!(Duration::from_millis(40), playback.delay());
+    }
+
+    #[test]
+    fn should_redraw_in_place_with_colours() {
+        let mut playback = Playback::from_states(vec!["#.", ".#"], |s| s.to_string())
+            .colours(CellColours::new().with('#', Colour::Grey));
+
+        let out = run(&mut playback, Scripted::keys("lq l"));
+
+        assert_eq!(2, out.matches(CURSOR_HOME).count());
+        assert!(out.contains("\x1b[90m#\x1b[0m.\x1b[K\r\n"));
+        assert!(out.contains("frame 2/2 - paused - 100ms per frame"));
+        assert!(out.ends_with(SHOW_CURSOR));
+        assert_eq!(1, playback.position());
+    }
+}
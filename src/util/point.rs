@@ -1,95 +1,378 @@
 use crate::util::grid::Direction;
 use std::collections::HashSet;
-use std::fmt::{Display, Formatter};
-use std::ops::{Add, Mul, Sub};
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 
-/// a point (i128, i128) with some convenience - has evolved to be more of a Vec2 but often semantically a Point
-/// ... could probably be refactored into a proper distinction between Point and Vec2,
-/// but I guess as long as I can solve the aoc I will just keep on going
+/// the integer types points and vectors are made of
+pub trait Coord:
+    Copy
+    + Ord
+    + Hash
+    + Default
+    + Debug
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + AddAssign
+    + SubAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// `|self - other|` - fine for unsigned types too, signed ones stop at `MAX` if the distance
+    /// doesn't fit (`-128i8` and `127i8` are 255 apart)
+    fn distance(self, other: Self) -> Self;
+
+    /// `self + other` but stops at `MAX` - distances are never negative, so that's the only end
+    /// they can run over
+    fn saturating_add(self, other: Self) -> Self;
+
+    /// -1, 0 or 1 (unsigned types never get below 0)
+    fn signum(self) -> Self;
+}
+
+/// coords that can be negative - needed for everything that turns or looks around the origin
+pub trait SignedCoord: Coord + Neg<Output = Self> {}
+
+macro_rules! signed_coord {
+    ($($t:ty),*) => {$(
+        impl Coord for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn distance(self, other: Self) -> Self {
+                <$t>::try_from(self.abs_diff(other)).unwrap_or(<$t>::MAX)
+            }
+
+            fn saturating_add(self, other: Self) -> Self {
+                <$t>::saturating_add(self, other)
+            }
+
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+        }
+
+        impl SignedCoord for $t {}
+    )*};
+}
+
+macro_rules! unsigned_coord {
+    ($($t:ty),*) => {$(
+        impl Coord for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn distance(self, other: Self) -> Self {
+                self.abs_diff(other)
+            }
+
+            fn saturating_add(self, other: Self) -> Self {
+                <$t>::saturating_add(self, other)
+            }
+
+            fn signum(self) -> Self {
+                (self > 0) as $t
+            }
+        }
+    )*};
+}
+
+signed_coord!(i8, i16, i32, i64, i128, isize);
+unsigned_coord!(u8, u16, u32, u64, u128, usize);
+
+/// a position on a (2d) map - the difference of two points is a [Vec2], which can be added back on
+/// top of a point
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
 pub struct Point<T = i32> {
     pub(crate) x: T,
     pub(crate) y: T,
 }
 
+/// a displacement (velocity, step, offset, ...) between two [Point]s
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Vec2<T = i32> {
+    pub(crate) x: T,
+    pub(crate) y: T,
+}
+
+/// a position in 3d - works like a [Point] with one more axis, the difference of two points is a
+/// [Vec3]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Point3<T = i32> {
+    pub(crate) x: T,
+    pub(crate) y: T,
+    pub(crate) z: T,
+}
+
+/// a displacement between two [Point3]s
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Vec3<T = i32> {
+    pub(crate) x: T,
+    pub(crate) y: T,
+    pub(crate) z: T,
+}
+
+/// what [Vec3::rotate90] turns around
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
 impl<T> Point<T> {
     pub fn new(x: T, y: T) -> Self {
         Point { x, y }
     }
 }
 
-impl<T> Point<T>
-where
-    T: Copy + Add<Output = T> + Sub<Output = T> + From<i32>,
-{
+impl<T> Vec2<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+impl<T> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T> Vec3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Vec3 { x, y, z }
+    }
+}
+
+impl<T: Coord> Point<T> {
     pub fn left(&self) -> Point<T> {
-        Point::new(self.x - T::from(1), self.y)
+        Point::new(self.x - T::ONE, self.y)
     }
 
     pub fn up(&self) -> Point<T> {
-        Point::new(self.x, self.y - T::from(1))
+        Point::new(self.x, self.y - T::ONE)
     }
 
     pub fn right(&self) -> Point<T> {
-        Point::new(self.x + T::from(1), self.y)
+        Point::new(self.x + T::ONE, self.y)
     }
 
     pub fn down(&self) -> Point<T> {
-        Point::new(self.x, self.y + T::from(1))
+        Point::new(self.x, self.y + T::ONE)
     }
 
     pub fn neighbors(&self) -> [Point<T>; 4] {
         [self.up(), self.right(), self.down(), self.left()]
     }
+
+    pub fn manhattan(&self, other: &Point<T>) -> T {
+        self.x
+            .distance(other.x)
+            .saturating_add(self.y.distance(other.y))
+    }
+
+    /// king moves - diagonal steps count as 1
+    pub fn chebyshev(&self, other: &Point<T>) -> T {
+        self.x.distance(other.x).max(self.y.distance(other.y))
+    }
+}
+
+impl<T: SignedCoord> Point<T> {
+    /// all points within manhattan distance `n` from `self`
+    pub fn proximity_manhattan(&self, n: T) -> HashSet<Point<T>> {
+        let mut points = HashSet::new();
+        let mut dx = -n;
+        while dx <= n {
+            let max_dy = n - dx.distance(T::ZERO);
+            let mut dy = -max_dy;
+            while dy <= max_dy {
+                points.insert(Point::new(self.x + dx, self.y + dy));
+                dy += T::ONE;
+            }
+            dx += T::ONE;
+        }
+        points
+    }
 }
 
-impl Point<i32> {
-    /// Returns all points within Manhattan distance `n` from `self`
-    pub fn proximity_manhattan(&self, n: i32) -> HashSet<Point> {
-        (-n..=n)
-            .flat_map(|dx| {
-                let max_dy = n - dx.abs();
-                (-max_dy..=max_dy).map(move |dy| Point::new(self.x + dx, self.y + dy))
-            })
-            .collect()
+impl<T: Coord> Vec2<T> {
+    /// steps needed if only straight steps are allowed
+    pub fn manhattan(&self) -> T {
+        self.x
+            .distance(T::ZERO)
+            .saturating_add(self.y.distance(T::ZERO))
+    }
+
+    /// steps needed if diagonal steps are allowed too
+    pub fn chebyshev(&self) -> T {
+        self.x.distance(T::ZERO).max(self.y.distance(T::ZERO))
+    }
+
+    /// every component reduced to -1, 0 or 1 - one step in the direction of `self`
+    pub fn signum(&self) -> Vec2<T> {
+        Vec2::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn dot(&self, other: &Vec2<T>) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// z of the 3d cross product - positive if `other` is clockwise of `self` (y grows downwards)
+    pub fn cross(&self, other: &Vec2<T>) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl<T: SignedCoord> Vec2<T> {
+    /// clockwise - y grows downwards like on the maps (so north turns into east)
+    pub fn rotate90(&self) -> Vec2<T> {
+        Vec2::new(-self.y, self.x)
+    }
+}
+
+impl<T: Coord> Point3<T> {
+    /// the 6 points sharing a face with `self`
+    pub fn neighbors(&self) -> [Point3<T>; 6] {
+        let (x, y, z) = (self.x, self.y, self.z);
+        [
+            Point3::new(x - T::ONE, y, z),
+            Point3::new(x + T::ONE, y, z),
+            Point3::new(x, y - T::ONE, z),
+            Point3::new(x, y + T::ONE, z),
+            Point3::new(x, y, z - T::ONE),
+            Point3::new(x, y, z + T::ONE),
+        ]
+    }
+
+    pub fn manhattan(&self, other: &Point3<T>) -> T {
+        self.x
+            .distance(other.x)
+            .saturating_add(self.y.distance(other.y))
+            .saturating_add(self.z.distance(other.z))
+    }
+
+    pub fn chebyshev(&self, other: &Point3<T>) -> T {
+        self.x
+            .distance(other.x)
+            .max(self.y.distance(other.y))
+            .max(self.z.distance(other.z))
     }
 }
 
-impl Display for Point {
+impl<T: SignedCoord> Point3<T> {
+    /// all points within manhattan distance `n` from `self`
+    pub fn proximity_manhattan(&self, n: T) -> HashSet<Point3<T>> {
+        let mut points = HashSet::new();
+        for p in Point::new(self.x, self.y).proximity_manhattan(n) {
+            let max_dz = n - p.manhattan(&Point::new(self.x, self.y));
+            let mut dz = -max_dz;
+            while dz <= max_dz {
+                points.insert(Point3::new(p.x, p.y, self.z + dz));
+                dz += T::ONE;
+            }
+        }
+        points
+    }
+}
+
+impl<T: Coord> Vec3<T> {
+    /// steps needed if only straight steps are allowed
+    pub fn manhattan(&self) -> T {
+        self.x
+            .distance(T::ZERO)
+            .saturating_add(self.y.distance(T::ZERO))
+            .saturating_add(self.z.distance(T::ZERO))
+    }
+
+    /// steps needed if diagonal steps are allowed too
+    pub fn chebyshev(&self) -> T {
+        self.x
+            .distance(T::ZERO)
+            .max(self.y.distance(T::ZERO))
+            .max(self.z.distance(T::ZERO))
+    }
+
+    /// every component reduced to -1, 0 or 1 - one step in the direction of `self`
+    pub fn signum(&self) -> Vec3<T> {
+        Vec3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    pub fn dot(&self, other: &Vec3<T>) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// right-handed - x cross y is z
+    pub fn cross(&self, other: &Vec3<T>) -> Vec3<T> {
+        Vec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+}
+
+impl<T: SignedCoord> Vec3<T> {
+    /// a quarter turn around `axis` - counterclockwise when looking from the positive end of the
+    /// axis (right-handed)
+    pub fn rotate90(&self, axis: Axis) -> Vec3<T> {
+        let (x, y, z) = (self.x, self.y, self.z);
+        match axis {
+            Axis::X => Vec3::new(x, -z, y),
+            Axis::Y => Vec3::new(z, y, -x),
+            Axis::Z => Vec3::new(-y, x, z),
+        }
+    }
+}
+
+impl<T: Display> Display for Point<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "({}, {})", &self.x, &self.y)
     }
 }
 
-impl Add<Point> for Point {
-    type Output = Point;
+impl<T: Display> Display for Vec2<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<{}, {}>", &self.x, &self.y)
+    }
+}
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Point::new(self.x + rhs.x, self.y + rhs.y)
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", &self.x, &self.y, &self.z)
     }
 }
 
-impl Add<(u8, u8)> for Point<u8> {
-    type Output = Point<u8>;
+impl<T: Display> Display for Vec3<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<{}, {}, {}>", &self.x, &self.y, &self.z)
+    }
+}
+
+impl<T: Coord> Add<Vec2<T>> for Point<T> {
+    type Output = Point<T>;
 
-    fn add(self, rhs: (u8, u8)) -> Self::Output {
-        Point::new(self.x + rhs.0, self.y + rhs.1)
+    fn add(self, rhs: Vec2<T>) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
     }
 }
 
-impl Add<(i32, i32)> for Point {
-    type Output = Point;
+impl<T: Coord> Sub<Vec2<T>> for Point<T> {
+    type Output = Point<T>;
 
-    fn add(self, rhs: (i32, i32)) -> Self::Output {
-        Point::new(self.x + rhs.0, self.y + rhs.1)
+    fn sub(self, rhs: Vec2<T>) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
     }
 }
 
-impl Add<(i128, i128)> for Point<i128> {
-    type Output = Self;
+/// the way from `rhs` to `self`
+impl<T: Coord> Sub for Point<T> {
+    type Output = Vec2<T>;
 
-    fn add(self, rhs: (i128, i128)) -> Self::Output {
-        Point::new(self.x + rhs.0, self.y + rhs.1)
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
     }
 }
 
@@ -97,40 +380,140 @@ impl Add<Direction> for Point {
     type Output = Point;
 
     fn add(self, rhs: Direction) -> Self::Output {
-        match rhs {
-            Direction::N => self.up(),
-            Direction::NE => self.up().right(),
-            Direction::E => self.right(),
-            Direction::SE => self.down().right(),
-            Direction::S => self.down(),
-            Direction::SW => self.down().left(),
-            Direction::W => self.left(),
-            Direction::NW => self.up().left(),
-        }
+        self + Vec2::from(rhs)
     }
 }
 
-impl Sub for Point {
-    type Output = Point;
+/// scales each axis on its own (e.g. `(2, 1)` doubles the width)
+impl<T: Coord> Mul<(T, T)> for Point<T> {
+    type Output = Point<T>;
+
+    fn mul(self, rhs: (T, T)) -> Self::Output {
+        Point::new(self.x * rhs.0, self.y * rhs.1)
+    }
+}
+
+impl<T: Coord> Add for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Vec2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Coord> Sub for Vec2<T> {
+    type Output = Vec2<T>;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        Point::new(self.x - rhs.x, self.y - rhs.y)
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
     }
 }
 
-impl Mul<i32> for Point {
-    type Output = Point;
+impl<T: Coord> Mul<T> for Vec2<T> {
+    type Output = Vec2<T>;
 
-    fn mul(self, rhs: i32) -> Self::Output {
-        Point::new(self.x * rhs, self.y * rhs)
+    fn mul(self, rhs: T) -> Self::Output {
+        Vec2::new(self.x * rhs, self.y * rhs)
     }
 }
 
-impl Mul<(i32, i32)> for Point {
-    type Output = Point;
+impl<T: SignedCoord> Neg for Vec2<T> {
+    type Output = Vec2<T>;
 
-    fn mul(self, rhs: (i32, i32)) -> Self::Output {
-        Point::new(self.x * rhs.0, self.y * rhs.1)
+    fn neg(self) -> Self::Output {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl From<Direction> for Vec2 {
+    fn from(value: Direction) -> Self {
+        let (x, y) = value.delta();
+        Vec2::new(x, y)
+    }
+}
+
+impl<T: Coord> Add<Vec3<T>> for Point3<T> {
+    type Output = Point3<T>;
+
+    fn add(self, rhs: Vec3<T>) -> Self::Output {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Coord> Sub<Vec3<T>> for Point3<T> {
+    type Output = Point3<T>;
+
+    fn sub(self, rhs: Vec3<T>) -> Self::Output {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+/// the way from `rhs` to `self`
+impl<T: Coord> Sub for Point3<T> {
+    type Output = Vec3<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vec3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Coord> Add for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Vec3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Coord> Sub for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vec3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Coord> Mul<T> for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Vec3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: SignedCoord> Neg for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn neg(self) -> Self::Output {
+        Vec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+/// the point `v` away from the origin
+impl<T> From<Vec2<T>> for Point<T> {
+    fn from(v: Vec2<T>) -> Self {
+        Point::new(v.x, v.y)
+    }
+}
+
+/// the way from the origin to `p`
+impl<T> From<Point<T>> for Vec2<T> {
+    fn from(p: Point<T>) -> Self {
+        Vec2::new(p.x, p.y)
+    }
+}
+
+/// the point `v` away from the origin
+impl<T> From<Vec3<T>> for Point3<T> {
+    fn from(v: Vec3<T>) -> Self {
+        Point3::new(v.x, v.y, v.z)
+    }
+}
+
+/// the way from the origin to `p`
+impl<T> From<Point3<T>> for Vec3<T> {
+    fn from(p: Point3<T>) -> Self {
+        Vec3::new(p.x, p.y, p.z)
     }
 }
 
@@ -160,4 +543,112 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn should_measure_distances_for_any_integer() {
+        let (a, b) = (Point::new(1u8, 7), Point::new(4u8, 2));
+
+        assert_eq!(8, a.manhattan(&b));
+        assert_eq!(5, a.chebyshev(&b));
+        assert_eq!(8, Point::new(-1i128, 7).manhattan(&Point::new(2, 2)));
+        assert_eq!(5, Point::new(-1i64, 0).chebyshev(&Point::new(2, -5)));
+        assert_eq!(Point::new(2usize, 3), Point::new(3, 3).left().down().up());
+    }
+
+    #[test]
+    fn should_not_wrap_distances_beyond_max() {
+        assert_eq!(i8::MAX, (-128i8).distance(127));
+        assert_eq!(127, (-127i8).distance(0));
+        assert_eq!(255, 0u8.distance(255));
+        assert_eq!(
+            i32::MAX,
+            Point::new(i32::MIN, 0).manhattan(&Point::new(0, 0))
+        );
+    }
+
+    #[test]
+    fn should_not_wrap_manhattan_beyond_max() {
+        assert_eq!(
+            i8::MAX,
+            Point::<i8>::new(0, 0).manhattan(&Point::new(100, 100))
+        );
+        assert_eq!(
+            u8::MAX,
+            Point::<u8>::new(0, 0).manhattan(&Point::new(200, 200))
+        );
+        assert_eq!(i8::MAX, Vec2::<i8>::new(-100, 100).manhattan());
+        assert_eq!(
+            i8::MAX,
+            Point3::<i8>::new(0, 0, 0).manhattan(&Point3::new(50, 50, 50))
+        );
+        assert_eq!(i8::MAX, Vec3::<i8>::new(50, -50, 50).manhattan());
+        assert_eq!(126, Point::<i8>::new(0, 0).manhattan(&Point::new(100, -26)));
+    }
+
+    #[test]
+    fn should_work_with_displacements() {
+        let (from, to) = (Point::new(2, 3), Point::new(-1, 7));
+        let v = to - from;
+
+        assert_eq!(Vec2::new(-3, 4), v);
+        assert_eq!(to, from + v);
+        assert_eq!(from, to - v);
+        assert_eq!(7, v.manhattan());
+        assert_eq!(4, v.chebyshev());
+        assert_eq!(Vec2::new(-1, 1), v.signum());
+        assert_eq!(Vec2::new(-6, 8), v * 2);
+        assert_eq!(Vec2::new(3, -4), -v);
+        assert_eq!(Point::new(3, 2), Point::new(2, 2) + Direction::E);
+    }
+
+    #[test]
+    fn should_rotate_and_multiply_vec2() {
+        let north = Vec2::from(Direction::N);
+
+        assert_eq!(Vec2::from(Direction::E), north.rotate90());
+        assert_eq!(north, north.rotate90().rotate90().rotate90().rotate90());
+        assert_eq!(0, north.dot(&north.rotate90()));
+        assert_eq!(1, north.dot(&north));
+        assert!(north.cross(&north.rotate90()) > 0);
+        assert_eq!(0, north.cross(&(north * 3)));
+    }
+
+    #[test]
+    fn should_work_in_3d() {
+        let (a, b) = (Point3::new(1, 2, 3), Point3::new(-1, 0, 7));
+        let v = b - a;
+
+        assert_eq!(8, a.manhattan(&b));
+        assert_eq!(4, a.chebyshev(&b));
+        assert_eq!(Vec3::new(-2, -2, 4), v);
+        assert_eq!(b, a + v);
+        assert_eq!(a, b - v);
+        assert_eq!(8, v.manhattan());
+        assert_eq!(4, v.chebyshev());
+        assert_eq!(Vec3::new(-1, -1, 1), v.signum());
+        assert_eq!(Vec3::new(2, 2, -4), -v);
+        assert!(a.neighbors().iter().all(|n| n.manhattan(&a) == 1));
+        assert_eq!(25, Point3::new(0, 0, 0).proximity_manhattan(2).len());
+        assert_eq!(Vec3::new(1u8, 0, 1), Vec3::new(7u8, 0, 2).signum());
+    }
+
+    #[test]
+    fn should_rotate_and_multiply_vec3() {
+        let (x, y, z) = (Vec3::new(1, 0, 0), Vec3::new(0, 1, 0), Vec3::new(0, 0, 1));
+        let v = Vec3::from(Point3::new(1, 2, 3));
+
+        assert_eq!(z, x.cross(&y));
+        assert_eq!(0, x.dot(&y));
+        assert_eq!(14, v.dot(&v));
+        assert_eq!(Vec3::new(-2, 1, 3), v.rotate90(Axis::Z));
+        assert_eq!(y, x.rotate90(Axis::Z));
+        assert_eq!(
+            v,
+            v.rotate90(Axis::X)
+                .rotate90(Axis::X)
+                .rotate90(Axis::X)
+                .rotate90(Axis::X)
+        );
+        assert_eq!(Vec3::new(3, 6, 9), v * 3);
+    }
 }
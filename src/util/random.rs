@@ -0,0 +1,86 @@
+/// tiny seeded xorshift64* generator - good enough to generate puzzle inputs reproducibly without
+/// pulling in a crate (not meant for anything where randomness actually matters)
+#[derive(Debug, Clone)]
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    pub fn new(seed: u64) -> Self {
+        // the state must never be 0 - otherwise it stays 0 forever
+        XorShift {
+            state: (seed ^ 0x9E37_79B9_7F4A_7C15) | 1,
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// uniform-ish in `0..n` (modulo bias is irrelevant for input generation) - `n` must be > 0
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// uniform-ish in `from..=to`
+    pub fn between(&mut self, from: usize, to: usize) -> usize {
+        from + self.below(to - from + 1)
+    }
+
+    /// true with a probability of `1/n`
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    /// Fisher-Yates
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_reproducible() {
+        let a: Vec<u64> = (0..5)
+            .map({
+                let mut rng = XorShift::new(42);
+                move |_| rng.next_u64()
+            })
+            .collect();
+        let b: Vec<u64> = (0..5)
+            .map({
+                let mut rng = XorShift::new(42);
+                move |_| rng.next_u64()
+            })
+            .collect();
+
+        assert_eq!(a, b);
+        assert_ne!(a[0], a[1]);
+    }
+
+    #[test]
+    fn should_stay_in_range() {
+        let mut rng = XorShift::new(0);
+        for _ in 0..1000 {
+            let n = rng.between(3, 7);
+            assert!((3..=7).contains(&n));
+        }
+
+        let mut items = [1, 2, 3, 4, 5];
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!([1, 2, 3, 4, 5], items);
+    }
+}
@@ -0,0 +1,240 @@
+use std::fmt::Display;
+
+use crate::util::grid::GridAccess;
+use crate::util::point::Point;
+
+const ANSI_REVERSE: &str = "\x1b[7m";
+const ANSI_RESET: &str = "\x1b[0m";
+const SIDE_BY_SIDE_SEPARATOR: &str = " | ";
+
+/// how [Renderer] marks changed cells in a diff
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Highlight {
+    /// changed cells in reverse video - for looking at it in a terminal
+    Ansi,
+    /// only changed cells are drawn, everything else is replaced by the char - for logs and tests
+    Only(char),
+}
+
+/// turns grids back into text, one line per row - cells that are missing (or empty) within the
+/// bounds of the grid are drawn as `empty`
+///
+/// works for [Grid](crate::util::grid::Grid) and [DenseGrid](crate::util::grid::DenseGrid) alike -
+/// sparse grids are drawn from the top-left corner of their bounds, not from (0, 0)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Renderer {
+    empty: char,
+    highlight: Highlight,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Renderer::new('.')
+    }
+}
+
+impl Renderer {
+    pub fn new(empty: char) -> Self {
+        Renderer {
+            empty,
+            highlight: Highlight::Ansi,
+        }
+    }
+
+    pub fn highlight(self, highlight: Highlight) -> Self {
+        Renderer { highlight, ..self }
+    }
+
+    pub fn render<T: Display>(&self, grid: &impl GridAccess<T>) -> String {
+        self.render_with(grid, |_, cell| cell.to_string())
+    }
+
+    /// for cells without a (fitting) [Display] impl - also gets the position of the cell, so things
+    /// that aren't part of the grid (e.g. a robot) can be drawn on top of it
+    pub fn render_with<T, S: Display>(
+        &self,
+        grid: &impl GridAccess<T>,
+        cell: impl Fn(Point, &T) -> S,
+    ) -> String {
+        let Some((min, max)) = grid.bounds() else {
+            return String::new();
+        };
+        (min.y..=max.y)
+            .map(|y| {
+                (min.x..=max.x)
+                    .map(|x| {
+                        let p = Point::new(x, y);
+                        grid.get(&p)
+                            .map_or(self.empty.to_string(), |t| cell(p, t).to_string())
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// `after` with every cell that differs from `before` highlighted
+    pub fn diff<T: Display + PartialEq>(
+        &self,
+        before: &impl GridAccess<T>,
+        after: &impl GridAccess<T>,
+    ) -> String {
+        self.diff_rows(before, after, |_, after| after).join("\n")
+    }
+
+    /// `before` and `after` next to each other (separated by `" | "`) with the changed cells
+    /// highlighted on both sides
+    pub fn diff_side_by_side<T: Display + PartialEq>(
+        &self,
+        before: &impl GridAccess<T>,
+        after: &impl GridAccess<T>,
+    ) -> String {
+        self.diff_rows(before, after, |before, after| {
+            format!("{before}{SIDE_BY_SIDE_SEPARATOR}{after}")
+        })
+        .join("\n")
+    }
+
+    /// both grids are drawn over the union of their bounds, so the rows line up even if the
+    /// bounds of a sparse grid grew or shrank in between
+    fn diff_rows<T: Display + PartialEq>(
+        &self,
+        before: &impl GridAccess<T>,
+        after: &impl GridAccess<T>,
+        join: impl Fn(String, String) -> String,
+    ) -> Vec<String> {
+        let Some((min, max)) = union(before.bounds(), after.bounds()) else {
+            return vec![];
+        };
+        (min.y..=max.y)
+            .map(|y| {
+                let (mut left, mut right) = (String::new(), String::new());
+                for x in min.x..=max.x {
+                    let p = Point::new(x, y);
+                    let (b, a) = (before.get(&p), after.get(&p));
+                    left.push_str(&self.cell(b, b != a));
+                    right.push_str(&self.cell(a, b != a));
+                }
+                join(left, right)
+            })
+            .collect()
+    }
+
+    fn cell<T: Display>(&self, cell: Option<&T>, changed: bool) -> String {
+        let text = cell.map_or(self.empty.to_string(), |t| t.to_string());
+        match (self.highlight, changed) {
+            (Highlight::Ansi, true) => format!("{ANSI_REVERSE}{text}{ANSI_RESET}"),
+            (Highlight::Only(c), false) => c.to_string(),
+            _ => text,
+        }
+    }
+}
+
+/// positions of all cells that were added, removed or changed between `before` and `after` - top to
+/// bottom, left to right
+pub fn changed_cells<T: PartialEq>(
+    before: &impl GridAccess<T>,
+    after: &impl GridAccess<T>,
+) -> Vec<Point> {
+    let Some((min, max)) = union(before.bounds(), after.bounds()) else {
+        return vec![];
+    };
+    (min.y..=max.y)
+        .flat_map(|y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+        .filter(|p| before.get(p) != after.get(p))
+        .collect()
+}
+
+fn union(a: Option<(Point, Point)>, b: Option<(Point, Point)>) -> Option<(Point, Point)> {
+    match (a, b) {
+        (Some((a_min, a_max)), Some((b_min, b_max))) => Some((
+            Point::new(a_min.x.min(b_min.x), a_min.y.min(b_min.y)),
+            Point::new(a_max.x.max(b_max.x), a_max.y.max(b_max.y)),
+        )),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::grid::{DenseGrid, Grid};
+
+    #[test]
+    fn should_render_what_was_parsed() {
+        let text = "#.#\n.A.\n#..";
+
+        assert_eq!(text, Renderer::default().render(&DenseGrid::from(text)));
+        assert_eq!(text, Renderer::default().render(&Grid::from(text)));
+    }
+
+    #[test]
+    fn should_fill_missing_cells() {
+        let grid: Grid<u8> = Grid::from("1.2\n.3.").filter_map(|c| c.to_digit(10).map(|d| d as u8));
+
+        assert_eq!("1 2\n 3 ", Renderer::new(' ').render(&grid));
+    }
+
+    #[test]
+    fn should_render_with_closure() {
+        let grid = DenseGrid::filled(3, 2, false);
+
+        let rendered = Renderer::default().render_with(&grid, |p, occupied| {
+            if p == Point::new(1, 1) {
+                '@'
+            } else if *occupied {
+                'X'
+            } else {
+                ' '
+            }
+        });
+
+        assert_eq!("   \n @ ", rendered);
+    }
+
+    #[test]
+    fn should_diff_inline() {
+        let before = DenseGrid::from("#..\n.O.\n...");
+        let after = DenseGrid::from("#..\n...\n.O.");
+
+        assert_eq!(
+            "...\n...\n.O.",
+            Renderer::default()
+                .highlight(Highlight::Only('.'))
+                .diff(&before, &after)
+        );
+        assert_eq!(
+            "#..\n.\x1b[7m.\x1b[0m.\n.\x1b[7mO\x1b[0m.",
+            Renderer::default().diff(&before, &after)
+        );
+    }
+
+    #[test]
+    fn should_diff_side_by_side() {
+        let before = DenseGrid::from("ab\ncd");
+        let after = DenseGrid::from("ab\nxd");
+
+        assert_eq!(
+            "   |   \nc  | x ",
+            Renderer::default()
+                .highlight(Highlight::Only(' '))
+                .diff_side_by_side(&before, &after)
+        );
+    }
+
+    #[test]
+    fn should_align_grown_sparse_grids() {
+        let before: Grid<char> = Grid::new([(Point::new(1, 1), 'O')].into());
+        let after: Grid<char> =
+            Grid::new([(Point::new(1, 1), 'O'), (Point::new(0, 0), 'O')].into());
+
+        assert_eq!(vec![Point::new(0, 0)], changed_cells(&before, &after));
+        assert_eq!(
+            ".. | O.\n.. | ..",
+            Renderer::default()
+                .highlight(Highlight::Only('.'))
+                .diff_side_by_side(&before, &after)
+        );
+    }
+}
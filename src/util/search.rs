@@ -0,0 +1,387 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// what a search found - the distance to every state it settled and, for each of them, all
+/// predecessors on a cheapest way there (so every optimal path can be restored, not just one)
+///
+/// if a goal was given the search stops as soon as nothing cheaper than the goal is left - the
+/// distance map only covers what was explored until then (pass `|_| false` for the full map)
+#[derive(Debug, Clone)]
+pub struct Search<S, C> {
+    start: S,
+    distances: HashMap<S, C>,
+    parents: HashMap<S, Vec<S>>,
+    goals: Vec<S>,
+}
+
+impl<S, C> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy,
+{
+    fn new(start: S, zero: C) -> Self {
+        Search {
+            distances: HashMap::from([(start.clone(), zero)]),
+            start,
+            parents: HashMap::new(),
+            goals: vec![],
+        }
+    }
+
+    /// cost of the cheapest path to a goal - `None` if no goal is reachable
+    pub fn cost(&self) -> Option<C> {
+        self.goals.first().map(|goal| self.distances[goal])
+    }
+
+    /// all goals that can be reached for [Search::cost] (e.g. the same point from different directions)
+    pub fn goals(&self) -> &[S] {
+        &self.goals
+    }
+
+    pub fn distances(&self) -> &HashMap<S, C> {
+        &self.distances
+    }
+
+    pub fn distance(&self, state: &S) -> Option<C> {
+        self.distances.get(state).copied()
+    }
+
+    /// one cheapest path from start to goal (both included)
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goals.first()?)
+    }
+
+    /// one cheapest path from start to any settled `state` (both included) - the first parent of
+    /// a state is always settled before it, only the start can pick up parents over zero cost steps
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        if !self.distances.contains_key(state) {
+            return None;
+        }
+        let mut path = vec![state.clone()];
+        while *path.last()? != self.start {
+            let parent = self.parents.get(path.last()?).and_then(|p| p.first())?;
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// every cheapest path to every goal - careful, there can be exponentially many (see
+    /// [Search::on_optimal_paths] if only the states are of interest)
+    ///
+    /// zero cost cycles make states each other's parents, so the paths only visit a state once
+    pub fn paths(&self) -> Vec<Vec<S>> {
+        let mut paths = vec![];
+        let mut stack: Vec<Vec<S>> = self.goals.iter().map(|goal| vec![goal.clone()]).collect();
+
+        while let Some(path) = stack.pop() {
+            let last = path.last().expect("paths are never empty");
+            match self.parents.get(last) {
+                Some(parents) if *last != self.start => {
+                    for parent in parents.iter().filter(|parent| !path.contains(parent)) {
+                        let mut longer = path.clone();
+                        longer.push(parent.clone());
+                        stack.push(longer);
+                    }
+                }
+                _ => {
+                    let mut path = path;
+                    path.reverse();
+                    paths.push(path);
+                }
+            }
+        }
+        paths
+    }
+
+    /// all states that are part of at least one cheapest path to a goal
+    pub fn on_optimal_paths(&self) -> HashSet<S> {
+        let mut seen: HashSet<S> = self.goals.iter().cloned().collect();
+        let mut todo: Vec<S> = self.goals.clone();
+
+        while let Some(state) = todo.pop() {
+            for parent in self.parents.get(&state).into_iter().flatten() {
+                if seen.insert(parent.clone()) {
+                    todo.push(parent.clone());
+                }
+            }
+        }
+        seen
+    }
+
+    /// `parent` -> `state` costs `cost` in total - keeps track of equally cheap parents
+    fn relax(&mut self, parent: &S, state: &S, cost: C) -> bool
+    where
+        C: Ord,
+    {
+        match self.distances.get(state).map(|known| cost.cmp(known)) {
+            Some(Ordering::Greater) => false,
+            Some(Ordering::Equal) => {
+                self.parents
+                    .entry(state.clone())
+                    .or_default()
+                    .push(parent.clone());
+                false
+            }
+            _ => {
+                self.distances.insert(state.clone(), cost);
+                self.parents.insert(state.clone(), vec![parent.clone()]);
+                true
+            }
+        }
+    }
+}
+
+/// breadth first - every step costs 1
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::new(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+    let mut goal_cost = None;
+
+    while let Some(state) = queue.pop_front() {
+        let cost = search.distances[&state];
+        if goal_cost.is_some_and(|goal| cost > goal) {
+            break;
+        }
+        if is_goal(&state) {
+            goal_cost = Some(cost);
+            search.goals.push(state);
+            continue;
+        }
+        for next in successors(&state) {
+            if search.relax(&state, &next, cost + 1) {
+                queue.push_back(next);
+            }
+        }
+    }
+    search
+}
+
+/// cheapest first - costs must not be negative
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    a_star(start, successors, |_| C::default(), is_goal)
+}
+
+/// like [dijkstra] but guided by `heuristic` - it must never overestimate the remaining cost (and
+/// never drop by more than the step cost) or the result might not be the cheapest
+pub fn a_star<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut search = Search::new(start.clone(), C::default());
+    let mut open = BinaryHeap::from([Open {
+        f: heuristic(&start),
+        g: C::default(),
+        state: start,
+    }]);
+    let mut closed: HashSet<S> = HashSet::new();
+    let mut goal_cost = None;
+
+    while let Some(Open { f, g, state }) = open.pop() {
+        if goal_cost.is_some_and(|goal| f > goal) {
+            break;
+        }
+        if g > search.distances[&state] || !closed.insert(state.clone()) {
+            continue; // outdated entry
+        }
+        if is_goal(&state) {
+            goal_cost = Some(g);
+            search.goals.push(state);
+            continue;
+        }
+        for (next, step) in successors(&state) {
+            let next_g = g + step;
+            if search.relax(&state, &next, next_g) {
+                open.push(Open {
+                    f: next_g + heuristic(&next),
+                    g: next_g,
+                    state: next,
+                });
+            }
+        }
+    }
+    search
+}
+
+/// entry of the open list - the cheapest `f` first and on ties the one that got further
+struct Open<S, C> {
+    f: C,
+    g: C,
+    state: S,
+}
+
+impl<S, C: Ord> PartialEq for Open<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S, C: Ord> Eq for Open<S, C> {}
+
+impl<S, C: Ord> PartialOrd for Open<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> Ord for Open<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.f.cmp(&self.f).then_with(|| self.g.cmp(&other.g))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::grid::{DenseGrid, GridAccess};
+    use crate::util::point::Point;
+
+    const MAZE: &str = "
+S..#
+.#..
+...E";
+
+    fn open_neighbors(maze: &DenseGrid<char>, p: &Point) -> Vec<Point> {
+        maze.neighbors(p)
+            .filter(|(_, &c)| c != '#')
+            .map(|(n, _)| n)
+            .collect()
+    }
+
+    #[test]
+    fn should_find_shortest_path_with_bfs() {
+        let maze = DenseGrid::from(MAZE.trim());
+        let end = Point::new(3, 2);
+
+        let search = bfs(
+            Point::new(0, 0),
+            |p| open_neighbors(&maze, p),
+            |p| *p == end,
+        );
+
+        assert_eq!(Some(5), search.cost());
+        assert_eq!(6, search.path().unwrap().len());
+        assert_eq!(Some(end), search.path().unwrap().last().copied());
+        assert_eq!(3, search.paths().len());
+    }
+
+    #[test]
+    fn should_agree_on_cost() {
+        let maze = DenseGrid::from(MAZE.trim());
+        let end = Point::new(3, 2);
+        let weighted = |p: &Point| {
+            open_neighbors(&maze, p)
+                .into_iter()
+                .map(|n| (n, 1u32))
+                .collect::<Vec<_>>()
+        };
+
+        let dijkstra = dijkstra(Point::new(0, 0), weighted, |p| *p == end);
+        let a_star = a_star(
+            Point::new(0, 0),
+            weighted,
+            |p| p.manhattan(&end) as u32,
+            |p| *p == end,
+        );
+
+        assert_eq!(Some(5), dijkstra.cost());
+        assert_eq!(Some(5), a_star.cost());
+        assert_eq!(dijkstra.on_optimal_paths(), a_star.on_optimal_paths());
+        assert_eq!(10, a_star.on_optimal_paths().len());
+    }
+
+    #[test]
+    fn should_prefer_cheaper_detour() {
+        // a -> b -> d is shorter but a -> c -> d is cheaper
+        let edges = HashMap::from([
+            ('a', vec![('b', 10), ('c', 1)]),
+            ('b', vec![('d', 1)]),
+            ('c', vec![('e', 1)]),
+            ('e', vec![('d', 1)]),
+        ]);
+
+        let search = dijkstra(
+            'a',
+            |n| edges.get(n).cloned().unwrap_or_default(),
+            |n| *n == 'd',
+        );
+
+        assert_eq!(Some(3), search.cost());
+        assert_eq!(Some(vec!['a', 'c', 'e', 'd']), search.path());
+    }
+
+    #[test]
+    fn should_not_loop_over_zero_cost_cycles() {
+        let edges = HashMap::from([
+            ('s', vec![('a', 0)]),
+            ('a', vec![('s', 0), ('b', 0)]),
+            ('b', vec![('a', 0), ('t', 1)]),
+        ]);
+
+        let search = dijkstra(
+            's',
+            |n| edges.get(n).cloned().unwrap_or_default(),
+            |n| *n == 't',
+        );
+
+        assert_eq!(Some(1), search.cost());
+        assert_eq!(Some(vec!['s', 'a', 'b', 't']), search.path());
+        assert_eq!(Some(vec!['s']), search.path_to(&'s'));
+        assert_eq!(vec![vec!['s', 'a', 'b', 't']], search.paths());
+        assert_eq!(4, search.on_optimal_paths().len());
+    }
+
+    #[test]
+    fn should_map_all_distances_without_goal() {
+        let maze = DenseGrid::from(MAZE.trim());
+
+        let search = bfs(Point::new(0, 0), |p| open_neighbors(&maze, p), |_| false);
+
+        assert_eq!(None, search.cost());
+        assert_eq!(10, search.distances().len());
+        assert_eq!(Some(5), search.distance(&Point::new(3, 2)));
+        assert_eq!(
+            Some(vec![Point::new(0, 0), Point::new(1, 0), Point::new(2, 0)]),
+            search.path_to(&Point::new(2, 0))
+        );
+    }
+
+    #[test]
+    fn should_not_reach_unreachable_goal() {
+        let search = bfs(
+            0u8,
+            |n| if *n < 5 { vec![n + 1] } else { vec![] },
+            |n| *n == 9,
+        );
+
+        assert_eq!(None, search.cost());
+        assert_eq!(None, search.path());
+        assert!(search.paths().is_empty());
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+
+use crate::util::grid::{Direction, GridAccess};
+use crate::util::point::{Point, Vec2};
+
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    children: HashMap<char, usize>,
+    /// index of the word that ends here
+    word: Option<usize>,
+}
+
+/// looks for many words at once - they share a trie, so every start cell and direction is only
+/// walked as long as some word still fits
+#[derive(Debug, Clone)]
+pub struct WordSearch {
+    nodes: Vec<TrieNode>,
+    words: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WordMatch {
+    /// index into [WordSearch::words]
+    pub word: usize,
+    /// where the first letter is
+    pub start: Point,
+    pub direction: Direction,
+}
+
+impl WordSearch {
+    /// a word that shows up twice is only reported under its first index - empty words are
+    /// never found
+    pub fn new<'a>(words: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut search = WordSearch {
+            nodes: vec![TrieNode::default()],
+            words: vec![],
+        };
+        for word in words {
+            let mut node = 0;
+            for c in word.chars() {
+                node = match search.nodes[node].children.get(&c) {
+                    Some(&child) => child,
+                    None => {
+                        search.nodes.push(TrieNode::default());
+                        let child = search.nodes.len() - 1;
+                        search.nodes[node].children.insert(c, child);
+                        child
+                    }
+                };
+            }
+            if node != 0 {
+                search.nodes[node].word.get_or_insert(search.words.len());
+            }
+            search.words.push(word.to_string());
+        }
+        search
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// every word read in any of the `directions` - palindromes (and single letters) show up once
+    /// per direction they can be read in, sorted by where they start
+    pub fn find(&self, grid: &impl GridAccess<char>, directions: &[Direction]) -> Vec<WordMatch> {
+        let mut matches = vec![];
+        for (start, c) in grid.iter() {
+            let Some(&first) = self.nodes[0].children.get(c) else {
+                continue;
+            };
+            for &direction in directions {
+                let (mut node, mut p) = (first, start);
+                loop {
+                    if let Some(word) = self.nodes[node].word {
+                        matches.push(WordMatch {
+                            word,
+                            start,
+                            direction,
+                        });
+                    }
+                    p = p + direction;
+                    match grid.get(&p).and_then(|c| self.nodes[node].children.get(c)) {
+                        Some(&child) => node = child,
+                        None => break,
+                    }
+                }
+            }
+        }
+        matches.sort_by_key(|m| (m.start.y, m.start.x));
+        matches
+    }
+}
+
+/// one of the 8 ways to put a square down - mirrored (left becomes right) first, then turned
+/// clockwise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Orientation {
+    pub flipped: bool,
+    /// 0 to 3 times 90°
+    pub quarter_turns: u8,
+}
+
+impl Orientation {
+    /// the untouched one first
+    pub const ALL: [Orientation; 8] = {
+        let mut all = [Orientation {
+            flipped: false,
+            quarter_turns: 0,
+        }; 8];
+        let mut i = 0;
+        while i < 8 {
+            all[i].flipped = i >= 4;
+            all[i].quarter_turns = (i % 4) as u8;
+            i += 1;
+        }
+        all
+    };
+
+    /// where `(x, y)` of a `width` x `height` rectangle ends up - relative to the top-left corner
+    /// of the moved rectangle
+    fn apply(&self, (x, y): (i32, i32), (width, height): (i32, i32)) -> (i32, i32) {
+        let (mut x, mut y, mut width, mut height) = match self.flipped {
+            true => (width - 1 - x, y, width, height),
+            false => (x, y, width, height),
+        };
+        for _ in 0..self.quarter_turns {
+            (x, y, width, height) = (height - 1 - y, x, height, width);
+        }
+        (x, y)
+    }
+}
+
+/// a small pattern of letters and wildcards that is tried in every [Orientation] - the letters
+/// have to match exactly, wildcards match anything (even cells outside the grid)
+#[derive(Debug, Clone)]
+pub struct Template {
+    /// the letters relative to the top-left corner - orientations that look exactly like an
+    /// earlier one are left out, so symmetric templates don't match the same cells twice
+    variants: Vec<(Orientation, Vec<(Vec2, char)>)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TemplateMatch {
+    /// top-left corner of the template as it was placed
+    pub anchor: Point,
+    pub orientation: Orientation,
+}
+
+impl Template {
+    /// one line per row, short rows are filled up with wildcards - panics if there isn't a single
+    /// letter
+    pub fn new(pattern: &str, wildcard: char) -> Self {
+        let rows: Vec<Vec<char>> = pattern.lines().map(|l| l.chars().collect()).collect();
+        let size = (
+            rows.iter().map(Vec::len).max().unwrap_or(0) as i32,
+            rows.len() as i32,
+        );
+        let letters: Vec<((i32, i32), char)> = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|&(_, &c)| c != wildcard)
+                    .map(move |(x, &c)| ((x as i32, y as i32), c))
+            })
+            .collect();
+        assert!(!letters.is_empty(), "a template needs at least one letter");
+
+        let mut variants: Vec<(Orientation, Vec<(Vec2, char)>)> = vec![];
+        for orientation in Orientation::ALL {
+            let mut cells: Vec<(Vec2, char)> = letters
+                .iter()
+                .map(|&(p, c)| {
+                    let (x, y) = orientation.apply(p, size);
+                    (Vec2::new(x, y), c)
+                })
+                .collect();
+            cells.sort_by_key(|(v, _)| (v.y, v.x));
+            if variants.iter().all(|(_, other)| *other != cells) {
+                variants.push((orientation, cells));
+            }
+        }
+        Template { variants }
+    }
+
+    /// the orientations that are actually tried
+    pub fn orientations(&self) -> impl Iterator<Item = Orientation> + '_ {
+        self.variants.iter().map(|(orientation, _)| *orientation)
+    }
+
+    /// every placement in every orientation, sorted by anchor
+    pub fn find(&self, grid: &impl GridAccess<char>) -> Vec<TemplateMatch> {
+        let mut matches = vec![];
+        for (p, c) in grid.iter() {
+            for (orientation, cells) in &self.variants {
+                // the first letter decides where the rest has to be
+                let (offset, first) = cells[0];
+                if *c != first {
+                    continue;
+                }
+                let anchor = p - offset;
+                let fits = cells[1..]
+                    .iter()
+                    .all(|&(offset, letter)| grid.get(&(anchor + offset)) == Some(&letter));
+                if fits {
+                    matches.push(TemplateMatch {
+                        anchor,
+                        orientation: *orientation,
+                    });
+                }
+            }
+        }
+        matches.sort_by_key(|m| (m.anchor.y, m.anchor.x, m.orientation));
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::grid::{DenseGrid, Grid};
+
+    use super::*;
+
+    const PUZZLE: &str = "CATS\nXOXA\nDOGT";
+
+    #[test]
+    fn should_find_many_words_at_once() {
+        let grid = Grid::from(PUZZLE);
+        let search = WordSearch::new(["CAT", "CATS", "DOG", "TAC", "COO", "CAT", ""]);
+
+        let found: Vec<(&str, Point, Direction)> = search
+            .find(&grid, &Direction::ALL)
+            .into_iter()
+            .map(|m| (search.words()[m.word].as_str(), m.start, m.direction))
+            .collect();
+
+        assert_eq!(
+            vec![
+                ("CAT", Point::new(0, 0), Direction::E),
+                ("CATS", Point::new(0, 0), Direction::E),
+                ("TAC", Point::new(2, 0), Direction::W),
+                ("DOG", Point::new(0, 2), Direction::E),
+            ],
+            found
+        );
+    }
+
+    #[test]
+    fn should_only_search_given_directions() {
+        let grid = DenseGrid::from("ABA\nBBB\nABA");
+        let search = WordSearch::new(["AB", "ABA"]);
+
+        assert_eq!(24, search.find(&grid, &Direction::ALL).len());
+        assert_eq!(16, search.find(&grid, &Direction::CARDINAL).len());
+        assert_eq!(8, search.find(&grid, &Direction::ORDINAL).len());
+        assert_eq!(4, search.find(&grid, &[Direction::E]).len());
+    }
+
+    #[test]
+    fn should_match_template_in_every_orientation() {
+        let grid = Grid::from("XY..\nX..X\n..YX\n....\nYX..\n.X..");
+        let corner = Template::new("XY\nX", ' ');
+
+        let found: Vec<(Point, Orientation)> = corner
+            .find(&grid)
+            .into_iter()
+            .map(|m| (m.anchor, m.orientation))
+            .collect();
+
+        assert_eq!(8, corner.orientations().count());
+        let orientation = |flipped, quarter_turns| Orientation {
+            flipped,
+            quarter_turns,
+        };
+        assert_eq!(
+            vec![
+                (Point::new(0, 0), orientation(false, 0)),
+                (Point::new(2, 1), orientation(false, 2)),
+                (Point::new(0, 4), orientation(true, 0)),
+            ],
+            found
+        );
+    }
+
+    #[test]
+    fn should_skip_symmetric_orientations() {
+        let x_mas = Template::new("M.S\n.A.\nM.S", '.');
+        let plus = Template::new(".A.\nAAA\n.A.", '.');
+
+        assert_eq!(4, x_mas.orientations().count());
+        assert_eq!(1, plus.orientations().count());
+        assert_eq!(
+            vec![Point::new(0, 0), Point::new(1, 1)],
+            plus.find(&Grid::from(".A.\nAAAA\n.AAA\n..A"))
+                .iter()
+                .map(|m| m.anchor)
+                .collect::<Vec<_>>()
+        );
+    }
+}